@@ -0,0 +1,71 @@
+//! Azure DevOps authentication
+
+use crate::auth::AuthSource;
+use crate::error::{Error, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::env;
+use tracing::debug;
+
+/// Azure DevOps authentication configuration
+#[derive(Debug, Clone)]
+pub struct AzureDevOpsAuthConfig {
+    /// Personal access token
+    pub token: String,
+    /// Where the token was obtained from
+    pub source: AuthSource,
+}
+
+/// Get Azure DevOps authentication
+///
+/// Priority:
+/// 1. `AZURE_DEVOPS_TOKEN` environment variable
+/// 2. `AZURE_DEVOPS_EXT_PAT` environment variable (shared with `az devops`)
+pub async fn get_azure_devops_auth() -> Result<AzureDevOpsAuthConfig> {
+    debug!("checking env vars for Azure DevOps token");
+    for var in ["AZURE_DEVOPS_TOKEN", "AZURE_DEVOPS_EXT_PAT"] {
+        if let Ok(token) = env::var(var) {
+            debug!(var, "obtained Azure DevOps token from env var");
+            return Ok(AzureDevOpsAuthConfig {
+                token,
+                source: AuthSource::EnvVar,
+            });
+        }
+    }
+
+    debug!("no Azure DevOps authentication found");
+    Err(Error::Auth(
+        "No Azure DevOps authentication found. Set AZURE_DEVOPS_TOKEN to a personal access token"
+            .to_string(),
+    ))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureProfile {
+    display_name: String,
+}
+
+/// Test Azure DevOps authentication
+pub async fn test_azure_devops_auth(config: &AzureDevOpsAuthConfig) -> Result<String> {
+    // Profiles live on the organization-independent VSSPS host
+    let url = "https://app.vssps.visualstudio.com/_apis/profile/profiles/me";
+
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| Error::AzureDevOpsApi(format!("failed to create HTTP client: {e}")))?;
+
+    let profile: AzureProfile = client
+        .get(url)
+        .basic_auth("", Some(&config.token))
+        .query(&[("api-version", "7.1")])
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| Error::Auth(format!("Invalid token: {e}")))?
+        .json()
+        .await?;
+
+    Ok(profile.display_name)
+}
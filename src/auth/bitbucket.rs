@@ -0,0 +1,85 @@
+//! Bitbucket Data Center / Server authentication
+
+use crate::auth::AuthSource;
+use crate::error::{Error, Result};
+use reqwest::Client;
+use std::env;
+use tracing::debug;
+
+/// Bitbucket Server authentication configuration
+#[derive(Debug, Clone)]
+pub struct BitbucketAuthConfig {
+    /// HTTP access token
+    pub token: String,
+    /// Where the token was obtained from
+    pub source: AuthSource,
+    /// Bitbucket host, including port and context path if any
+    /// (e.g., "bitbucket.example.com" or "git.example.com:8443/bitbucket")
+    pub host: String,
+}
+
+/// Get Bitbucket Server authentication
+///
+/// Bitbucket Server is always self-hosted, so the host must come from the
+/// remote URL or the `BITBUCKET_HOST` environment variable.
+///
+/// Priority:
+/// 1. `BITBUCKET_TOKEN` environment variable
+/// 2. `BITBUCKET_SERVER_TOKEN` environment variable
+pub async fn get_bitbucket_auth(host: Option<&str>) -> Result<BitbucketAuthConfig> {
+    let host = host
+        .map(String::from)
+        .or_else(|| env::var("BITBUCKET_HOST").ok())
+        .ok_or_else(|| {
+            Error::Auth(
+                "No Bitbucket host known. Set BITBUCKET_HOST to your Bitbucket Server host"
+                    .to_string(),
+            )
+        })?;
+
+    debug!(host = %host, "checking env vars for Bitbucket token");
+    for var in ["BITBUCKET_TOKEN", "BITBUCKET_SERVER_TOKEN"] {
+        if let Ok(token) = env::var(var) {
+            debug!(var, "obtained Bitbucket token from env var");
+            return Ok(BitbucketAuthConfig {
+                token,
+                source: AuthSource::EnvVar,
+                host,
+            });
+        }
+    }
+
+    debug!("no Bitbucket authentication found");
+    Err(Error::Auth(
+        "No Bitbucket authentication found. Set BITBUCKET_TOKEN to an HTTP access token"
+            .to_string(),
+    ))
+}
+
+/// Test Bitbucket Server authentication
+pub async fn test_bitbucket_auth(config: &BitbucketAuthConfig) -> Result<String> {
+    // `whoami` returns the authenticated username as plain text
+    let url = format!("https://{}/plugins/servlet/applinks/whoami", config.host);
+
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| Error::BitbucketApi(format!("failed to create HTTP client: {e}")))?;
+
+    let username = client
+        .get(&url)
+        .bearer_auth(&config.token)
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| Error::Auth(format!("Invalid token: {e}")))?
+        .text()
+        .await?;
+
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(Error::Auth("Invalid token: not authenticated".to_string()));
+    }
+
+    Ok(username.to_string())
+}
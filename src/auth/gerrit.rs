@@ -0,0 +1,89 @@
+//! Gerrit authentication
+
+use crate::auth::AuthSource;
+use crate::error::{Error, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::env;
+use tracing::debug;
+
+/// Gerrit authentication configuration
+#[derive(Debug, Clone)]
+pub struct GerritAuthConfig {
+    /// Gerrit account username
+    pub username: String,
+    /// HTTP password generated in Gerrit's settings
+    pub token: String,
+    /// Where the credentials were obtained from
+    pub source: AuthSource,
+    /// Gerrit REST host (e.g., "review.example.com")
+    pub host: String,
+}
+
+/// Get Gerrit authentication
+///
+/// The host comes from the remote URL or the `GERRIT_HOST` environment
+/// variable. Credentials are read from `GERRIT_USERNAME` and
+/// `GERRIT_HTTP_PASSWORD`.
+pub async fn get_gerrit_auth(host: Option<&str>) -> Result<GerritAuthConfig> {
+    let host = host
+        .map(String::from)
+        .or_else(|| env::var("GERRIT_HOST").ok())
+        .ok_or_else(|| {
+            Error::Auth("No Gerrit host known. Set GERRIT_HOST to your Gerrit host".to_string())
+        })?;
+
+    debug!(host = %host, "checking env vars for Gerrit credentials");
+    if let (Ok(username), Ok(token)) = (
+        env::var("GERRIT_USERNAME"),
+        env::var("GERRIT_HTTP_PASSWORD"),
+    ) {
+        debug!("obtained Gerrit credentials from env vars");
+        return Ok(GerritAuthConfig {
+            username,
+            token,
+            source: AuthSource::EnvVar,
+            host,
+        });
+    }
+
+    debug!("no Gerrit authentication found");
+    Err(Error::Auth(
+        "No Gerrit authentication found. Set GERRIT_USERNAME and GERRIT_HTTP_PASSWORD".to_string(),
+    ))
+}
+
+#[derive(Deserialize)]
+struct GerritAccount {
+    username: Option<String>,
+    name: Option<String>,
+}
+
+/// Test Gerrit authentication
+pub async fn test_gerrit_auth(config: &GerritAuthConfig) -> Result<String> {
+    let url = format!("https://{}/a/accounts/self", config.host);
+
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| Error::GerritApi(format!("failed to create HTTP client: {e}")))?;
+
+    let body = client
+        .get(&url)
+        .basic_auth(&config.username, Some(&config.token))
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| Error::Auth(format!("Invalid credentials: {e}")))?
+        .text()
+        .await?;
+
+    // Strip the XSSI guard Gerrit prefixes to JSON responses
+    let json = body.trim_start().strip_prefix(")]}'").unwrap_or(&body);
+    let account: GerritAccount = serde_json::from_str(json)?;
+
+    Ok(account
+        .username
+        .or(account.name)
+        .unwrap_or_else(|| config.username.clone()))
+}
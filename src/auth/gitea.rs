@@ -0,0 +1,85 @@
+//! Gitea/Forgejo authentication
+
+use crate::auth::AuthSource;
+use crate::error::{Error, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::env;
+use tracing::debug;
+
+/// Gitea authentication configuration
+#[derive(Debug, Clone)]
+pub struct GiteaAuthConfig {
+    /// Authentication token
+    pub token: String,
+    /// Where the token was obtained from
+    pub source: AuthSource,
+    /// Gitea host (e.g., "codeberg.org")
+    pub host: String,
+}
+
+/// Get Gitea authentication
+///
+/// Priority:
+/// 1. `GITEA_TOKEN` environment variable
+/// 2. `FORGEJO_TOKEN` environment variable
+///
+/// The `tea` CLI has no command for printing its stored token, so only
+/// environment variables are consulted.
+pub async fn get_gitea_auth(host: Option<&str>) -> Result<GiteaAuthConfig> {
+    let host = host
+        .map(String::from)
+        .or_else(|| env::var("GITEA_HOST").ok())
+        .unwrap_or_else(|| "gitea.com".to_string());
+
+    debug!(host = %host, "checking env vars for Gitea token");
+    if let Ok(token) = env::var("GITEA_TOKEN") {
+        debug!("obtained Gitea token from GITEA_TOKEN env var");
+        return Ok(GiteaAuthConfig {
+            token,
+            source: AuthSource::EnvVar,
+            host,
+        });
+    }
+
+    if let Ok(token) = env::var("FORGEJO_TOKEN") {
+        debug!("obtained Gitea token from FORGEJO_TOKEN env var");
+        return Ok(GiteaAuthConfig {
+            token,
+            source: AuthSource::EnvVar,
+            host,
+        });
+    }
+
+    debug!("no Gitea authentication found");
+    Err(Error::Auth(
+        "No Gitea authentication found. Set GITEA_TOKEN (or FORGEJO_TOKEN)".to_string(),
+    ))
+}
+
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: String,
+}
+
+/// Test Gitea authentication
+pub async fn test_gitea_auth(config: &GiteaAuthConfig) -> Result<String> {
+    let url = format!("https://{}/api/v1/user", config.host);
+
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| Error::GiteaApi(format!("failed to create HTTP client: {e}")))?;
+
+    let user: GiteaUser = client
+        .get(&url)
+        .header("Authorization", format!("token {}", config.token))
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|e| Error::Auth(format!("Invalid token: {e}")))?
+        .json()
+        .await?;
+
+    Ok(user.login)
+}
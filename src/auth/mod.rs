@@ -1,10 +1,19 @@
-//! Authentication for GitHub and GitLab
+//! Authentication for GitHub, GitLab, Gitea, Bitbucket Server, Azure DevOps and
+//! Gerrit
 //!
 //! Supports CLI-based auth (gh, glab) and environment variables.
 
+mod azure_devops;
+mod bitbucket;
+mod gerrit;
+mod gitea;
 mod github;
 mod gitlab;
 
+pub use azure_devops::{AzureDevOpsAuthConfig, get_azure_devops_auth, test_azure_devops_auth};
+pub use bitbucket::{BitbucketAuthConfig, get_bitbucket_auth, test_bitbucket_auth};
+pub use gerrit::{GerritAuthConfig, get_gerrit_auth, test_gerrit_auth};
+pub use gitea::{GiteaAuthConfig, get_gitea_auth, test_gitea_auth};
 pub use github::{GitHubAuthConfig, get_github_auth, test_github_auth};
 pub use gitlab::{GitLabAuthConfig, get_gitlab_auth, test_gitlab_auth};
 
@@ -1,11 +1,15 @@
 //! Default analyze command - print stack visualization
 
+use crate::cli::output::{emit, is_json, println};
+use crate::cli::status::describe_status;
 use crate::cli::style::{self, Stylize, check, pipe, up_arrow};
-use anstream::println;
 use jj_ryu::error::Result;
 use jj_ryu::graph::build_change_graph;
+use jj_ryu::output::{DocumentKind, StackDocument};
 use jj_ryu::repo::JjWorkspace;
-use jj_ryu::tracking::{load_pr_cache, load_tracking};
+use jj_ryu::tracking::{PrCache, TrackingState, load_pr_cache, load_tracking};
+use jj_ryu::types::{BranchStack, ChangeGraph, PrStatus};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Run the analyze command (default when no subcommand given)
@@ -24,6 +28,23 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
     // Build change graph from working copy
     let graph = build_change_graph(&workspace)?;
 
+    if is_json() {
+        let tracked = tracking.tracked_names();
+        let prs = pr_cache
+            .prs
+            .iter()
+            .filter(|p| tracking.is_tracked(&p.bookmark))
+            .collect();
+        return emit(
+            DocumentKind::Stack,
+            StackDocument {
+                graph: &graph,
+                tracked,
+                prs,
+            },
+        );
+    }
+
     let Some(stack) = &graph.stack else {
         println!("{}", "No bookmark stack found".muted());
         println!();
@@ -35,6 +56,7 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
             "{}",
             "Create a bookmark with: jj bookmark create <name>".muted()
         );
+        print_other_stacks(&graph);
         return Ok(());
     };
 
@@ -43,6 +65,114 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
         return Ok(());
     }
 
+    print_stack(stack, &tracking, &pr_cache, &HashMap::new());
+    println!();
+
+    // Summary - count tracked vs total
+    let total_bookmarks = stack.segments.iter().flat_map(|s| &s.bookmarks).count();
+    let tracked_count = stack
+        .segments
+        .iter()
+        .flat_map(|s| &s.bookmarks)
+        .filter(|b| tracking.is_tracked(&b.name))
+        .count();
+    let untracked_count = total_bookmarks - tracked_count;
+
+    if tracked_count > 0 {
+        println!(
+            "{} bookmark{} ({} tracked)",
+            total_bookmarks.accent(),
+            if total_bookmarks == 1 { "" } else { "s" },
+            tracked_count
+        );
+    } else {
+        println!(
+            "{} bookmark{}",
+            total_bookmarks.accent(),
+            if total_bookmarks == 1 { "" } else { "s" }
+        );
+    }
+
+    if graph.excluded_bookmark_count > 0 {
+        println!(
+            "{}",
+            format!(
+                "({} bookmark{} excluded due to merge commits)",
+                graph.excluded_bookmark_count,
+                if graph.excluded_bookmark_count == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            )
+            .muted()
+        );
+    }
+
+    print_other_stacks(&graph);
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "Legend: {} = tracked synced, {} = tracked needs push, · = untracked, {} = working copy",
+            style::CHECK,
+            style::UP_ARROW,
+            style::CURRENT
+        )
+        .muted()
+    );
+
+    // Hint about tracking if untracked bookmarks exist
+    if untracked_count > 0 {
+        println!();
+        println!(
+            "{}",
+            "(use 'ryu track' to track untracked bookmarks)".muted()
+        );
+    }
+
+    println!();
+    println!("To submit this stack: {}", "ryu submit".accent());
+
+    Ok(())
+}
+
+/// List the stacks besides the working copy's, bottom bookmark first
+fn print_other_stacks(graph: &ChangeGraph) {
+    let skip = usize::from(graph.stack.is_some());
+    if graph.stacks.len() <= skip {
+        return;
+    }
+
+    println!();
+    println!("{}:", "Other stacks".emphasis());
+    for stack in &graph.stacks[skip..] {
+        let submit = format!("ryu submit {}", stack.leaf_bookmark().unwrap_or_default());
+        println!("  {}  {}", stack_outline(stack).accent(), submit.muted());
+    }
+}
+
+/// A stack's bookmarks on one line, bottom first
+pub fn stack_outline(stack: &BranchStack) -> String {
+    let names: Vec<&str> = stack
+        .segments
+        .iter()
+        .filter_map(|s| s.bookmarks.first())
+        .map(|b| b.name.as_str())
+        .collect();
+    names.join(" → ")
+}
+
+/// Print the stack from its leaf down to trunk
+///
+/// Bookmarks with an entry in `statuses` show their PR's live status.
+pub fn print_stack(
+    stack: &BranchStack,
+    tracking: &TrackingState,
+    pr_cache: &PrCache,
+    statuses: &HashMap<String, PrStatus>,
+) {
     // Print header
     let leaf = stack.segments.last().unwrap();
     let leaf_name = &leaf.bookmarks[0].name;
@@ -50,9 +180,18 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
     println!();
 
     // Print each segment in reverse order (newest/leaf first, oldest last)
-    for segment in stack.segments.iter().rev() {
+    for (index, segment) in stack.segments.iter().enumerate().rev() {
         let bookmark_names: Vec<&str> = segment.bookmarks.iter().map(|b| b.name.as_str()).collect();
 
+        // Where the stack branches, name the bookmark a segment builds on
+        // unless it's the one printed right below
+        let on = stack
+            .parent_index(index)
+            .filter(|&parent| parent + 1 != index)
+            .and_then(|parent| stack.segments[parent].bookmarks.first())
+            .map(|b| format!(" (on {})", b.name).muted().to_string())
+            .unwrap_or_default();
+
         // Print commits in segment (already newest-first from revset)
         for (j, change) in segment.changes.iter().enumerate() {
             let is_first_in_segment = j == 0;
@@ -81,7 +220,8 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
 
             // Show bookmark on first commit of segment (the tip)
             if is_first_in_segment && !bookmark_names.is_empty() {
-                for bm in &bookmark_names {
+                for (k, bm) in bookmark_names.iter().enumerate() {
+                    let on = if k == 0 { on.as_str() } else { "" };
                     let bookmark = segment.bookmarks.iter().find(|b| b.name == *bm).unwrap();
                     let is_tracked = tracking.is_tracked(bm);
 
@@ -97,11 +237,29 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
                         format!(" {}", "·".muted())
                     };
 
-                    // PR number from cache (tracked only)
+                    // Live PR status if fetched, else the PR number from
+                    // the cache (tracked only)
+                    if let Some(pr) = statuses.get(*bm) {
+                        let name = if is_tracked { bm.accent() } else { bm.muted() };
+                        println!(
+                            "       [{}{}]{}{}  {}",
+                            name,
+                            format!(" #{}", pr.pr.number).muted(),
+                            status,
+                            on,
+                            describe_status(pr)
+                        );
+                        continue;
+                    }
                     let pr_info = if is_tracked {
                         pr_cache
                             .get(bm)
-                            .map(|p| format!(" #{}", p.number))
+                            .map(|p| {
+                                p.auto_merge.map_or_else(
+                                    || format!(" #{}", p.number),
+                                    |method| format!(" #{} auto-merge ({method})", p.number),
+                                )
+                            })
                             .unwrap_or_default()
                     } else {
                         String::new()
@@ -109,9 +267,15 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
 
                     // Dim untracked bookmark names
                     if is_tracked {
-                        println!("       [{}{}]{}", bm.accent(), pr_info.muted(), status);
+                        println!(
+                            "       [{}{}]{}{}",
+                            bm.accent(),
+                            pr_info.muted(),
+                            status,
+                            on
+                        );
                     } else {
-                        println!("       [{}]{}", bm.muted(), status);
+                        println!("       [{}]{}{}", bm.muted(), status, on);
                     }
                 }
             }
@@ -128,72 +292,4 @@ pub async fn run_analyze(path: &Path) -> Result<()> {
 
     // Print trunk base at bottom
     println!("  {}", "trunk()".muted());
-    println!();
-
-    // Summary - count tracked vs total
-    let total_bookmarks = stack.segments.iter().flat_map(|s| &s.bookmarks).count();
-    let tracked_count = stack
-        .segments
-        .iter()
-        .flat_map(|s| &s.bookmarks)
-        .filter(|b| tracking.is_tracked(&b.name))
-        .count();
-    let untracked_count = total_bookmarks - tracked_count;
-
-    if tracked_count > 0 {
-        println!(
-            "{} bookmark{} ({} tracked)",
-            total_bookmarks.accent(),
-            if total_bookmarks == 1 { "" } else { "s" },
-            tracked_count
-        );
-    } else {
-        println!(
-            "{} bookmark{}",
-            total_bookmarks.accent(),
-            if total_bookmarks == 1 { "" } else { "s" }
-        );
-    }
-
-    if graph.excluded_bookmark_count > 0 {
-        println!(
-            "{}",
-            format!(
-                "({} bookmark{} excluded due to merge commits)",
-                graph.excluded_bookmark_count,
-                if graph.excluded_bookmark_count == 1 {
-                    ""
-                } else {
-                    "s"
-                }
-            )
-            .muted()
-        );
-    }
-
-    println!();
-    println!(
-        "{}",
-        format!(
-            "Legend: {} = tracked synced, {} = tracked needs push, · = untracked, {} = working copy",
-            style::CHECK,
-            style::UP_ARROW,
-            style::CURRENT
-        )
-        .muted()
-    );
-
-    // Hint about tracking if untracked bookmarks exist
-    if untracked_count > 0 {
-        println!();
-        println!(
-            "{}",
-            "(use 'ryu track' to track untracked bookmarks)".muted()
-        );
-    }
-
-    println!();
-    println!("To submit this stack: {}", "ryu submit".accent());
-
-    Ok(())
 }
@@ -1,9 +1,13 @@
 //! Auth command - test and manage authentication
 
+use crate::cli::output::println;
 use crate::cli::style::{Stylize, check, spinner_style};
-use anstream::println;
 use indicatif::ProgressBar;
-use jj_ryu::auth::{get_github_auth, get_gitlab_auth, test_github_auth, test_gitlab_auth};
+use jj_ryu::auth::{
+    get_azure_devops_auth, get_bitbucket_auth, get_gerrit_auth, get_gitea_auth, get_github_auth,
+    get_gitlab_auth, test_azure_devops_auth, test_bitbucket_auth, test_gerrit_auth,
+    test_gitea_auth, test_github_auth, test_gitlab_auth,
+};
 use jj_ryu::error::Result;
 use jj_ryu::types::Platform;
 use std::time::Duration;
@@ -38,11 +42,70 @@ pub async fn run_auth_test(platform: Platform) -> Result<()> {
             println!("  {} {:?}", "Token source:".muted(), config.source);
             println!("  {} {}", "Host:".muted(), config.host);
         }
+        Platform::Gitea => {
+            let spinner = ProgressBar::new_spinner();
+            spinner.set_style(spinner_style());
+            spinner.set_message("Testing Gitea authentication...");
+            spinner.enable_steady_tick(Duration::from_millis(80));
+
+            let config = get_gitea_auth(None).await?;
+            let username = test_gitea_auth(&config).await?;
+
+            spinner.finish_and_clear();
+            println!("{} Authenticated as: {}", check(), username.accent());
+            println!("  {} {:?}", "Token source:".muted(), config.source);
+            println!("  {} {}", "Host:".muted(), config.host);
+        }
+        Platform::Bitbucket => {
+            let spinner = ProgressBar::new_spinner();
+            spinner.set_style(spinner_style());
+            spinner.set_message("Testing Bitbucket authentication...");
+            spinner.enable_steady_tick(Duration::from_millis(80));
+
+            let config = get_bitbucket_auth(None).await?;
+            let username = test_bitbucket_auth(&config).await?;
+
+            spinner.finish_and_clear();
+            println!("{} Authenticated as: {}", check(), username.accent());
+            println!("  {} {:?}", "Token source:".muted(), config.source);
+            println!("  {} {}", "Host:".muted(), config.host);
+        }
+        Platform::AzureDevOps => {
+            let spinner = ProgressBar::new_spinner();
+            spinner.set_style(spinner_style());
+            spinner.set_message("Testing Azure DevOps authentication...");
+            spinner.enable_steady_tick(Duration::from_millis(80));
+
+            let config = get_azure_devops_auth().await?;
+            let username = test_azure_devops_auth(&config).await?;
+
+            spinner.finish_and_clear();
+            println!("{} Authenticated as: {}", check(), username.accent());
+            println!("  {} {:?}", "Token source:".muted(), config.source);
+        }
+        Platform::Gerrit => {
+            let spinner = ProgressBar::new_spinner();
+            spinner.set_style(spinner_style());
+            spinner.set_message("Testing Gerrit authentication...");
+            spinner.enable_steady_tick(Duration::from_millis(80));
+
+            let config = get_gerrit_auth(None).await?;
+            let username = test_gerrit_auth(&config).await?;
+
+            spinner.finish_and_clear();
+            println!("{} Authenticated as: {}", check(), username.accent());
+            println!("  {} {:?}", "Token source:".muted(), config.source);
+            println!("  {} {}", "Host:".muted(), config.host);
+        }
+        Platform::Local => {
+            println!("{} The local forge needs no authentication", check());
+        }
     }
     Ok(())
 }
 
 /// Run the auth setup command (show instructions)
+#[allow(clippy::too_many_lines)]
 pub fn run_auth_setup(platform: Platform) {
     match platform {
         Platform::GitHub => {
@@ -82,6 +145,98 @@ pub fn run_auth_setup(platform: Platform) {
             println!("{}", "For self-hosted GitLab:".muted());
             println!("  {}", "Set GITLAB_HOST to your instance hostname".muted());
         }
+        Platform::Gitea => {
+            println!("{}", "Gitea / Forgejo Authentication Setup".emphasis());
+            println!();
+            println!("{}", "Create an access token".emphasis());
+            println!(
+                "  In your instance: {}",
+                "Settings → Applications → Generate New Token".accent()
+            );
+            println!("  Grant read/write access to repositories and issues");
+            println!();
+            println!("{}", "Export it as an environment variable".emphasis());
+            println!(
+                "  Set {} or {}",
+                "GITEA_TOKEN".accent(),
+                "FORGEJO_TOKEN".accent()
+            );
+            println!();
+            println!("{}", "For self-hosted Gitea/Forgejo:".muted());
+            println!("  {}", "Set GITEA_HOST to your instance hostname".muted());
+        }
+        Platform::Bitbucket => {
+            println!(
+                "{}",
+                "Bitbucket Data Center / Server Authentication Setup".emphasis()
+            );
+            println!();
+            println!("{}", "Create an HTTP access token".emphasis());
+            println!(
+                "  In your instance: {}",
+                "Manage account → HTTP access tokens → Create token".accent()
+            );
+            println!("  Grant project read and repository write permissions");
+            println!();
+            println!("{}", "Export it as an environment variable".emphasis());
+            println!(
+                "  Set {} or {}",
+                "BITBUCKET_TOKEN".accent(),
+                "BITBUCKET_SERVER_TOKEN".accent()
+            );
+            println!();
+            println!("{}", "Set the server host:".muted());
+            println!(
+                "  {}",
+                "Set BITBUCKET_HOST to your instance hostname (and context path)".muted()
+            );
+        }
+        Platform::AzureDevOps => {
+            println!("{}", "Azure DevOps Authentication Setup".emphasis());
+            println!();
+            println!("{}", "Create a personal access token".emphasis());
+            println!(
+                "  In your organization: {}",
+                "User settings → Personal access tokens → New Token".accent()
+            );
+            println!("  Grant the Code (Read & write) scope");
+            println!();
+            println!("{}", "Export it as an environment variable".emphasis());
+            println!(
+                "  Set {} or {}",
+                "AZURE_DEVOPS_TOKEN".accent(),
+                "AZURE_DEVOPS_EXT_PAT".accent()
+            );
+        }
+        Platform::Gerrit => {
+            println!("{}", "Gerrit Authentication Setup".emphasis());
+            println!();
+            println!("{}", "Generate an HTTP password".emphasis());
+            println!(
+                "  In your instance: {}",
+                "Settings → HTTP Credentials → Generate new password".accent()
+            );
+            println!();
+            println!("{}", "Export your credentials".emphasis());
+            println!(
+                "  Set {} and {}",
+                "GERRIT_USERNAME".accent(),
+                "GERRIT_HTTP_PASSWORD".accent()
+            );
+            println!();
+            println!("{}", "Set the server host:".muted());
+            println!(
+                "  {}",
+                "Set GERRIT_HOST to your instance hostname (not needed for googlesource.com)"
+                    .muted()
+            );
+        }
+        Platform::Local => {
+            println!("{}", "Local Forge Setup".emphasis());
+            println!();
+            println!("  The local forge needs no credentials. Use a file:// remote or run:");
+            println!("  {}", "jj config set --repo ryu.platform local".accent());
+        }
     }
 }
 
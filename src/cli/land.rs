@@ -0,0 +1,423 @@
+//! Land command - merge the bottom of the stack and restack the rest
+
+use crate::cli::CliProgress;
+use crate::cli::output::{emit, println};
+use crate::cli::remote::{SubmitRemotes, connect_platform, select_remotes};
+use crate::cli::style::{CHECK, Stylize, arrow, check, spinner_style};
+use crate::cli::sync::{clean_up_finished, fetch_remotes};
+use indicatif::ProgressBar;
+use jj_ryu::error::{Error, Result};
+use jj_ryu::graph::build_change_graph;
+use jj_ryu::output::{DocumentKind, LandDocument};
+use jj_ryu::platform::PlatformService;
+use jj_ryu::repo::JjWorkspace;
+use jj_ryu::submit::{
+    FinishedBookmark, FinishedPrs, LandPlan, LandTarget, MERGE_METHOD_CONFIG_KEY,
+    analyze_submission, create_submission_plan, ensure_ready, execute_submission, plan_landing,
+};
+use jj_ryu::tracking::{load_pr_cache, load_tracking, save_pr_cache};
+use jj_ryu::types::{MergeMethod, Mergeability, PullRequest};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Interval between mergeability checks while CI runs
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Longest wait for checks on one PR before giving up
+const WAIT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Options for the land command
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct LandOptions {
+    /// Land up to (and including) this bookmark
+    pub bookmark: Option<String>,
+    /// Land every PR up to the bookmark (or the whole stack), not just the lowest
+    pub all: bool,
+    /// Merge method, overriding `ryu.merge-method`
+    pub method: Option<MergeMethod>,
+    /// Add the bottom PR to the merge queue instead of merging it
+    pub queue: bool,
+    /// Take a PR out of the merge queue
+    pub dequeue: bool,
+    /// Dry run - show what would be landed without merging
+    pub dry_run: bool,
+    /// Remote to push bookmarks to, when PRs go to another remote (fork workflow)
+    pub push_remote: Option<String>,
+}
+
+/// Merge method from `ryu.merge-method`, squash if unset
+pub fn configured_merge_method(workspace: &JjWorkspace) -> Result<MergeMethod> {
+    Ok(workspace
+        .config_string(MERGE_METHOD_CONFIG_KEY)
+        .map(|m| m.parse())
+        .transpose()?
+        .unwrap_or_default())
+}
+
+/// Run the land command
+pub async fn run_land(path: &Path, remote: Option<&str>, options: LandOptions) -> Result<()> {
+    let mut workspace = JjWorkspace::open(path)?;
+
+    let method = match options.method {
+        Some(method) => method,
+        None => configured_merge_method(&workspace)?,
+    };
+
+    let (remotes, mut platform) = connect_platform(select_remotes(
+        &workspace,
+        remote,
+        options.push_remote.as_deref(),
+    )?)
+    .await?;
+    platform.set_retry_progress(Arc::new(CliProgress::compact()));
+
+    let mut doc = LandDocument {
+        dry_run: options.dry_run,
+        ..LandDocument::default()
+    };
+    if options.dequeue {
+        let pr = dequeue(&mut workspace, platform.as_ref(), &remotes, &options).await?;
+        doc.dequeued.push(pr);
+        return emit(DocumentKind::Land, &doc);
+    }
+
+    let mut upto = options.bookmark.clone();
+    let mut all = options.all;
+    loop {
+        if !options.dry_run {
+            fetch_remotes(&mut workspace, &remotes)?;
+        }
+        let default_branch = workspace.default_branch()?;
+        let graph = build_change_graph(&workspace)?;
+        let plan = plan_landing(
+            &graph,
+            platform.as_ref(),
+            &default_branch,
+            upto.as_deref(),
+            all,
+        )
+        .await?;
+
+        if options.dry_run {
+            let mergeability = platform.get_mergeability(plan.targets[0].pr.number).await?;
+            if options.queue {
+                print_queue_plan(&plan.targets[0], &mergeability);
+                doc.planned.push(plan.targets[0].clone());
+            } else {
+                print_land_plan(&plan, &mergeability, method, &default_branch);
+                doc.planned = plan.targets;
+            }
+            println!("{}", "Dry run complete".muted());
+            return emit(DocumentKind::Land, &doc);
+        }
+
+        if options.queue {
+            enqueue_bottom(
+                workspace.workspace_root(),
+                platform.as_ref(),
+                &remotes,
+                &plan.targets[0],
+            )
+            .await?;
+            doc.queued.push(plan.targets[0].pr.clone());
+            return emit(DocumentKind::Land, &doc);
+        }
+
+        // Later rounds land the rest of the same targets, bottom first
+        let last = plan.targets.len() == 1;
+        upto = plan.targets.last().map(|t| t.bookmark.clone());
+        all = true;
+
+        land_bottom(
+            &mut workspace,
+            platform.as_ref(),
+            &remotes,
+            &plan,
+            method,
+            &default_branch,
+        )
+        .await?;
+        doc.merged.push(plan.targets[0].pr.clone());
+
+        if last {
+            break;
+        }
+        println!();
+    }
+
+    let landed = doc.merged.len();
+    println!();
+    println!(
+        "{} {} PR{} merged",
+        format!("{CHECK} Land complete:").success(),
+        landed.accent(),
+        if landed == 1 { "" } else { "s" }
+    );
+    emit(DocumentKind::Land, &doc)
+}
+
+/// Merge the lowest PR of `plan`, then rebase and push what's left above it
+async fn land_bottom(
+    workspace: &mut JjWorkspace,
+    platform: &dyn PlatformService,
+    remotes: &SubmitRemotes,
+    plan: &LandPlan,
+    method: MergeMethod,
+    default_branch: &str,
+) -> Result<()> {
+    let target = &plan.targets[0];
+    wait_until_ready(platform, target).await?;
+
+    platform.merge_pr(target.pr.number, method).await?;
+    println!(
+        "{} Merged #{} {} into {} ({method})",
+        check(),
+        target.pr.number,
+        target.bookmark.accent(),
+        default_branch
+    );
+
+    let retargeted = plan.retargeted();
+    if retargeted.is_empty() {
+        // Nothing stacked above: drop the landed change and finish
+        fetch_remotes(workspace, remotes)?;
+        return drop_landed(workspace, target);
+    }
+
+    for next in retargeted {
+        platform
+            .update_pr_base(next.pr.number, default_branch)
+            .await?;
+        println!(
+            "{} Retargeted #{} {} to {}",
+            check(),
+            next.pr.number,
+            next.bookmark.accent(),
+            default_branch
+        );
+    }
+
+    fetch_remotes(workspace, remotes)?;
+    drop_landed(workspace, target)?;
+
+    // Push the rebased PRs and refresh their bases and stack comments
+    let open: Vec<&str> = plan.targets[1..]
+        .iter()
+        .chain(&plan.rest)
+        .map(|t| t.bookmark.as_str())
+        .collect();
+    let graph = build_change_graph(workspace)?;
+    if graph.stack.is_none() {
+        return Ok(());
+    }
+    let mut analysis = analyze_submission(&graph, None)?;
+    analysis
+        .segments
+        .retain(|s| open.contains(&s.bookmark.name.as_str()));
+    if analysis.segments.is_empty() {
+        return Ok(());
+    }
+
+    let mut submission =
+        create_submission_plan(&analysis, platform, &remotes.push_remote, default_branch).await?;
+    if remotes.is_fork() {
+        submission.pr_remote = Some(remotes.pr_remote.clone());
+    }
+    let result = execute_submission(
+        &submission,
+        workspace,
+        platform,
+        &CliProgress::compact(),
+        false,
+    )
+    .await?;
+    // Landing the next PR on top of a stale remote stack would merge the
+    // wrong commits
+    if !result.success {
+        return Err(Error::Platform(format!(
+            "failed to update the stack above #{}: {}",
+            target.pr.number,
+            result.errors.join("; ")
+        )));
+    }
+    Ok(())
+}
+
+/// Add the bottom PR to the merge queue once it's ready
+///
+/// The queue merges it later; `ryu sync` then restacks the rest and reports
+/// if it leaves the queue without merging.
+async fn enqueue_bottom(
+    workspace_root: &Path,
+    platform: &dyn PlatformService,
+    remotes: &SubmitRemotes,
+    target: &LandTarget,
+) -> Result<()> {
+    wait_until_ready(platform, target).await?;
+    platform.enqueue_pr(target.pr.number).await?;
+
+    let mut pr_cache = load_pr_cache(workspace_root).unwrap_or_default();
+    pr_cache.upsert(&target.bookmark, &target.pr, &remotes.pr_remote);
+    pr_cache.set_queued(&target.bookmark, true);
+    // Best effort - sync just won't report on it if the cache write fails
+    let _ = save_pr_cache(workspace_root, &pr_cache);
+
+    println!(
+        "{} Added #{} {} to the merge queue",
+        check(),
+        target.pr.number,
+        target.bookmark.accent()
+    );
+    println!(
+        "{}",
+        "Run 'ryu sync' once it merges to restack the rest of the stack".muted()
+    );
+    Ok(())
+}
+
+/// Take a bookmark's PR (the bottom one by default) out of the merge queue
+///
+/// Returns the PR, which a dry run leaves in the queue.
+async fn dequeue(
+    workspace: &mut JjWorkspace,
+    platform: &dyn PlatformService,
+    remotes: &SubmitRemotes,
+    options: &LandOptions,
+) -> Result<PullRequest> {
+    fetch_remotes(workspace, remotes)?;
+    let graph = build_change_graph(workspace)?;
+    let (bookmark, pr) = if let Some(name) = &options.bookmark {
+        let bookmark = graph
+            .bookmarks
+            .get(name)
+            .ok_or_else(|| Error::BookmarkNotFound(name.clone()))?;
+        let pr = platform
+            .find_existing_pr(&platform.review_head(bookmark))
+            .await?
+            .ok_or_else(|| Error::InvalidArgument(format!("bookmark '{name}' has no open PR")))?;
+        (name.clone(), pr)
+    } else {
+        let default_branch = workspace.default_branch()?;
+        let mut plan = plan_landing(&graph, platform, &default_branch, None, false).await?;
+        let target = plan.targets.remove(0);
+        (target.bookmark, target.pr)
+    };
+
+    if options.dry_run {
+        println!(
+            "{} Would remove #{} {} from the merge queue",
+            arrow(),
+            pr.number,
+            bookmark.accent()
+        );
+        return Ok(pr);
+    }
+
+    platform.dequeue_pr(pr.number).await?;
+    let workspace_root = workspace.workspace_root();
+    let mut pr_cache = load_pr_cache(workspace_root).unwrap_or_default();
+    pr_cache.set_queued(&bookmark, false);
+    let _ = save_pr_cache(workspace_root, &pr_cache);
+
+    println!(
+        "{} Removed #{} {} from the merge queue",
+        check(),
+        pr.number,
+        bookmark.accent()
+    );
+    Ok(pr)
+}
+
+/// Drop a merged PR's changes from the stack and stop tracking its bookmark
+fn drop_landed(workspace: &mut JjWorkspace, target: &LandTarget) -> Result<()> {
+    let mut tracking = load_tracking(workspace.workspace_root())?;
+    let finished = FinishedPrs {
+        landed: vec![FinishedBookmark {
+            bookmark: target.bookmark.clone(),
+            pr: target.pr.clone(),
+            merged: true,
+            commit_id: target.commit_ids.first().cloned(),
+        }],
+        landed_commits: target.commit_ids.clone(),
+        deleted_bookmarks: target.bookmarks.clone(),
+        ..FinishedPrs::default()
+    };
+    clean_up_finished(workspace, &mut tracking, &finished)
+}
+
+/// Wait for pending checks on `target`, then fail unless it can merge
+async fn wait_until_ready(platform: &dyn PlatformService, target: &LandTarget) -> Result<()> {
+    let number = target.pr.number;
+    let mut mergeability = platform.get_mergeability(number).await?;
+
+    if mergeability.is_pending() {
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(spinner_style());
+        spinner.set_message(format!(
+            "Waiting for checks on #{number} {}...",
+            target.bookmark.emphasis()
+        ));
+        spinner.enable_steady_tick(Duration::from_millis(80));
+
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        while mergeability.is_pending() && Instant::now() < deadline {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            mergeability = platform.get_mergeability(number).await?;
+        }
+        spinner.finish_and_clear();
+    }
+
+    ensure_ready(&target.pr, &mergeability)
+}
+
+/// Print what `ryu land --queue` would enqueue
+fn print_queue_plan(target: &LandTarget, mergeability: &Mergeability) {
+    let status = match ensure_ready(&target.pr, mergeability) {
+        Ok(()) => "ready".success().to_string(),
+        Err(e) => e.to_string().warn().to_string(),
+    };
+    println!(
+        "  {} Add #{} {} to the merge queue {status}",
+        arrow(),
+        target.pr.number,
+        target.bookmark.accent()
+    );
+    println!();
+}
+
+/// Print what `ryu land` would merge
+fn print_land_plan(
+    plan: &LandPlan,
+    mergeability: &Mergeability,
+    method: MergeMethod,
+    default_branch: &str,
+) {
+    println!("{} ({method}):", "Land plan".emphasis());
+    for (i, target) in plan.targets.iter().enumerate() {
+        let status = if i > 0 {
+            "after rebasing".muted().to_string()
+        } else {
+            match ensure_ready(&target.pr, mergeability) {
+                Ok(()) => "ready".success().to_string(),
+                Err(e) => e.to_string().warn().to_string(),
+            }
+        };
+        println!(
+            "  {} Merge #{} {} into {default_branch} {status}",
+            arrow(),
+            target.pr.number,
+            target.bookmark.accent()
+        );
+    }
+    for target in &plan.rest {
+        println!(
+            "  {} Rebase #{} {} onto {default_branch}",
+            arrow(),
+            target.pr.number,
+            target.bookmark.accent()
+        );
+    }
+    println!();
+}
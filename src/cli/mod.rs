@@ -4,7 +4,11 @@
 
 mod analyze;
 mod auth;
+mod land;
+mod output;
 mod progress;
+mod remote;
+mod status;
 pub mod style;
 mod submit;
 mod sync;
@@ -13,7 +17,10 @@ mod untrack;
 
 pub use analyze::run_analyze;
 pub use auth::run_auth;
+pub use land::{LandOptions, run_land};
+pub use output::{OutputFormat, set_output_format};
 pub use progress::CliProgress;
+pub use status::run_status;
 pub use submit::{SubmitOptions, SubmitScope, run_submit};
 pub use sync::{SyncOptions, run_sync};
 pub use track::{TrackOptions, run_track};
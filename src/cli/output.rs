@@ -0,0 +1,71 @@
+//! Output format selection
+//!
+//! With `--output json` a command prints one JSON document (see
+//! [`jj_ryu::output`]) to stdout. The `println!` and `print!` macros here
+//! stand in for anstream's: in text mode they write to stdout as usual, in
+//! JSON mode to stderr, so progress and messages never mix with the document.
+
+use clap::ValueEnum;
+use jj_ryu::error::Result;
+use jj_ryu::output::{Document, DocumentKind};
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Format of a command's output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Styled text for people
+    #[default]
+    Text,
+    /// One versioned JSON document on stdout
+    Json,
+}
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Select the output format for the rest of the process
+pub fn set_output_format(format: OutputFormat) {
+    JSON_OUTPUT.store(format == OutputFormat::Json, Ordering::Relaxed);
+}
+
+/// Whether `--output json` was given
+pub fn is_json() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Print a document to stdout, if `--output json` was given
+pub fn emit<T: Serialize>(kind: DocumentKind, data: T) -> Result<()> {
+    if !is_json() {
+        return Ok(());
+    }
+    let json = Document::new(kind, data).to_json()?;
+    let mut stdout = std::io::stdout().lock();
+    // Nothing sensible to do if stdout is gone
+    let _ = writeln!(stdout, "{json}");
+    Ok(())
+}
+
+/// `anstream::println!`, on stderr in JSON mode
+macro_rules! println {
+    ($($arg:tt)*) => {
+        if $crate::cli::output::is_json() {
+            ::anstream::eprintln!($($arg)*)
+        } else {
+            ::anstream::println!($($arg)*)
+        }
+    };
+}
+
+/// `anstream::print!`, on stderr in JSON mode
+macro_rules! print {
+    ($($arg:tt)*) => {
+        if $crate::cli::output::is_json() {
+            ::anstream::eprint!($($arg)*)
+        } else {
+            ::anstream::print!($($arg)*)
+        }
+    };
+}
+
+pub(crate) use {print, println};
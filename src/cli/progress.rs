@@ -1,7 +1,8 @@
 //! Shared CLI progress callback with styled output and spinners
 
+use crate::cli::output::{print, println};
 use crate::cli::style::{Stream, Stylize, check, cross, hyperlink_url};
-use anstream::{eprintln, print, println};
+use anstream::eprintln;
 use async_trait::async_trait;
 use jj_ryu::error::Error;
 use jj_ryu::submit::{Phase, ProgressCallback, PushStatus};
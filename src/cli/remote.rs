@@ -0,0 +1,131 @@
+//! Remote selection - where bookmarks are pushed and where PRs are opened
+
+use jj_ryu::error::{Error, Result};
+use jj_ryu::platform::{
+    PLATFORM_CONFIG_KEY, PlatformService, create_workspace_platform_service,
+    resolve_platform_config,
+};
+use jj_ryu::repo::{JjWorkspace, select_remote};
+use jj_ryu::types::{Platform, PlatformConfig};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// jj config key for the default push remote (e.g. `ryu.push-remote = "fork"`)
+const PUSH_REMOTE_CONFIG_KEY: &str = "ryu.push-remote";
+
+/// Remotes used by a submission
+#[derive(Debug, Clone)]
+pub struct SubmitRemotes {
+    /// Remote PRs are opened against (e.g. `upstream`)
+    pub pr_remote: String,
+    /// Remote bookmarks are pushed to (e.g. `fork`); same as `pr_remote` unless forking
+    pub push_remote: String,
+}
+
+impl SubmitRemotes {
+    /// Whether PRs are opened from a fork
+    pub fn is_fork(&self) -> bool {
+        self.push_remote != self.pr_remote
+    }
+}
+
+/// Remotes and platforms selected for a submission, before connecting
+pub struct RemoteSelection {
+    remotes: SubmitRemotes,
+    platform_config: PlatformConfig,
+    /// Platform config of the push remote, when forking
+    head_config: Option<PlatformConfig>,
+    /// `Change-Id` trailers of the stack's commits, for Gerrit
+    change_id_trailers: HashMap<String, String>,
+    workspace_root: PathBuf,
+}
+
+/// Select the PR and push remotes and detect their platforms
+///
+/// The push remote comes from `--push-remote`, then the `ryu.push-remote`
+/// setting, and defaults to the PR remote.
+pub fn select_remotes(
+    workspace: &JjWorkspace,
+    remote: Option<&str>,
+    push_remote: Option<&str>,
+) -> Result<RemoteSelection> {
+    let remotes = workspace.git_remotes()?;
+    let pr_remote = select_remote(&remotes, remote)?;
+    let push_remote = match push_remote
+        .map(String::from)
+        .or_else(|| workspace.config_string(PUSH_REMOTE_CONFIG_KEY))
+    {
+        Some(name) => select_remote(&remotes, Some(&name))?,
+        None => pr_remote.clone(),
+    };
+
+    let url_of = |name: &str| {
+        remotes
+            .iter()
+            .find(|r| r.name == name)
+            .map(|r| r.url.as_str())
+            .ok_or_else(|| Error::RemoteNotFound(name.to_string()))
+    };
+    let platform_setting = workspace.config_string(PLATFORM_CONFIG_KEY);
+
+    // Detect platform from the PR remote's URL
+    let platform_config =
+        resolve_platform_config(url_of(&pr_remote)?, platform_setting.as_deref())?;
+
+    let remotes = SubmitRemotes {
+        pr_remote,
+        push_remote,
+    };
+    let head_config = if remotes.is_fork() {
+        let head_config =
+            resolve_platform_config(url_of(&remotes.push_remote)?, platform_setting.as_deref())?;
+        if head_config.platform != platform_config.platform {
+            return Err(Error::InvalidArgument(format!(
+                "push remote '{}' is on {}, but PR remote '{}' is on {}",
+                remotes.push_remote,
+                head_config.platform,
+                remotes.pr_remote,
+                platform_config.platform
+            )));
+        }
+        Some(head_config)
+    } else {
+        None
+    };
+
+    // Reviews of commits that already had a Change-Id are found by it;
+    // bookmarks cover the ones that landed
+    let change_id_trailers = if platform_config.platform == Platform::Gerrit {
+        workspace.change_id_trailers("trunk().. | bookmarks()")?
+    } else {
+        HashMap::new()
+    };
+
+    Ok(RemoteSelection {
+        remotes,
+        platform_config,
+        head_config,
+        change_id_trailers,
+        workspace_root: workspace.workspace_root().to_path_buf(),
+    })
+}
+
+/// Connect to the PR remote's platform
+///
+/// When forking, the platform service is told to open PRs from the push
+/// remote's repository. Gerrit is told the `Change-Id` trailers the stack's
+/// commits already carry.
+pub async fn connect_platform(
+    selection: RemoteSelection,
+) -> Result<(SubmitRemotes, Box<dyn PlatformService>)> {
+    let mut platform =
+        create_workspace_platform_service(&selection.platform_config, &selection.workspace_root)
+            .await?;
+
+    if let Some(head_config) = &selection.head_config {
+        platform.set_head_repository(head_config).await?;
+    }
+    platform.set_change_id_trailers(selection.change_id_trailers);
+
+    Ok((selection.remotes, platform))
+}
@@ -0,0 +1,121 @@
+//! Status command - live PR state, reviews and CI for every bookmark
+
+use crate::cli::CliProgress;
+use crate::cli::analyze::print_stack;
+use crate::cli::output::{OutputFormat, emit, is_json, println, set_output_format};
+use crate::cli::remote::{connect_platform, select_remotes};
+use crate::cli::style::{Stylize, spinner_style};
+use indicatif::ProgressBar;
+use jj_ryu::error::Result;
+use jj_ryu::graph::build_change_graph;
+use jj_ryu::output::DocumentKind;
+use jj_ryu::repo::JjWorkspace;
+use jj_ryu::submit::collect_stack_status;
+use jj_ryu::tracking::{load_pr_cache, load_tracking};
+use jj_ryu::types::{CheckStatus, PrState, PrStatus, ReviewDecision};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Run the status command
+///
+/// `json` (`--json`) is shorthand for `--output json`.
+pub async fn run_status(path: &Path, remote: Option<&str>, json: bool) -> Result<()> {
+    if json {
+        set_output_format(OutputFormat::Json);
+    }
+    let workspace = JjWorkspace::open(path)?;
+    let workspace_root = workspace.workspace_root().to_path_buf();
+    let tracking = load_tracking(&workspace_root).unwrap_or_default();
+    let pr_cache = load_pr_cache(&workspace_root).unwrap_or_default();
+
+    let graph = build_change_graph(&workspace)?;
+    let (_, mut platform) = connect_platform(select_remotes(&workspace, remote, None)?).await?;
+    platform.set_retry_progress(Arc::new(CliProgress::compact()));
+
+    let known: HashMap<String, u64> = pr_cache
+        .prs
+        .iter()
+        .map(|p| (p.bookmark.clone(), p.number))
+        .collect();
+
+    let spinner = (!is_json()).then(|| {
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(spinner_style());
+        spinner.set_message("Fetching PR status...");
+        spinner.enable_steady_tick(Duration::from_millis(80));
+        spinner
+    });
+    let statuses = collect_stack_status(&graph, platform.as_ref(), &known).await;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+    let statuses = statuses?;
+
+    if is_json() {
+        return emit(DocumentKind::Status, &statuses);
+    }
+
+    let Some(stack) = &graph.stack else {
+        println!("{}", "No bookmark stack found".muted());
+        return Ok(());
+    };
+    let by_bookmark: HashMap<String, PrStatus> = statuses
+        .into_iter()
+        .filter_map(|s| s.pr.map(|pr| (s.bookmark, pr)))
+        .collect();
+    print_stack(stack, &tracking, &pr_cache, &by_bookmark);
+    Ok(())
+}
+
+/// One-line summary of a PR's state, reviews and CI
+pub fn describe_status(status: &PrStatus) -> String {
+    let state = match status.state {
+        PrState::Open if status.pr.is_draft => "draft".muted().to_string(),
+        PrState::Open => "open".success().to_string(),
+        PrState::Merged => "merged".accent().to_string(),
+        PrState::Closed => "closed".muted().to_string(),
+    };
+    let mut parts = vec![state];
+    if status.state != PrState::Open {
+        return parts.join(" · ");
+    }
+
+    let review = match status.review_decision {
+        ReviewDecision::Approved => Some("approved".success().to_string()),
+        ReviewDecision::ChangesRequested => Some("changes requested".error().to_string()),
+        ReviewDecision::ReviewRequired => Some("review required".warn().to_string()),
+        ReviewDecision::None => Some("no review required".muted().to_string()),
+        ReviewDecision::Unknown => None,
+    };
+    match review {
+        Some(review) if status.approvals > 0 => {
+            parts.push(format!("{review} ({})", status.approvals));
+        }
+        Some(review) => parts.push(review),
+        None => {}
+    }
+    if status.unresolved_threads > 0 {
+        parts.push(
+            format!(
+                "{} unresolved thread{}",
+                status.unresolved_threads,
+                if status.unresolved_threads == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            )
+            .warn()
+            .to_string(),
+        );
+    }
+    match status.checks {
+        CheckStatus::Success => parts.push("checks passed".success().to_string()),
+        CheckStatus::Pending => parts.push("checks running".warn().to_string()),
+        CheckStatus::Failure => parts.push("checks failed".error().to_string()),
+        CheckStatus::None | CheckStatus::Unknown => {}
+    }
+    parts.join(" · ")
+}
@@ -1,20 +1,35 @@
 //! Submit command - submit a bookmark stack as PRs
 
 use crate::cli::CliProgress;
-use crate::cli::style::{CHECK, Stylize, arrow, bullet, cross};
-use anstream::{eprintln, println};
+use crate::cli::analyze::stack_outline;
+use crate::cli::land::configured_merge_method;
+use crate::cli::output::{emit, println};
+use crate::cli::remote::{connect_platform, select_remotes};
+use crate::cli::style::{CHECK, Stylize, arrow, bullet, check};
+use crate::cli::sync::stack_revset;
+use anstream::eprintln;
 use dialoguer::Confirm;
 use jj_ryu::error::{Error, Result};
-use jj_ryu::graph::build_change_graph;
-use jj_ryu::platform::{PlatformService, create_platform_service, parse_repo_info};
-use jj_ryu::repo::{JjWorkspace, select_remote};
+use jj_ryu::graph::{build_change_graph, build_change_graph_with};
+use jj_ryu::output::DocumentKind;
+use jj_ryu::platform::PlatformService;
+use jj_ryu::repo::JjWorkspace;
 use jj_ryu::submit::{
-    ExecutionStep, SubmissionAnalysis, SubmissionPlan, analyze_submission, create_submission_plan,
-    execute_submission, select_bookmark_for_segment,
+    AutoBookmark, BOOKMARK_TEMPLATE_CONFIG_KEY, ChangeBookmark, DEFAULT_BOOKMARK_TEMPLATE,
+    ExecutionStep, PR_TEMPLATE_CONFIG_KEY, SubmissionAnalysis, SubmissionJournal, SubmissionPlan,
+    SubmissionResult, analyze_submission, analyze_upstack, apply_pr_template, close_orphaned_prs,
+    codeowner_reviewers, create_submission_plan, execute_submission, find_codeowners,
+    find_pr_templates, load_journal, parent_bookmark, plan_auto_bookmarks, plan_auto_merge,
+    plan_change_bookmarks, plan_triage, select_pr_template, split_into_changes,
 };
-use jj_ryu::tracking::{load_pr_cache, load_tracking, save_pr_cache};
-use jj_ryu::types::{ChangeGraph, NarrowedBookmarkSegment};
+use jj_ryu::tracking::{
+    TrackedBookmark, TrackingState, load_pr_cache, load_tracking, save_pr_cache, save_tracking,
+};
+use jj_ryu::types::{Bookmark, BranchStack, ChangeGraph, MergeMethod, Platform, PrTriage};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, IsTerminal};
 use std::path::Path;
+use std::sync::Arc;
 
 /// Scope of bookmark submission (mutually exclusive options)
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -61,8 +76,154 @@ pub struct SubmitOptions<'a> {
     pub publish: bool,
     /// Interactively select which bookmarks to submit
     pub select: bool,
+    /// Pick the stack to submit from a list of every stack
+    pub pick_stack: bool,
     /// Submit all bookmarks in `trunk()`..@ (ignore tracking)
     pub all: bool,
+    /// Remote to push bookmarks to, when PRs go to another remote (fork workflow)
+    pub push_remote: Option<&'a str>,
+    /// Reviewers, assignees and labels for every PR, unless a tracked
+    /// bookmark overrides them
+    pub triage: PrTriage,
+    /// Enable auto-merge on the bottom PR, and on the next one after each sync
+    pub auto_merge: bool,
+    /// Continue an interrupted submission from its journal
+    pub resume: bool,
+    /// Create (and track) a bookmark for each unbookmarked change
+    pub auto_bookmark: bool,
+    /// Submit each change as its own PR
+    pub per_change: bool,
+}
+
+/// What each bookmark's PR should have: `triage`, with each tracked
+/// bookmark's own lists taking precedence
+pub fn requested_triage(
+    plan: &SubmissionPlan,
+    triage: &PrTriage,
+    tracking: &TrackingState,
+) -> HashMap<String, PrTriage> {
+    plan.segments
+        .iter()
+        .map(|s| {
+            let name = &s.bookmark.name;
+            let wanted = tracking
+                .get(name)
+                .map_or_else(|| triage.clone(), |t| triage.overridden_by(&t.triage));
+            (name.clone(), wanted)
+        })
+        .collect()
+}
+
+/// Method each bookmark's PR should auto-merge with: `method` (from
+/// `--auto-merge`), or what the bookmark was tracked with
+pub fn requested_auto_merge(
+    plan: &SubmissionPlan,
+    method: Option<MergeMethod>,
+    tracking: &TrackingState,
+) -> HashMap<String, MergeMethod> {
+    plan.segments
+        .iter()
+        .filter_map(|s| {
+            let name = &s.bookmark.name;
+            method
+                .or_else(|| tracking.get(name).and_then(|t| t.auto_merge))
+                .map(|method| (name.clone(), method))
+        })
+        .collect()
+}
+
+/// Record `--auto-merge` on the tracked bookmarks being submitted
+fn remember_auto_merge(
+    workspace_root: &Path,
+    tracking: &mut TrackingState,
+    plan: &SubmissionPlan,
+    method: MergeMethod,
+) -> Result<()> {
+    let names: Vec<&str> = plan
+        .segments
+        .iter()
+        .map(|s| s.bookmark.name.as_str())
+        .collect();
+    let mut changed = false;
+    for tracked in &mut tracking.bookmarks {
+        if names.contains(&tracked.name.as_str()) && tracked.auto_merge != Some(method) {
+            tracked.auto_merge = Some(method);
+            changed = true;
+        }
+    }
+    if changed {
+        save_tracking(workspace_root, tracking)?;
+    }
+    Ok(())
+}
+
+/// Cache the PRs a submission created or updated, for the stack view
+pub fn record_submitted_prs(
+    workspace_root: &Path,
+    plan: &SubmissionPlan,
+    platform: &dyn PlatformService,
+    result: &SubmissionResult,
+    pr_remote: &str,
+) {
+    let mut pr_cache = load_pr_cache(workspace_root).unwrap_or_default();
+    for pr in result.created_prs.iter().chain(result.updated_prs.iter()) {
+        // Map the PR's head back to its bookmark (they differ on Gerrit)
+        let bookmark = plan
+            .segments
+            .iter()
+            .find(|s| platform.review_head(&s.bookmark) == pr.head_ref)
+            .map_or(pr.head_ref.as_str(), |s| s.bookmark.name.as_str());
+        pr_cache.upsert(bookmark, pr, pr_remote);
+    }
+    for merged in &result.auto_merged {
+        let bookmark = merged.bookmark.as_str();
+        if pr_cache.get(bookmark).is_none()
+            && let Some(pr) = plan.existing_prs.get(bookmark)
+        {
+            pr_cache.upsert(bookmark, pr, pr_remote);
+        }
+        pr_cache.set_auto_merge(bookmark, merged.method);
+    }
+    // Best effort - don't fail the command if the cache write fails
+    let _ = save_pr_cache(workspace_root, &pr_cache);
+}
+
+/// Code owners of what each new PR changes, by bookmark name
+pub fn find_codeowner_reviewers(
+    workspace: &JjWorkspace,
+    platform: &dyn PlatformService,
+    plan: &SubmissionPlan,
+) -> Result<HashMap<String, Vec<String>>> {
+    match find_codeowners(workspace, platform.config().platform)? {
+        Some(codeowners) if !codeowners.is_empty() => {
+            codeowner_reviewers(workspace, &codeowners, plan)
+        }
+        _ => Ok(HashMap::new()),
+    }
+}
+
+/// Add code owners to the reviewers each bookmark's PR should have
+///
+/// The PR author can't review their own PR, so they're left out, and so are
+/// GitLab groups, which can't be reviewers.
+pub async fn add_codeowner_reviewers(
+    wanted: &mut HashMap<String, PrTriage>,
+    owners: HashMap<String, Vec<String>>,
+    platform: &dyn PlatformService,
+) {
+    if owners.is_empty() {
+        return;
+    }
+    let me = platform.current_user().await.ok();
+    let gitlab = platform.config().platform == Platform::GitLab;
+
+    for (bookmark, owners) in owners {
+        let reviewers = &mut wanted.entry(bookmark).or_default().reviewers;
+        reviewers.extend(owners.into_iter().filter(|owner| {
+            !(gitlab && owner.contains('/'))
+                && me.as_ref().is_none_or(|me| !me.eq_ignore_ascii_case(owner))
+        }));
+    }
 }
 
 /// Run the submit command
@@ -84,34 +245,129 @@ pub async fn run_submit(
     let mut workspace = JjWorkspace::open(path)?;
     let workspace_root = workspace.workspace_root().to_path_buf();
 
+    // A journal on disk means the previous submission didn't finish
+    let journal = load_journal(&workspace_root)?;
+    let resume = if options.resume {
+        Some(journal.ok_or_else(|| {
+            Error::InvalidArgument("No interrupted submission to resume".to_string())
+        })?)
+    } else {
+        if let Some(journal) = &journal {
+            warn_unfinished_submission(journal);
+        }
+        None
+    };
+
     // Load tracking state (unless --all bypasses tracking)
-    let tracking = load_tracking(&workspace_root)?;
-    let tracked_names: Vec<&str> = tracking.tracked_names().into_iter().collect();
+    let mut tracking = load_tracking(&workspace_root)?;
+
+    // Resuming repeats the interrupted submission's scope and options; it
+    // was one PR per change if every bookmark is a tracked change's
+    let (bookmark, remote, options) = match &resume {
+        Some(journal) => (
+            journal.bookmarks.last().map(String::as_str),
+            Some(journal.pr_remote.as_deref().unwrap_or(&journal.remote)),
+            SubmitOptions {
+                draft: journal.draft,
+                publish: journal.publish,
+                push_remote: Some(&journal.remote),
+                per_change: options.per_change
+                    || (!journal.bookmarks.is_empty()
+                        && journal
+                            .bookmarks
+                            .iter()
+                            .all(|b| tracking.changes.iter().any(|c| &c.bookmark == b))),
+                ..options
+            },
+        ),
+        None => (bookmark, remote, options),
+    };
+    let mut tracked_names: Vec<String> = resume.as_ref().map_or_else(
+        || {
+            tracking
+                .tracked_names()
+                .into_iter()
+                .map(String::from)
+                .collect()
+        },
+        |journal| journal.bookmarks.clone(),
+    );
 
     // If no bookmarks tracked and not --all, error
-    if tracked_names.is_empty() && !options.all {
+    if tracked_names.is_empty() && !options.all && !options.auto_bookmark && !options.per_change {
         return Err(Error::Tracking(
             "No bookmarks tracked. Run 'ryu track' first, or use 'ryu submit --all' to submit all bookmarks.".to_string()
         ));
     }
 
-    // Get remotes and select one
-    let remotes = workspace.git_remotes()?;
-    let remote_name = select_remote(&remotes, remote)?;
+    // Select push and PR remotes and create the platform service
+    let (remotes, mut platform) =
+        connect_platform(select_remotes(&workspace, remote, options.push_remote)?).await?;
+    // Announce rate-limit waits and retries as they happen
+    platform.set_retry_progress(Arc::new(CliProgress::verbose()));
 
-    // Detect platform from remote URL
-    let remote_info = remotes
-        .iter()
-        .find(|r| r.name == remote_name)
-        .ok_or_else(|| Error::RemoteNotFound(remote_name.clone()))?;
+    // Get default branch
+    let default_branch = workspace.default_branch()?;
 
-    let platform_config = parse_repo_info(&remote_info.url)?;
+    // Build change graph from working copy, moving to the stack of the
+    // picked or given bookmark if that's another one
+    let mut graph = build_change_graph(&workspace)?;
+    let picked = if options.pick_stack {
+        pick_stack(&graph)?
+    } else {
+        None
+    };
+    let bookmark = picked.as_deref().or(bookmark);
+    let other_stack = focus_other_stack(&mut graph, bookmark.or(options.upto_bookmark));
 
-    // Create platform service
-    let platform = create_platform_service(&platform_config).await?;
+    // Give each unbookmarked change a bookmark of its own, tracked so it's
+    // submitted with the rest. The plan is built as if they existed; they're
+    // created once the submission goes ahead.
+    let auto_bookmarks = if options.auto_bookmark && resume.is_none() {
+        plan_stack_auto_bookmarks(
+            &workspace,
+            &graph,
+            &stack_revset(other_stack.as_deref()),
+            options.dry_run,
+        )?
+    } else {
+        Vec::new()
+    };
+    if !auto_bookmarks.is_empty() {
+        tracked_names.extend(auto_bookmarks.iter().map(|b| b.name.clone()));
+        let planned: Vec<Bookmark> = auto_bookmarks.iter().map(AutoBookmark::bookmark).collect();
+        graph = build_change_graph_with(&workspace, &planned)?;
+        if let Some(leaf) = &other_stack {
+            graph.focus(leaf);
+        }
+    }
 
-    // Build change graph from working copy
-    let graph = build_change_graph(&workspace)?;
+    // One PR per change: every change gets a bookmark of its own, mapped to
+    // it by change ID in the tracking state. As with --auto-bookmark, new
+    // ones are created once the submission goes ahead.
+    let change_bookmarks = if options.per_change {
+        let planned = plan_stack_change_bookmarks(
+            &workspace,
+            &graph,
+            &tracking,
+            &stack_revset(other_stack.as_deref()),
+            options.dry_run,
+        )?;
+        let created: Vec<Bookmark> = planned
+            .iter()
+            .filter(|b| b.create)
+            .map(ChangeBookmark::bookmark)
+            .collect();
+        if !created.is_empty() {
+            graph = build_change_graph_with(&workspace, &created)?;
+            if let Some(leaf) = &other_stack {
+                graph.focus(leaf);
+            }
+        }
+        Some(planned)
+    } else {
+        None
+    };
 
     // Check if we have a stack
     if graph.stack.is_none() {
@@ -119,10 +375,17 @@ pub async fn run_submit(
             "{}",
             "No bookmarks found between trunk and working copy.".muted()
         );
-        println!(
-            "{}",
-            "Create a bookmark with: jj bookmark create <name>".muted()
-        );
+        if graph.stacks.is_empty() {
+            println!(
+                "{}",
+                "Create a bookmark with: jj bookmark create <name>".muted()
+            );
+        } else {
+            println!(
+                "{}",
+                "Pass a bookmark, or use --pick-stack, to submit another stack.".muted()
+            );
+        }
         return Ok(());
     }
 
@@ -130,17 +393,44 @@ pub async fn run_submit(
     if let Some(bm) = bookmark
         && !graph.bookmarks.contains_key(bm)
     {
+        if resume.is_some() {
+            return Err(Error::InvalidArgument(format!(
+                "Bookmark '{bm}' from the interrupted submission is no longer in the stack. \
+                 Run 'ryu submit' to start over."
+            )));
+        }
         return Err(Error::BookmarkNotFound(bm.to_string()));
     }
 
     // Analyze submission based on options
     let mut analysis = build_analysis(&graph, bookmark, &options, platform.as_ref()).await?;
 
-    // Filter to tracked bookmarks unless --all
-    if !options.all && !tracked_names.is_empty() {
+    // Split into one segment per change
+    if let Some(planned) = &change_bookmarks {
+        let bookmarks = planned
+            .iter()
+            .map(|b| {
+                let bookmark = graph
+                    .bookmarks
+                    .get(&b.name)
+                    .cloned()
+                    .unwrap_or_else(|| b.bookmark());
+                (b.change_id.clone(), bookmark)
+            })
+            .collect();
+        analysis = split_into_changes(&analysis, &bookmarks);
+        if analysis.segments.is_empty() {
+            return Err(Error::InvalidArgument(
+                "No described changes to submit. Describe them with 'jj describe'.".to_string(),
+            ));
+        }
+    }
+
+    // Filter to tracked bookmarks unless --all (or one PR per change)
+    if !options.all && !options.per_change && !tracked_names.is_empty() {
         analysis
             .segments
-            .retain(|s| tracked_names.contains(&s.bookmark.name.as_str()));
+            .retain(|s| tracked_names.contains(&s.bookmark.name));
         if analysis.segments.is_empty() {
             return Err(Error::Tracking(
                 "No tracked bookmarks in submission scope. Use 'ryu track' to track bookmarks, or 'ryu submit --all'.".to_string()
@@ -151,15 +441,21 @@ pub async fn run_submit(
     // Display what will be submitted
     print_submission_summary(&analysis, &options);
 
-    // Get default branch
-    let default_branch = workspace.default_branch()?;
-
     // Create submission plan
-    let mut plan =
-        create_submission_plan(&analysis, platform.as_ref(), &remote_name, &default_branch).await?;
+    let mut plan = create_submission_plan(
+        &analysis,
+        platform.as_ref(),
+        &remotes.push_remote,
+        &default_branch,
+    )
+    .await?;
+    if remotes.is_fork() {
+        plan.pr_remote = Some(remotes.pr_remote.clone());
+    }
 
     // Apply plan modifications based on options
     apply_plan_options(&mut plan, &options);
+    apply_repo_pr_template(&workspace, platform.as_ref(), &mut plan)?;
 
     // Handle interactive selection
     if options.select {
@@ -171,6 +467,37 @@ pub async fn run_submit(
         filter_plan_to_selection(&mut plan, &selected);
     }
 
+    // Request reviewers, assignees and labels the PRs don't have yet, and
+    // the code owners of what new PRs change
+    let wanted = if let Some(journal) = &resume {
+        journal.triage.clone().into_iter().collect()
+    } else {
+        let owners = find_codeowner_reviewers(&workspace, platform.as_ref(), &plan)?;
+        let mut wanted = requested_triage(&plan, &options.triage, &tracking);
+        add_codeowner_reviewers(&mut wanted, owners, platform.as_ref()).await;
+        wanted
+    };
+    plan_triage(&mut plan, platform.as_ref(), &wanted).await?;
+
+    // Arm auto-merge on the bottom PR; tracked bookmarks remember it, so
+    // sync arms the next PR once this one lands
+    let merge_method = if options.auto_merge && resume.is_none() {
+        Some(configured_merge_method(&workspace)?)
+    } else {
+        None
+    };
+    let auto_merge = resume.as_ref().map_or_else(
+        || requested_auto_merge(&plan, merge_method, &tracking),
+        |journal| journal.auto_merge.clone().into_iter().collect(),
+    );
+    plan_auto_merge(&mut plan, platform.as_ref(), &auto_merge).await?;
+
+    // The fresh plan reflects what the remote already has; compare it with
+    // what the journal says finished
+    if let Some(journal) = &resume {
+        print_resume_summary(journal, &plan);
+    }
+
     // Show confirmation if requested
     if options.confirm && !options.dry_run {
         print_plan_preview(&plan);
@@ -186,6 +513,30 @@ pub async fn run_submit(
         println!();
     }
 
+    if !options.dry_run {
+        create_auto_bookmarks(&mut workspace, &mut tracking, &auto_bookmarks, &plan)?;
+        if let Some(planned) = &change_bookmarks {
+            create_change_bookmarks(&mut workspace, &mut tracking, planned, &plan)?;
+        }
+        if let Some(method) = merge_method {
+            remember_auto_merge(&workspace_root, &mut tracking, &plan, method)?;
+        }
+    }
+
+    // Change-based platforms (Gerrit) find reviews by a Change-Id trailer,
+    // so every commit in the stack must carry one before it is pushed.
+    // Bookmarks are pushed by name, so the plan holds for the rewritten
+    // commits.
+    if platform.review_push_ref(&default_branch).is_some() && !options.dry_run {
+        let rewritten = workspace.add_change_id_trailers(&stack_revset(other_stack.as_deref()))?;
+        if rewritten > 0 {
+            println!(
+                "{}",
+                format!("Added Change-Id trailers to {rewritten} commit(s)").muted()
+            );
+        }
+    }
+
     // Execute plan
     let progress = CliProgress::verbose();
     let result = execute_submission(
@@ -199,12 +550,38 @@ pub async fn run_submit(
 
     // Update PR cache with results
     if !options.dry_run && result.success {
-        let mut pr_cache = load_pr_cache(&workspace_root).unwrap_or_default();
-        for pr in result.created_prs.iter().chain(result.updated_prs.iter()) {
-            pr_cache.upsert(&pr.head_ref, pr, &remote_name);
+        record_submitted_prs(
+            &workspace_root,
+            &plan,
+            platform.as_ref(),
+            &result,
+            &remotes.pr_remote,
+        );
+    }
+    // A failed submission may have stopped before the changes it would have
+    // replaced were pushed, so their PRs stay open until one succeeds
+    if options.per_change && result.success {
+        if !options.dry_run {
+            record_change_prs(
+                &workspace_root,
+                &mut tracking,
+                &plan,
+                platform.as_ref(),
+                &result,
+            )?;
         }
-        // Best effort - don't fail submit if cache write fails
-        let _ = save_pr_cache(&workspace_root, &pr_cache);
+        close_orphaned_changes(
+            &mut workspace,
+            platform.as_ref(),
+            &mut tracking,
+            options.dry_run,
+        )
+        .await?;
+    }
+    if options.dry_run {
+        emit(DocumentKind::Plan, &plan)?;
+    } else {
+        emit(DocumentKind::Result, &result)?;
     }
 
     // Summary
@@ -234,13 +611,214 @@ pub async fn run_submit(
                 );
             }
         } else {
-            eprintln!("{} Submission failed", cross());
-            for err in &result.errors {
-                eprintln!("  {}", err.error());
-            }
+            return Err(Error::Platform(format!(
+                "submission failed: {}",
+                result.errors.join("; ")
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Name a bookmark for each unbookmarked change in `revset`
+///
+/// On a dry run, also prints the names.
+fn plan_stack_auto_bookmarks(
+    workspace: &JjWorkspace,
+    graph: &ChangeGraph,
+    revset: &str,
+    dry_run: bool,
+) -> Result<Vec<AutoBookmark>> {
+    let template = workspace
+        .config_string(BOOKMARK_TEMPLATE_CONFIG_KEY)
+        .unwrap_or_else(|| DEFAULT_BOOKMARK_TEMPLATE.to_string());
+    let changes = workspace.resolve_revset(revset)?;
+    let planned = plan_auto_bookmarks(&changes, &graph.bookmarks, &template)?;
+
+    if dry_run && !planned.is_empty() {
+        for bookmark in &planned {
+            println!(
+                "  {} Would create bookmark {}",
+                arrow(),
+                bookmark.name.accent()
+            );
+        }
+        println!();
+    }
+    Ok(planned)
+}
+
+/// Create and track the planned bookmarks `plan` submits from
+fn create_auto_bookmarks(
+    workspace: &mut JjWorkspace,
+    tracking: &mut TrackingState,
+    planned: &[AutoBookmark],
+    plan: &SubmissionPlan,
+) -> Result<()> {
+    let planned: Vec<&AutoBookmark> = planned
+        .iter()
+        .filter(|b| plan.segments.iter().any(|s| s.bookmark.name == b.name))
+        .collect();
+    if planned.is_empty() {
+        return Ok(());
+    }
+
+    let targets: Vec<(String, String)> = planned
+        .iter()
+        .map(|b| (b.name.clone(), b.commit_id.clone()))
+        .collect();
+    workspace.create_bookmarks(&targets)?;
+    for bookmark in &planned {
+        tracking.track(TrackedBookmark::new(
+            bookmark.name.clone(),
+            bookmark.change_id.clone(),
+        ));
+        println!("{} Created bookmark {}", check(), bookmark.name.accent());
+    }
+    save_tracking(workspace.workspace_root(), tracking)?;
+    println!();
+    Ok(())
+}
+
+/// Pick the bookmark of each change in `revset`
+///
+/// On a dry run, also prints the bookmarks it would create.
+fn plan_stack_change_bookmarks(
+    workspace: &JjWorkspace,
+    graph: &ChangeGraph,
+    tracking: &TrackingState,
+    revset: &str,
+    dry_run: bool,
+) -> Result<Vec<ChangeBookmark>> {
+    let template = workspace
+        .config_string(BOOKMARK_TEMPLATE_CONFIG_KEY)
+        .unwrap_or_else(|| DEFAULT_BOOKMARK_TEMPLATE.to_string());
+    let changes = workspace.resolve_revset(revset)?;
+    let planned = plan_change_bookmarks(&changes, tracking, &graph.bookmarks, &template)?;
+
+    let created: Vec<&ChangeBookmark> = planned.iter().filter(|b| b.create).collect();
+    if dry_run && !created.is_empty() {
+        for bookmark in &created {
+            println!(
+                "  {} Would create bookmark {}",
+                arrow(),
+                bookmark.name.accent()
+            );
         }
+        println!();
+    }
+    Ok(planned)
+}
+
+/// Create the planned bookmarks of the changes `plan` submits, and track
+/// each change with its bookmark
+fn create_change_bookmarks(
+    workspace: &mut JjWorkspace,
+    tracking: &mut TrackingState,
+    planned: &[ChangeBookmark],
+    plan: &SubmissionPlan,
+) -> Result<()> {
+    let planned: Vec<&ChangeBookmark> = planned
+        .iter()
+        .filter(|b| plan.segments.iter().any(|s| s.bookmark.name == b.name))
+        .collect();
+    let created: Vec<&ChangeBookmark> = planned.iter().copied().filter(|b| b.create).collect();
+
+    let targets: Vec<(String, String)> = created
+        .iter()
+        .map(|b| (b.name.clone(), b.commit_id.clone()))
+        .collect();
+    workspace.create_bookmarks(&targets)?;
+    for bookmark in &planned {
+        tracking.track_change(&bookmark.change_id, &bookmark.name);
+        tracking.track(TrackedBookmark::new(
+            bookmark.name.clone(),
+            bookmark.change_id.clone(),
+        ));
+    }
+    save_tracking(workspace.workspace_root(), tracking)?;
+    for bookmark in &created {
+        println!("{} Created bookmark {}", check(), bookmark.name.accent());
+    }
+    if !created.is_empty() {
+        println!();
+    }
+    Ok(())
+}
+
+/// Record the PR of each change submitted one PR per change
+fn record_change_prs(
+    workspace_root: &Path,
+    tracking: &mut TrackingState,
+    plan: &SubmissionPlan,
+    platform: &dyn PlatformService,
+    result: &SubmissionResult,
+) -> Result<()> {
+    let created = result.created_prs.iter().filter_map(|pr| {
+        // Map the PR's head back to its bookmark (they differ on Gerrit)
+        plan.segments
+            .iter()
+            .find(|s| platform.review_head(&s.bookmark) == pr.head_ref)
+            .map(|s| (s.bookmark.name.as_str(), pr.number))
+    });
+    let prs = plan
+        .existing_prs
+        .iter()
+        .map(|(bookmark, pr)| (bookmark.as_str(), pr.number))
+        .chain(created);
+    for (bookmark, number) in prs {
+        if let Some(change) = tracking.changes.iter_mut().find(|c| c.bookmark == bookmark) {
+            change.pr_number = Some(number);
+        }
+    }
+    save_tracking(workspace_root, tracking)
+}
+
+/// Close the PRs of tracked changes that were squashed away or abandoned,
+/// and forget their bookmarks
+async fn close_orphaned_changes(
+    workspace: &mut JjWorkspace,
+    platform: &dyn PlatformService,
+    tracking: &mut TrackingState,
+    dry_run: bool,
+) -> Result<()> {
+    let live: HashSet<String> = workspace
+        .resolve_revset("trunk()..")?
+        .into_iter()
+        .map(|c| c.change_id)
+        .collect();
+    let orphaned = close_orphaned_prs(platform, &tracking.changes, &live, dry_run).await?;
+    if orphaned.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    for orphan in &orphaned {
+        let Some(pr) = &orphan.pr else { continue };
+        println!(
+            "{} {} {} ({} is gone)",
+            if dry_run { arrow() } else { check() },
+            if dry_run { "Would close" } else { "Closed" },
+            format!("PR #{}", pr.number).accent(),
+            orphan.change.bookmark.accent()
+        );
+    }
+    if dry_run {
+        return Ok(());
     }
 
+    let names: Vec<String> = orphaned.iter().map(|o| o.change.bookmark.clone()).collect();
+    workspace.forget_bookmarks(&names)?;
+    let mut pr_cache = load_pr_cache(workspace.workspace_root()).unwrap_or_default();
+    for orphan in &orphaned {
+        tracking.untrack_change(&orphan.change.change_id);
+        tracking.untrack(&orphan.change.bookmark);
+        pr_cache.remove(&orphan.change.bookmark);
+    }
+    save_tracking(workspace.workspace_root(), tracking)?;
+    // Best effort, as when recording submitted PRs
+    let _ = save_pr_cache(workspace.workspace_root(), &pr_cache);
     Ok(())
 }
 
@@ -268,21 +846,11 @@ async fn build_analysis(
                 Error::InvalidArgument("--upto requires a bookmark name".to_string())
             })?;
 
-            let upto_idx = analysis
-                .segments
-                .iter()
-                .position(|s| s.bookmark.name == upto_bookmark);
-
-            match upto_idx {
-                Some(idx) => {
-                    analysis.segments.truncate(idx + 1);
-                    analysis.target_bookmark = upto_bookmark.to_string();
-                }
-                None => {
-                    return Err(Error::InvalidArgument(format!(
-                        "Bookmark '{upto_bookmark}' not found in stack"
-                    )));
-                }
+            // Keeps the bookmarks below it, wherever the stack branches
+            if !analysis.truncate_to(upto_bookmark) {
+                return Err(Error::InvalidArgument(format!(
+                    "Bookmark '{upto_bookmark}' not found in stack"
+                )));
             }
         }
 
@@ -297,14 +865,23 @@ async fn build_analysis(
                 Error::InvalidArgument(format!("Target bookmark '{target}' not found in analysis"))
             })?;
 
-            // If not the first segment, verify parent has a PR
-            if target_idx > 0 {
-                let parent_bookmark = &analysis.segments[target_idx - 1].bookmark.name;
-                let parent_pr = platform.find_existing_pr(parent_bookmark).await?;
+            // If not based on trunk, verify parent has a PR
+            if let Some(parent_name) =
+                parent_bookmark(&target, &analysis.segments, &analysis.parents)?
+            {
+                let parent = &analysis
+                    .segments
+                    .iter()
+                    .find(|s| s.bookmark.name == parent_name)
+                    .expect("parent_bookmark returns a submitted bookmark")
+                    .bookmark;
+                let parent_pr = platform
+                    .find_existing_pr(&platform.review_head(parent))
+                    .await?;
 
                 if parent_pr.is_none() {
                     return Err(Error::InvalidArgument(format!(
-                        "Cannot use --only: parent bookmark '{parent_bookmark}' has no PR. Use --upto instead."
+                        "Cannot use --only: parent bookmark '{parent_name}' has no PR. Use --upto instead."
                     )));
                 }
             }
@@ -314,33 +891,8 @@ async fn build_analysis(
         }
 
         SubmitScope::Stack => {
-            // Handle --stack (upstack): include all segments from target to leaf
-            // With single-stack semantics, we can use graph.stack directly
-            let stack = graph
-                .stack
-                .as_ref()
-                .expect("stack existence checked before build_analysis");
-
-            // Find target position in the full stack
-            let target_idx = stack
-                .segments
-                .iter()
-                .position(|s| s.bookmarks.iter().any(|b| b.name == target))
-                .expect("target was set by analyze_submission");
-
-            // Build narrowed segments from target to leaf (skip segments before target)
-            analysis.segments = stack.segments[target_idx..]
-                .iter()
-                .map(|segment| NarrowedBookmarkSegment {
-                    bookmark: select_bookmark_for_segment(segment, Some(&target)),
-                    changes: segment.changes.clone(),
-                })
-                .collect();
-
-            // Update target to reflect the new leaf
-            if let Some(last) = analysis.segments.last() {
-                analysis.target_bookmark.clone_from(&last.bookmark.name);
-            }
+            // Handle --stack (upstack): the target and every branch above it
+            analysis = analyze_upstack(graph, &target)?;
         }
     }
 
@@ -387,6 +939,81 @@ fn apply_plan_options(plan: &mut SubmissionPlan, options: &SubmitOptions<'_>) {
     }
 }
 
+/// Render the repository's PR template into the bodies of new PRs
+///
+/// Templates are read from the trunk tree; [`PR_TEMPLATE_CONFIG_KEY`] chooses
+/// among several.
+pub fn apply_repo_pr_template(
+    workspace: &JjWorkspace,
+    platform: &dyn PlatformService,
+    plan: &mut SubmissionPlan,
+) -> Result<()> {
+    if plan.count_creates() == 0 {
+        return Ok(());
+    }
+
+    let templates = find_pr_templates(workspace, platform.config().platform)?;
+    let configured = workspace.config_string(PR_TEMPLATE_CONFIG_KEY);
+    if let Some(template) = select_pr_template(&templates, configured.as_deref())? {
+        tracing::debug!(path = %template.path, "using PR template");
+        apply_pr_template(plan, template);
+    }
+    Ok(())
+}
+
+/// Move `graph` to the stack containing `bookmark`, if that isn't the
+/// working copy's; returns the leaf bookmark of the stack moved to
+fn focus_other_stack(graph: &mut ChangeGraph, bookmark: Option<&str>) -> Option<String> {
+    let bookmark = bookmark?;
+    let in_current = graph.stack.as_ref().is_some_and(|s| s.contains(bookmark));
+    if in_current || !graph.stacks.iter().any(|s| s.contains(bookmark)) {
+        return None;
+    }
+    graph.focus(bookmark);
+    graph
+        .stack
+        .as_ref()
+        .and_then(BranchStack::leaf_bookmark)
+        .map(String::from)
+}
+
+/// Interactive stack selection using dialoguer; returns the leaf bookmark
+fn pick_stack(graph: &ChangeGraph) -> Result<Option<String>> {
+    use dialoguer::Select;
+
+    if graph.stacks.is_empty() {
+        return Ok(None);
+    }
+    if !io::stdin().is_terminal() {
+        return Err(Error::InvalidArgument(
+            "--pick-stack needs a terminal; pass the stack's top bookmark instead".to_string(),
+        ));
+    }
+
+    let items: Vec<String> = graph
+        .stacks
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            let here = if i == 0 && graph.stack.is_some() {
+                format!(" {}", "(working copy)".muted())
+            } else {
+                String::new()
+            };
+            format!("{}{here}", stack_outline(stack))
+        })
+        .collect();
+
+    let selection = Select::new()
+        .with_prompt("Select a stack to submit")
+        .items(&items)
+        .default(0)
+        .interact()
+        .map_err(|e| Error::Internal(format!("Failed to read selection: {e}")))?;
+
+    Ok(graph.stacks[selection].leaf_bookmark().map(String::from))
+}
+
 /// Interactive bookmark selection using dialoguer
 fn interactive_select(analysis: &SubmissionAnalysis) -> Result<Vec<String>> {
     use dialoguer::MultiSelect;
@@ -485,6 +1112,61 @@ fn print_submission_summary(analysis: &SubmissionAnalysis, options: &SubmitOptio
     println!();
 }
 
+/// Warn that the previous submission didn't finish
+pub fn warn_unfinished_submission(journal: &SubmissionJournal) {
+    let next = journal
+        .first_unfinished()
+        .map(|step| format!(" (next: {})", step.description))
+        .unwrap_or_default();
+    eprintln!(
+        "{}",
+        format!(
+            "Warning: a previous submission started {} stopped after {} of {} steps{next}",
+            journal.started_at.format("%Y-%m-%d %H:%M UTC"),
+            journal.completed(),
+            journal.steps.len()
+        )
+        .warn()
+    );
+    eprintln!(
+        "{}",
+        "Run 'ryu submit --resume' to finish it; this run replaces it.".muted()
+    );
+    eprintln!();
+}
+
+/// Print how the interrupted submission's steps compare to the fresh plan
+fn print_resume_summary(journal: &SubmissionJournal, plan: &SubmissionPlan) {
+    println!(
+        "{} submission started {} ({} of {} steps done)",
+        "Resuming".emphasis(),
+        journal.started_at.format("%Y-%m-%d %H:%M UTC"),
+        journal.completed().accent(),
+        journal.steps.len()
+    );
+
+    for step in &journal.steps {
+        let planned = plan.execution_steps.iter().any(|s| step.matches(s));
+        match (step.done, planned) {
+            (true, false) => println!("  {} {}", check(), step.description.muted()),
+            (true, true) => println!(
+                "  {} {} {}",
+                arrow(),
+                step.description,
+                "(recorded as done, but the remote disagrees; redoing)".warn()
+            ),
+            (false, true) => println!("  {} {}", arrow(), step.description),
+            (false, false) => println!(
+                "  {} {} {}",
+                bullet(),
+                step.description.muted(),
+                "(no longer needed)".muted()
+            ),
+        }
+    }
+    println!();
+}
+
 /// Print plan preview for --confirm
 fn print_plan_preview(plan: &SubmissionPlan) {
     println!("{}:", "Plan".emphasis());
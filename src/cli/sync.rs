@@ -1,23 +1,34 @@
-//! Sync command - sync current stack with remote
+//! Sync command - sync the current stack, or every stack, with remote
 
 use crate::cli::CliProgress;
-use crate::cli::style::{CHECK, Stylize, arrow, check, spinner_style};
-use anstream::println;
+use crate::cli::output::{emit, println};
+use crate::cli::remote::{SubmitRemotes, connect_platform, select_remotes};
+use crate::cli::style::{CHECK, Stylize, arrow, bullet, check, cross, spinner_style};
+use crate::cli::submit::{
+    add_codeowner_reviewers, apply_repo_pr_template, find_codeowner_reviewers,
+    record_submitted_prs, requested_auto_merge, requested_triage, warn_unfinished_submission,
+};
 use dialoguer::Confirm;
 use indicatif::ProgressBar;
 use jj_ryu::error::{Error, Result};
 use jj_ryu::graph::build_change_graph;
-use jj_ryu::platform::{create_platform_service, parse_repo_info};
-use jj_ryu::repo::{JjWorkspace, select_remote};
+use jj_ryu::output::DocumentKind;
+use jj_ryu::platform::PlatformService;
+use jj_ryu::repo::JjWorkspace;
 use jj_ryu::submit::{
-    SubmissionPlan, analyze_submission, create_submission_plan, execute_submission,
+    FinishedPrs, SubmissionAnalysis, SubmissionPlan, SubmissionResult, analyze_submission,
+    check_merge_queue, create_submission_plan, execute_submission, find_finished_prs, load_journal,
+    plan_auto_merge, plan_triage,
 };
-use jj_ryu::tracking::load_tracking;
+use jj_ryu::tracking::{TrackingState, load_pr_cache, load_tracking, save_pr_cache, save_tracking};
+use jj_ryu::types::{BranchStack, ChangeGraph, PrTriage};
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Options for the sync command
 #[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct SyncOptions {
     /// Dry run - show what would be done without making changes
     pub dry_run: bool,
@@ -25,59 +36,178 @@ pub struct SyncOptions {
     pub confirm: bool,
     /// Sync all bookmarks in `trunk()`..@ (ignore tracking)
     pub all: bool,
+    /// Sync every stack, not just the working copy's
+    pub all_stacks: bool,
+    /// Remote to push bookmarks to, when PRs go to another remote (fork workflow)
+    pub push_remote: Option<String>,
 }
 
 /// Run the sync command
-#[allow(clippy::too_many_lines)]
 pub async fn run_sync(path: &Path, remote: Option<&str>, options: SyncOptions) -> Result<()> {
     // Open workspace
     let mut workspace = JjWorkspace::open(path)?;
     let workspace_root = workspace.workspace_root().to_path_buf();
 
+    if let Some(journal) = load_journal(&workspace_root)? {
+        warn_unfinished_submission(&journal);
+    }
+
     // Load tracking state (unless --all bypasses tracking)
-    let tracking = load_tracking(&workspace_root)?;
-    let tracked_names: Vec<&str> = tracking.tracked_names().into_iter().collect();
+    let mut tracking = load_tracking(&workspace_root)?;
 
     // If no bookmarks tracked and not --all, error
-    if tracked_names.is_empty() && !options.all {
+    if tracking.bookmarks.is_empty() && !options.all {
         return Err(Error::Tracking(
             "No bookmarks tracked. Run 'ryu track' first, or use 'ryu sync --all' to sync all bookmarks.".to_string()
         ));
     }
 
-    // Get remotes and select one
-    let remotes = workspace.git_remotes()?;
-    let remote_name = select_remote(&remotes, remote)?;
+    // Select push and PR remotes and create the platform service
+    let (remotes, mut platform) = connect_platform(select_remotes(
+        &workspace,
+        remote,
+        options.push_remote.as_deref(),
+    )?)
+    .await?;
+    // Announce rate-limit waits and retries as they happen
+    platform.set_retry_progress(Arc::new(CliProgress::compact()));
+
+    if !options.dry_run {
+        fetch_remotes(&mut workspace, &remotes)?;
+    }
+
+    let default_branch = workspace.default_branch()?;
 
-    // Detect platform
-    let remote_info = remotes
+    if !options.all_stacks {
+        let Some((plan, result)) = sync_stack(
+            &mut workspace,
+            &mut tracking,
+            platform.as_ref(),
+            &remotes,
+            &default_branch,
+            None,
+            &options,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        if options.dry_run {
+            return emit(DocumentKind::Plan, &plan);
+        }
+        emit(DocumentKind::Result, &result)?;
+        return sync_failure(&[&result]);
+    }
+
+    // Every stack, each found again by its leaf bookmark as syncing one
+    // rebases the ones sharing its lower segments
+    let graph = build_change_graph(&workspace)?;
+    let leaves: Vec<String> = graph
+        .stacks
         .iter()
-        .find(|r| r.name == remote_name)
-        .ok_or_else(|| Error::RemoteNotFound(remote_name.clone()))?;
+        .filter_map(|s| s.leaf_bookmark().map(String::from))
+        .collect();
+    if leaves.is_empty() {
+        println!("{}", "No stacks to sync".muted());
+        return Ok(());
+    }
 
-    let platform_config = parse_repo_info(&remote_info.url)?;
+    let mut synced = Vec::new();
+    for (i, leaf) in leaves.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{} {}",
+            format!("Stack {}/{}:", i + 1, leaves.len()).emphasis(),
+            leaf.accent()
+        );
+        if let Some(outcome) = sync_stack(
+            &mut workspace,
+            &mut tracking,
+            platform.as_ref(),
+            &remotes,
+            &default_branch,
+            Some(leaf),
+            &options,
+        )
+        .await?
+        {
+            synced.push(outcome);
+        }
+    }
 
-    // Create platform service
-    let platform = create_platform_service(&platform_config).await?;
+    if options.dry_run {
+        let plans: Vec<&SubmissionPlan> = synced.iter().map(|(plan, _)| plan).collect();
+        emit(DocumentKind::Plan, &plans)
+    } else {
+        let results: Vec<&SubmissionResult> = synced.iter().map(|(_, result)| result).collect();
+        emit(DocumentKind::Result, &results)?;
+        sync_failure(&results)
+    }
+}
 
-    // Fetch from remote with spinner
-    if !options.dry_run {
-        let spinner = ProgressBar::new_spinner();
-        spinner.set_style(spinner_style());
-        spinner.set_message(format!("Fetching from {}...", remote_name.emphasis()));
-        spinner.enable_steady_tick(Duration::from_millis(80));
+/// Fail with the errors of every unsuccessful sync, so the command exits
+/// non-zero once every stack has been tried
+fn sync_failure(results: &[&SubmissionResult]) -> Result<()> {
+    let errors: Vec<&str> = results
+        .iter()
+        .filter(|result| !result.success)
+        .flat_map(|result| result.errors.iter().map(String::as_str))
+        .collect();
+    if results.iter().all(|result| result.success) {
+        Ok(())
+    } else {
+        Err(Error::Platform(format!(
+            "sync failed: {}",
+            errors.join("; ")
+        )))
+    }
+}
 
-        workspace.git_fetch(&remote_name)?;
+/// Revset of a stack's commits: the working copy's, or the one `leaf` tops
+pub fn stack_revset(leaf: Option<&str>) -> String {
+    leaf.map_or_else(
+        || "trunk()..@".to_string(),
+        |leaf| format!(r#"trunk()..bookmarks(exact:"{leaf}")"#),
+    )
+}
 
-        spinner.finish_with_message(format!(
-            "{} Fetched from {}",
-            check(),
-            remote_name.emphasis()
-        ));
+/// Build the change graph, focused on the stack `leaf` tops (if given)
+///
+/// The graph has no stack if `leaf` is gone, e.g. after its PR landed.
+fn build_stack_graph(workspace: &JjWorkspace, leaf: Option<&str>) -> Result<ChangeGraph> {
+    let mut graph = build_change_graph(workspace)?;
+    if let Some(leaf) = leaf {
+        graph.focus(leaf);
+    }
+    Ok(graph)
+}
+
+/// Sync one stack: the working copy's, or the one `leaf` tops
+///
+/// Returns the plan and its result, or `None` if there was nothing to
+/// submit or the user aborted.
+#[allow(clippy::too_many_lines)]
+async fn sync_stack(
+    workspace: &mut JjWorkspace,
+    tracking: &mut TrackingState,
+    platform: &dyn PlatformService,
+    remotes: &SubmitRemotes,
+    default_branch: &str,
+    leaf: Option<&str>,
+    options: &SyncOptions,
+) -> Result<Option<(SubmissionPlan, SubmissionResult)>> {
+    let workspace_root = workspace.workspace_root().to_path_buf();
+
+    // Change-based platforms (Gerrit) find reviews by a Change-Id trailer,
+    // so every commit in the stack must carry one before it is pushed
+    if platform.review_push_ref(default_branch).is_some() && !options.dry_run {
+        workspace.add_change_id_trailers(&stack_revset(leaf))?;
     }
 
     // Build change graph from working copy
-    let graph = build_change_graph(&workspace)?;
+    let mut graph = build_stack_graph(workspace, leaf)?;
 
     if graph.stack.is_none() {
         println!("{}", "No stack to sync".muted());
@@ -85,29 +215,49 @@ pub async fn run_sync(path: &Path, remote: Option<&str>, options: SyncOptions) -
             "{}",
             "Create bookmarks between trunk and working copy first.".muted()
         );
-        return Ok(());
+        return Ok(None);
     }
 
-    let default_branch = workspace.default_branch()?;
+    // PRs merged or closed since the last sync: landed changes are dropped
+    // and their bookmarks (and closed ones) leave the stack
+    let finished = find_finished_prs(&graph, &tracking.bookmarks, platform, default_branch).await?;
+    let mut dropped: Vec<String> = finished.deleted_bookmarks.clone();
+    dropped.extend(finished.untrack_names().into_iter().map(String::from));
+
+    if !finished.is_empty() {
+        print_finished_prs(&finished, default_branch);
+    }
+    // With every stack synced, each reports on its own bookmarks only
+    let queue_scope = graph.stack.as_ref().filter(|_| options.all_stacks);
+    report_merge_queue(
+        &workspace_root,
+        platform,
+        &finished,
+        queue_scope,
+        options.dry_run,
+    )
+    .await?;
+
     let progress = CliProgress::compact();
 
-    // Analyze and plan for the single stack
-    let mut analysis = analyze_submission(&graph, None)?;
+    // Analyze and plan for the single stack, as it will be after the cleanup
+    let Some(mut analysis) = select_segments(&graph, tracking, &dropped, options.all)? else {
+        finish_without_stack(workspace, tracking, &finished, options)?;
+        return Ok(None);
+    };
 
-    // Filter to tracked bookmarks unless --all
-    if !options.all && !tracked_names.is_empty() {
-        analysis
-            .segments
-            .retain(|s| tracked_names.contains(&s.bookmark.name.as_str()));
-        if analysis.segments.is_empty() {
-            return Err(Error::Tracking(
-                "No tracked bookmarks in stack. Use 'ryu track' to track bookmarks, or 'ryu sync --all'.".to_string()
-            ));
-        }
+    let mut plan =
+        create_submission_plan(&analysis, platform, &remotes.push_remote, default_branch).await?;
+    if remotes.is_fork() {
+        plan.pr_remote = Some(remotes.pr_remote.clone());
     }
-
-    let plan =
-        create_submission_plan(&analysis, platform.as_ref(), &remote_name, &default_branch).await?;
+    apply_repo_pr_template(workspace, platform, &mut plan)?;
+    let owners = find_codeowner_reviewers(workspace, platform, &plan)?;
+    let mut wanted = requested_triage(&plan, &PrTriage::default(), tracking);
+    add_codeowner_reviewers(&mut wanted, owners, platform).await;
+    plan_triage(&mut plan, platform, &wanted).await?;
+    let auto_merge = requested_auto_merge(&plan, None, tracking);
+    plan_auto_merge(&mut plan, platform, &auto_merge).await?;
 
     // Show confirmation if requested
     if options.confirm && !options.dry_run {
@@ -119,11 +269,36 @@ pub async fn run_sync(path: &Path, remote: Option<&str>, options: SyncOptions) -
             .map_err(|e| Error::Internal(format!("Failed to read confirmation: {e}")))?
         {
             println!("{}", "Aborted".muted());
-            return Ok(());
+            return Ok(None);
         }
         println!();
     }
 
+    // Drop landed changes, then plan again against the rebased stack
+    if !finished.is_empty() && !options.dry_run {
+        clean_up_finished(workspace, tracking, &finished)?;
+
+        graph = build_stack_graph(workspace, leaf)?;
+        let Some(rebased) = select_segments(&graph, tracking, &[], options.all)? else {
+            println!();
+            println!("{}", "Nothing left to sync".muted());
+            return Ok(None);
+        };
+        analysis = rebased;
+        plan = create_submission_plan(&analysis, platform, &remotes.push_remote, default_branch)
+            .await?;
+        if remotes.is_fork() {
+            plan.pr_remote = Some(remotes.pr_remote.clone());
+        }
+        apply_repo_pr_template(workspace, platform, &mut plan)?;
+        let owners = find_codeowner_reviewers(workspace, platform, &plan)?;
+        let mut wanted = requested_triage(&plan, &PrTriage::default(), tracking);
+        add_codeowner_reviewers(&mut wanted, owners, platform).await;
+        plan_triage(&mut plan, platform, &wanted).await?;
+        let auto_merge = requested_auto_merge(&plan, None, tracking);
+        plan_auto_merge(&mut plan, platform, &auto_merge).await?;
+    }
+
     // Execute
     println!(
         "{} {}",
@@ -131,20 +306,23 @@ pub async fn run_sync(path: &Path, remote: Option<&str>, options: SyncOptions) -
         analysis.target_bookmark.accent()
     );
 
-    let result = execute_submission(
-        &plan,
-        &mut workspace,
-        platform.as_ref(),
-        &progress,
-        options.dry_run,
-    )
-    .await?;
+    let result = execute_submission(&plan, workspace, platform, &progress, options.dry_run).await?;
+
+    if !options.dry_run && result.success {
+        record_submitted_prs(
+            workspace.workspace_root(),
+            &plan,
+            platform,
+            &result,
+            &remotes.pr_remote,
+        );
+    }
 
     // Summary
     println!();
     if options.dry_run {
         println!("{}", "Dry run complete".muted());
-    } else {
+    } else if result.success {
         println!(
             "{} {} pushed, {} created, {} updated",
             format!("{CHECK} Sync complete:").success(),
@@ -152,11 +330,271 @@ pub async fn run_sync(path: &Path, remote: Option<&str>, options: SyncOptions) -
             result.created_prs.len().accent(),
             result.updated_prs.len().accent()
         );
+    } else {
+        println!("{} Sync failed", cross());
+    }
+
+    Ok(Some((plan, result)))
+}
+
+/// Fetch from the PR remote (trunk), and the push remote when forking
+pub fn fetch_remotes(workspace: &mut JjWorkspace, remotes: &SubmitRemotes) -> Result<()> {
+    let mut fetch_remotes = vec![&remotes.pr_remote];
+    if remotes.is_fork() {
+        fetch_remotes.push(&remotes.push_remote);
     }
 
+    for remote_name in fetch_remotes {
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(spinner_style());
+        spinner.set_message(format!("Fetching from {}...", remote_name.emphasis()));
+        spinner.enable_steady_tick(Duration::from_millis(80));
+
+        workspace.git_fetch(remote_name)?;
+
+        spinner.finish_with_message(format!(
+            "{} Fetched from {}",
+            check(),
+            remote_name.emphasis()
+        ));
+    }
+    Ok(())
+}
+
+/// Select the stack segments to sync
+///
+/// Bookmarks in `dropped` are left out, and unless `all` is set so are
+/// untracked ones. Returns `None` when nothing is left.
+fn select_segments(
+    graph: &ChangeGraph,
+    tracking: &TrackingState,
+    dropped: &[String],
+    all: bool,
+) -> Result<Option<SubmissionAnalysis>> {
+    if graph.stack.is_none() {
+        return Ok(None);
+    }
+
+    let mut analysis = analyze_submission(graph, None)?;
+    analysis
+        .segments
+        .retain(|s| !dropped.contains(&s.bookmark.name));
+
+    // Filter to tracked bookmarks unless --all
+    if !all {
+        analysis
+            .segments
+            .retain(|s| tracking.is_tracked(&s.bookmark.name));
+    }
+
+    Ok(Some(analysis).filter(|a| !a.segments.is_empty()))
+}
+
+/// Finish a sync that leaves no segments to submit
+///
+/// Either every tracked bookmark in the stack finished (the cleanup still
+/// runs), or none was tracked to begin with.
+fn finish_without_stack(
+    workspace: &mut JjWorkspace,
+    tracking: &mut TrackingState,
+    finished: &FinishedPrs,
+    options: &SyncOptions,
+) -> Result<()> {
+    if finished.is_empty() && options.all_stacks {
+        println!("{}", "No tracked bookmarks in this stack".muted());
+        return Ok(());
+    }
+    if finished.is_empty() {
+        return Err(Error::Tracking(
+            "No tracked bookmarks in stack. Use 'ryu track' to track bookmarks, or 'ryu sync --all'.".to_string()
+        ));
+    }
+
+    if options.dry_run {
+        println!("{}", "Dry run complete".muted());
+        return Ok(());
+    }
+
+    if options.confirm
+        && !Confirm::new()
+            .with_prompt("Proceed with sync?")
+            .default(true)
+            .interact()
+            .map_err(|e| Error::Internal(format!("Failed to read confirmation: {e}")))?
+    {
+        println!("{}", "Aborted".muted());
+        return Ok(());
+    }
+
+    clean_up_finished(workspace, tracking, finished)?;
+    println!();
+    println!(
+        "{}",
+        format!("{CHECK} Sync complete: stack landed").success()
+    );
+    Ok(())
+}
+
+/// Drop landed changes from the stack and untrack finished bookmarks
+pub fn clean_up_finished(
+    workspace: &mut JjWorkspace,
+    tracking: &mut TrackingState,
+    finished: &FinishedPrs,
+) -> Result<()> {
+    let workspace_root = workspace.workspace_root().to_path_buf();
+
+    if !finished.landed_commits.is_empty() {
+        let rebased =
+            workspace.drop_landed_commits(&finished.landed_commits, &finished.deleted_bookmarks)?;
+        let count = finished.landed_commits.len();
+        println!(
+            "{} Dropped {} landed change{}, rebased {} onto trunk",
+            check(),
+            count.accent(),
+            if count == 1 { "" } else { "s" },
+            rebased.accent()
+        );
+    }
+
+    let names = finished.untrack_names();
+    for name in &names {
+        tracking.untrack(name);
+    }
+    save_tracking(&workspace_root, tracking)?;
+
+    // The cache only speeds up the stack view; a stale entry is harmless
+    let mut pr_cache = load_pr_cache(&workspace_root).unwrap_or_default();
+    for name in &names {
+        pr_cache.remove(name);
+    }
+    let _ = save_pr_cache(&workspace_root, &pr_cache);
+
+    if !names.is_empty() {
+        println!("{} Untracked {}", check(), names.join(", ").accent());
+    }
+    Ok(())
+}
+
+/// Report PRs ryu queued that left the merge queue unmerged or are failing in it
+///
+/// Removed PRs are no longer marked as queued, so each removal is reported
+/// once. With `stack`, only PRs of its bookmarks are checked.
+async fn report_merge_queue(
+    workspace_root: &Path,
+    platform: &dyn PlatformService,
+    finished: &FinishedPrs,
+    stack: Option<&BranchStack>,
+    dry_run: bool,
+) -> Result<()> {
+    let mut pr_cache = load_pr_cache(workspace_root).unwrap_or_default();
+    let done: Vec<u64> = finished
+        .landed
+        .iter()
+        .chain(&finished.untracked)
+        .chain(&finished.skipped)
+        .map(|f| f.pr.number)
+        .collect();
+    let queued: Vec<(String, u64)> = pr_cache
+        .queued()
+        .filter(|p| !done.contains(&p.number))
+        .filter(|p| stack.is_none_or(|s| s.contains(&p.bookmark)))
+        .map(|p| (p.bookmark.clone(), p.number))
+        .collect();
+    if queued.is_empty() {
+        return Ok(());
+    }
+
+    let problems = check_merge_queue(platform, &queued).await?;
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    println!("{}:", "Merge queue".emphasis());
+    for problem in &problems {
+        let what = if problem.was_removed() {
+            "was removed from the merge queue without merging"
+        } else {
+            "is failing in the merge queue"
+        };
+        println!(
+            "  {} #{} {} {}",
+            cross(),
+            problem.pr_number,
+            problem.bookmark.accent(),
+            what.warn()
+        );
+        if problem.was_removed() && !dry_run {
+            pr_cache.set_queued(&problem.bookmark, false);
+        }
+    }
+    println!();
+
+    if !dry_run {
+        // Best effort - a failed write only means reporting the removal again
+        let _ = save_pr_cache(workspace_root, &pr_cache);
+    }
     Ok(())
 }
 
+/// Print the merged and closed PRs found, and what sync does about them
+fn print_finished_prs(finished: &FinishedPrs, default_branch: &str) {
+    println!("{}:", "Finished PRs".emphasis());
+
+    for f in &finished.landed {
+        println!(
+            "  {} #{} {} merged into {}",
+            check(),
+            f.pr.number,
+            f.bookmark.accent(),
+            default_branch
+        );
+    }
+    for f in &finished.untracked {
+        let how = if f.merged {
+            format!("merged into {}", f.pr.base_ref)
+        } else {
+            "closed without merging".to_string()
+        };
+        println!(
+            "  {} #{} {} {how}",
+            bullet(),
+            f.pr.number,
+            f.bookmark.accent()
+        );
+    }
+    for f in &finished.skipped {
+        println!(
+            "  {} #{} {} merged into {} {}",
+            bullet(),
+            f.pr.number,
+            f.bookmark.accent(),
+            f.pr.base_ref,
+            "(not at the bottom of the stack; left alone)".warn()
+        );
+    }
+
+    if !finished.landed_commits.is_empty() {
+        let count = finished.landed_commits.len();
+        println!(
+            "  {} Drop {count} landed change{} and rebase the stack onto {default_branch}",
+            arrow(),
+            if count == 1 { "" } else { "s" },
+        );
+    }
+    if !finished.deleted_bookmarks.is_empty() {
+        println!(
+            "  {} Delete {}",
+            arrow(),
+            finished.deleted_bookmarks.join(", ")
+        );
+    }
+    let untracked = finished.untrack_names();
+    if !untracked.is_empty() {
+        println!("  {} Untrack {}", arrow(), untracked.join(", "));
+    }
+    println!();
+}
+
 /// Print sync preview for --confirm
 fn print_sync_preview(plan: &SubmissionPlan) {
     println!("{}:", "Sync plan".emphasis());
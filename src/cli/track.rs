@@ -1,12 +1,15 @@
 //! `ryu track` command - explicit bookmark tracking
 
+use crate::cli::output::emit;
 use crate::cli::style::{Stylize, check};
 use anyhow::Result;
 use chrono::Utc;
 use dialoguer::MultiSelect;
 use jj_ryu::graph::build_change_graph;
+use jj_ryu::output::DocumentKind;
 use jj_ryu::repo::JjWorkspace;
 use jj_ryu::tracking::{TrackedBookmark, load_tracking, save_tracking};
+use jj_ryu::types::PrTriage;
 use std::io::{self, IsTerminal};
 use std::path::Path;
 
@@ -18,16 +21,32 @@ pub struct TrackOptions {
     pub force: bool,
     /// Associate with specific remote
     pub remote: Option<String>,
+    /// Reviewers, assignees and labels for the bookmarks' PRs
+    pub triage: PrTriage,
 }
 
 /// Run the track command.
-#[allow(clippy::too_many_lines)]
 pub async fn run_track(path: &Path, bookmarks: &[String], options: TrackOptions) -> Result<()> {
     let workspace = JjWorkspace::open(path)?;
+    track_bookmarks(&workspace, bookmarks, &options)?;
+    // Report the tracking state, whether or not anything changed
+    emit(
+        DocumentKind::Tracking,
+        load_tracking(workspace.workspace_root())?,
+    )?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_lines)]
+fn track_bookmarks(
+    workspace: &JjWorkspace,
+    bookmarks: &[String],
+    options: &TrackOptions,
+) -> Result<()> {
     let workspace_root = workspace.workspace_root().to_path_buf();
 
     // Build graph to get available bookmarks
-    let graph = build_change_graph(&workspace)?;
+    let graph = build_change_graph(workspace)?;
 
     // Get bookmarks in the stack
     let available_bookmarks: Vec<&str> = graph
@@ -138,6 +157,8 @@ pub async fn run_track(path: &Path, bookmarks: &[String], options: TrackOptions)
             change_id,
             remote: options.remote.clone(),
             tracked_at: Utc::now(),
+            triage: options.triage.clone(),
+            auto_merge: None,
         };
 
         // If force-tracking, remove existing entry first
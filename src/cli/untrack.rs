@@ -1,8 +1,10 @@
 //! `ryu untrack` command - remove bookmarks from tracking
 
+use crate::cli::output::emit;
 use crate::cli::style::{Stylize, check};
 use anyhow::Result;
 use dialoguer::MultiSelect;
+use jj_ryu::output::DocumentKind;
 use jj_ryu::repo::JjWorkspace;
 use jj_ryu::tracking::{load_pr_cache, load_tracking, save_tracking};
 use std::io::{self, IsTerminal};
@@ -17,10 +19,20 @@ pub struct UntrackOptions {
 /// Run the untrack command.
 pub async fn run_untrack(path: &Path, bookmarks: &[String], options: UntrackOptions) -> Result<()> {
     let workspace = JjWorkspace::open(path)?;
-    let workspace_root = workspace.workspace_root().to_path_buf();
+    let workspace_root = workspace.workspace_root();
+    untrack_bookmarks(workspace_root, bookmarks, &options)?;
+    // Report the tracking state, whether or not anything changed
+    emit(DocumentKind::Tracking, load_tracking(workspace_root)?)?;
+    Ok(())
+}
 
+fn untrack_bookmarks(
+    workspace_root: &Path,
+    bookmarks: &[String],
+    options: &UntrackOptions,
+) -> Result<()> {
     // Load existing tracking state
-    let mut state = load_tracking(&workspace_root)?;
+    let mut state = load_tracking(workspace_root)?;
 
     if state.bookmarks.is_empty() {
         eprintln!("{}", "No bookmarks currently tracked".muted());
@@ -28,7 +40,7 @@ pub async fn run_untrack(path: &Path, bookmarks: &[String], options: UntrackOpti
     }
 
     // Load PR cache for notes about open PRs
-    let pr_cache = load_pr_cache(&workspace_root)?;
+    let pr_cache = load_pr_cache(workspace_root)?;
 
     // Determine which bookmarks to untrack
     let bookmarks_to_untrack: Vec<String> = if options.all {
@@ -97,7 +109,7 @@ pub async fn run_untrack(path: &Path, bookmarks: &[String], options: UntrackOpti
     }
 
     // Save state
-    save_tracking(&workspace_root, &state)?;
+    save_tracking(workspace_root, &state)?;
 
     // Print summary
     if untracked_names.len() == 1 {
@@ -24,8 +24,8 @@ pub enum Error {
     #[error("{0}")]
     NoStack(String),
 
-    /// No supported remotes (GitHub/GitLab) found
-    #[error("no supported remotes found (GitHub/GitLab)")]
+    /// No supported remotes (GitHub/GitLab/Gitea/Bitbucket/Azure DevOps/Gerrit) found
+    #[error("no supported remotes found (GitHub/GitLab/Gitea/Bitbucket/Azure DevOps/Gerrit)")]
     NoSupportedRemotes,
 
     /// Specified remote not found
@@ -44,9 +44,31 @@ pub enum Error {
     #[error("GitLab API error: {0}")]
     GitLabApi(String),
 
-    /// Merge commit detected (cannot stack)
-    #[error("merge commit detected in bookmark '{0}' history - rebasing required")]
-    MergeCommitDetected(String),
+    /// Gitea/Forgejo API error
+    #[error("Gitea API error: {0}")]
+    GiteaApi(String),
+
+    /// Bitbucket Data Center / Server API error
+    #[error("Bitbucket API error: {0}")]
+    BitbucketApi(String),
+
+    /// Azure DevOps API error
+    #[error("Azure DevOps API error: {0}")]
+    AzureDevOpsApi(String),
+
+    /// Gerrit API error
+    #[error("Gerrit API error: {0}")]
+    GerritApi(String),
+
+    /// Local forge database error
+    #[error("local forge error: {0}")]
+    LocalForge(String),
+
+    /// Merge commit detected (cannot stack): bookmark and merge commit
+    #[error(
+        "merge commit {1} in bookmark '{0}' history joins two lines of work - rebasing required"
+    )]
+    MergeCommitDetected(String, String),
 
     /// Revset evaluation failed
     #[error("revset error: {0}")]
@@ -101,6 +123,10 @@ pub enum Error {
     #[error("invalid argument: {0}")]
     InvalidArgument(String),
 
+    /// PR can't be landed yet (not approved, checks failing, conflicts)
+    #[error("PR #{0} is not ready to land: {1}")]
+    NotReadyToLand(u64, String),
+
     /// Tracking state error
     #[error("tracking error: {0}")]
     Tracking(String),
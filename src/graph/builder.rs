@@ -1,69 +1,250 @@
 //! Change graph builder
 //!
-//! Builds a `ChangeGraph` from jj workspace state.
-//! Uses single-stack semantics: only the stack from trunk to working copy.
+//! Builds a `ChangeGraph` from jj workspace state: the stack from trunk to
+//! the working copy, plus the other stacks of the bookmarks `ryu.stacks`
+//! matches. Stacks sharing their bottom segment are merged into a tree.
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::repo::JjWorkspace;
 use crate::types::{Bookmark, BookmarkSegment, BranchStack, ChangeGraph, LogEntry};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::debug;
 
+/// jj config key for the revset of bookmarks whose stacks ryu works with
+pub const STACKS_REVSET_CONFIG_KEY: &str = "ryu.stacks";
+
+/// Bookmarks whose stacks ryu works with, unless `ryu.stacks` is set
+pub const DEFAULT_STACKS_REVSET: &str = "mine() & bookmarks()";
+
 /// Build a change graph from the current workspace state
 ///
-/// This analyzes the single stack from trunk to working copy.
-/// Only bookmarks between trunk and @ are included in the stack.
+/// This analyzes the single stack from trunk to working copy, and every
+/// other stack topped by a bookmark in `ryu.stacks` (default: your own
+/// bookmarks) that isn't merged into trunk.
 ///
 /// Returns a `ChangeGraph` with:
 /// - `bookmarks`: All local bookmarks in the workspace (not just those in the stack).
 ///   This allows callers to validate bookmark existence before submission.
-/// - `stack: Some(...)` if there are bookmarked commits between trunk and @
+/// - `stack: Some(...)` if there are bookmarked commits between trunk and @,
+///   including the stacks that branch off it
 /// - `stack: None` if working copy is at trunk or no bookmarks exist
+/// - `stacks`: the stack from trunk to @ (if any), then the others
+///
+/// A merge commit with a parent on trunk just brings trunk in, and stays in
+/// the stack. Any other merge in the working copy's stack fails with
+/// [`Error::MergeCommitDetected`]; other stacks with one are left out and
+/// counted in `excluded_bookmark_count`.
 pub fn build_change_graph(workspace: &JjWorkspace) -> Result<ChangeGraph> {
+    build_change_graph_with(workspace, &[])
+}
+
+/// Build a change graph as if the `planned` bookmarks already existed
+///
+/// Lets a submission that creates bookmarks be previewed, and confirmed,
+/// before anything is written to the repo.
+pub fn build_change_graph_with(
+    workspace: &JjWorkspace,
+    planned: &[Bookmark],
+) -> Result<ChangeGraph> {
     debug!("Building change graph from trunk to working copy...");
 
+    let bookmarks: HashMap<String, Bookmark> = workspace
+        .local_bookmarks()?
+        .into_iter()
+        .chain(planned.iter().cloned())
+        .map(|b| (b.name.clone(), b))
+        .collect();
+    let mut graph = ChangeGraph {
+        bookmarks,
+        ..ChangeGraph::default()
+    };
+    let stack = |revset: &str, bookmarks: &HashMap<String, Bookmark>| {
+        build_stack(workspace, revset, bookmarks, planned)
+    };
+
     // Query trunk()..@ to get all commits between trunk and working copy
-    let changes = workspace.resolve_revset("trunk()..@")?;
+    let mut lines = Vec::new();
+    let has_working_copy_stack = match stack("trunk()..@", &graph.bookmarks)? {
+        StackBuild::Stack(stack) => {
+            debug!("Built {} segments", stack.segments.len());
+            lines.push(stack);
+            true
+        }
+        StackBuild::Empty => {
+            debug!("No bookmarked segments between trunk and @");
+            false
+        }
+        StackBuild::MergeCommit { merge, .. } => {
+            return Err(Error::MergeCommitDetected(merge.bookmark, merge.commit_id));
+        }
+    };
+
+    // Heads of the other stacks: matched bookmarks not merged into trunk,
+    // and not below the working copy (those are in the stack above)
+    let revset = workspace
+        .config_string(STACKS_REVSET_CONFIG_KEY)
+        .unwrap_or_else(|| DEFAULT_STACKS_REVSET.to_string());
+    let heads = workspace.resolve_revset(&format!("heads((trunk()..({revset})) ~ ::@)"))?;
+    let mut excluded = HashSet::new();
+    for head in &heads {
+        match stack(&format!("trunk()..{}", head.commit_id), &graph.bookmarks)? {
+            StackBuild::Stack(stack) => lines.push(stack),
+            StackBuild::Empty => {}
+            StackBuild::MergeCommit { merge, bookmarks } => {
+                debug!(
+                    "Found merge commit {} under {} - skipping stack",
+                    merge.commit_id, merge.bookmark
+                );
+                excluded.extend(bookmarks);
+            }
+        }
+    }
 
-    if changes.is_empty() {
-        debug!("Working copy is at trunk, no stack to build");
-        return Ok(ChangeGraph::default());
+    // The working copy's line comes first, so its tree does too
+    graph.stacks = merge_into_trees(lines);
+    // Bookmarks only in stacks left out (others can be in a kept stack too)
+    graph.excluded_bookmark_count = excluded
+        .iter()
+        .filter(|name| !graph.stacks.iter().any(|s| s.contains(name)))
+        .count();
+    if has_working_copy_stack {
+        graph.stack = graph.stacks.first().cloned();
     }
+    debug!("Found {} stacks", graph.stacks.len());
 
-    debug!("Found {} commits between trunk and @", changes.len());
-
-    // Check for merge commits - we don't support them
-    for change in &changes {
-        if change.parents.len() > 1 {
-            debug!("Found merge commit {} - excluding stack", change.commit_id);
-            return Ok(ChangeGraph {
-                bookmarks: HashMap::new(),
-                stack: None,
-                // Signals merge commit exclusion occurred, not actual count of excluded bookmarks
-                excluded_bookmark_count: 1,
-            });
+    Ok(graph)
+}
+
+/// Merge linear stacks that share their bottom segment into trees
+///
+/// Each segment's parent comes from the first line it appears in, so the
+/// trees keep the lines' order: a tree's segments still follow their parents.
+fn merge_into_trees(lines: Vec<BranchStack>) -> Vec<BranchStack> {
+    let mut trees: Vec<BranchStack> = Vec::new();
+    for line in lines {
+        let root = &line.segments[0].bookmarks[0].name;
+        let Some(tree) = trees.iter_mut().find(|t| t.contains(root)) else {
+            trees.push(line);
+            continue;
+        };
+
+        let BranchStack { segments, parents } = line;
+        for segment in segments {
+            if tree.contains(&segment.bookmarks[0].name) {
+                continue;
+            }
+            for bookmark in &segment.bookmarks {
+                if let Some(parent) = parents.get(&bookmark.name) {
+                    tree.parents.insert(bookmark.name.clone(), parent.clone());
+                }
+            }
+            tree.segments.push(segment);
         }
     }
+    trees
+}
+
+/// Outcome of building the stack for one revset
+enum StackBuild {
+    Stack(BranchStack),
+    /// No bookmarked commits
+    Empty,
+    /// The revset has a merge commit, which stacks can't contain
+    MergeCommit {
+        /// The first such merge
+        merge: UnsupportedMerge,
+        /// Every bookmark in the revset
+        bookmarks: Vec<String>,
+    },
+}
+
+/// A merge commit joining two lines of work
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UnsupportedMerge {
+    /// Short commit ID of the merge
+    commit_id: String,
+    /// Bookmark whose history has the merge
+    bookmark: String,
+}
+
+/// Build the stack of the commits in `revset`, with the `planned`
+/// bookmarks on their commits
+fn build_stack(
+    workspace: &JjWorkspace,
+    revset: &str,
+    bookmarks_by_name: &HashMap<String, Bookmark>,
+    planned: &[Bookmark],
+) -> Result<StackBuild> {
+    let mut changes = workspace.resolve_revset(revset)?;
+    debug!("Found {} commits in {revset}", changes.len());
+    for change in &mut changes {
+        change.local_bookmarks.extend(
+            planned
+                .iter()
+                .filter(|b| b.commit_id == change.commit_id)
+                .map(|b| b.name.clone()),
+        );
+    }
+
+    // Merges bringing trunk in are fine; other merges aren't supported
+    if let Some(merge) = find_unsupported_merge(&changes, bookmarks_by_name) {
+        let bookmarks = changes
+            .iter()
+            .flat_map(|c| &c.local_bookmarks)
+            .filter(|name| bookmarks_by_name.contains_key(*name))
+            .cloned()
+            .collect();
+        return Ok(StackBuild::MergeCommit { merge, bookmarks });
+    }
 
     // Build segments from the changes
     // Changes are returned newest-first (working copy toward trunk)
-    let (segments, bookmarks_by_name) = build_segments_from_changes(&changes, workspace)?;
-
+    let segments = build_segments_from_changes(&changes, bookmarks_by_name);
     if segments.is_empty() {
-        debug!("No bookmarked segments found");
-        return Ok(ChangeGraph {
-            bookmarks: bookmarks_by_name,
-            stack: None,
-            excluded_bookmark_count: 0,
-        });
+        return Ok(StackBuild::Empty);
     }
+    Ok(StackBuild::Stack(BranchStack::linear(segments)))
+}
 
-    debug!("Built {} segments", segments.len());
+/// Find a merge commit that doesn't just bring trunk in
+///
+/// `changes` are the commits of a `trunk()..` revset, newest first, so a
+/// parent outside them is on trunk. A merge with at most one parent among
+/// them is a trunk sync and stays in the stack like any other commit.
+///
+/// The merge is reported with the nearest bookmark on or above it, whose
+/// history it's in, or the nearest below it when no bookmark is above.
+/// Returns `None` when `changes` have no bookmarks: there's no stack then.
+fn find_unsupported_merge(
+    changes: &[LogEntry],
+    bookmarks_by_name: &HashMap<String, Bookmark>,
+) -> Option<UnsupportedMerge> {
+    let in_range: HashSet<&str> = changes.iter().map(|c| c.commit_id.as_str()).collect();
+    let index = changes.iter().position(|c| {
+        c.parents
+            .iter()
+            .filter(|p| in_range.contains(p.as_str()))
+            .count()
+            > 1
+    })?;
 
-    Ok(ChangeGraph {
-        bookmarks: bookmarks_by_name,
-        stack: Some(BranchStack { segments }),
-        excluded_bookmark_count: 0,
+    let bookmark_of = |change: &LogEntry| {
+        change
+            .local_bookmarks
+            .iter()
+            .find(|name| bookmarks_by_name.contains_key(*name))
+            .cloned()
+    };
+    let bookmark = changes[..=index]
+        .iter()
+        .rev()
+        .find_map(bookmark_of)
+        .or_else(|| changes[index..].iter().find_map(bookmark_of))?;
+
+    let commit_id = &changes[index].commit_id;
+    Some(UnsupportedMerge {
+        commit_id: commit_id[..12.min(commit_id.len())].to_string(),
+        bookmark,
     })
 }
 
@@ -72,14 +253,8 @@ pub fn build_change_graph(workspace: &JjWorkspace) -> Result<ChangeGraph> {
 /// Returns segments in trunk-to-leaf order (reversed from input)
 fn build_segments_from_changes(
     changes: &[LogEntry],
-    workspace: &JjWorkspace,
-) -> Result<(Vec<BookmarkSegment>, HashMap<String, Bookmark>)> {
-    let all_bookmarks = workspace.local_bookmarks()?;
-    let bookmarks_by_name: HashMap<String, Bookmark> = all_bookmarks
-        .iter()
-        .map(|b| (b.name.clone(), b.clone()))
-        .collect();
-
+    bookmarks_by_name: &HashMap<String, Bookmark>,
+) -> Vec<BookmarkSegment> {
     let mut segments: Vec<BookmarkSegment> = Vec::new();
     let mut current_changes: Vec<LogEntry> = Vec::new();
 
@@ -128,7 +303,7 @@ fn build_segments_from_changes(
     // Reverse to get trunk-to-leaf order
     segments.reverse();
 
-    Ok((segments, bookmarks_by_name))
+    segments
 }
 
 #[cfg(test)]
@@ -143,6 +318,7 @@ mod tests {
             author_name: "Test".to_string(),
             author_email: "test@test.com".to_string(),
             description_first_line: format!("Commit {commit_id}"),
+            description: format!("Commit {commit_id}\n"),
             parents: vec!["parent".to_string()],
             local_bookmarks: bookmarks.into_iter().map(String::from).collect(),
             remote_bookmarks: vec![],
@@ -162,6 +338,55 @@ mod tests {
         }
     }
 
+    fn make_merge(commit_id: &str, parents: &[&str], bookmarks: Vec<&str>) -> LogEntry {
+        LogEntry {
+            parents: parents.iter().map(ToString::to_string).collect(),
+            ..make_log_entry(commit_id, commit_id, bookmarks)
+        }
+    }
+
+    #[test]
+    fn test_trunk_merge_is_not_reported() {
+        // trunk <- c1 (feat-a) <- m (merges trunk in) <- c2 (feat-b)
+        let changes = vec![
+            make_merge("c2", &["m"], vec!["feat-b"]),
+            make_merge("m", &["c1", "trunk"], vec![]),
+            make_merge("c1", &["trunk"], vec!["feat-a"]),
+        ];
+        let bookmarks: HashMap<String, Bookmark> = [
+            ("feat-a".to_string(), make_bookmark("feat-a", "c1", "c1")),
+            ("feat-b".to_string(), make_bookmark("feat-b", "c2", "c2")),
+        ]
+        .into();
+
+        assert_eq!(find_unsupported_merge(&changes, &bookmarks), None);
+        assert_eq!(build_segments_from_changes(&changes, &bookmarks).len(), 2);
+    }
+
+    #[test]
+    fn test_merge_of_two_lines_is_reported() {
+        // trunk <- c1 (feat-a) <- m <- c3 (feat-b), with m also on c2
+        let changes = vec![
+            make_merge("c3", &["m"], vec!["feat-b"]),
+            make_merge("m", &["c1", "c2"], vec![]),
+            make_merge("c2", &["trunk"], vec![]),
+            make_merge("c1", &["trunk"], vec!["feat-a"]),
+        ];
+        let bookmarks: HashMap<String, Bookmark> = [
+            ("feat-a".to_string(), make_bookmark("feat-a", "c1", "c1")),
+            ("feat-b".to_string(), make_bookmark("feat-b", "c3", "c3")),
+        ]
+        .into();
+
+        let merge = find_unsupported_merge(&changes, &bookmarks).unwrap();
+        assert_eq!(merge.commit_id, "m");
+        assert_eq!(merge.bookmark, "feat-b");
+
+        // Without a bookmark above, the one below is named
+        let merge = find_unsupported_merge(&changes[1..], &bookmarks).unwrap();
+        assert_eq!(merge.bookmark, "feat-a");
+    }
+
     #[test]
     fn test_single_bookmark_segment() {
         // Simulate: trunk <- commit1 (feat-a) <- commit2 (@)
@@ -4,4 +4,6 @@
 
 mod builder;
 
-pub use builder::build_change_graph;
+pub use builder::{
+    DEFAULT_STACKS_REVSET, STACKS_REVSET_CONFIG_KEY, build_change_graph, build_change_graph_with,
+};
@@ -1,8 +1,8 @@
 //! jj-ryu - Stacked PRs for Jujutsu
 //!
 //! This library provides the core functionality for managing stacked pull requests
-//! when using Jujutsu (jj) as your version control system. It supports both GitHub
-//! and GitLab.
+//! when using Jujutsu (jj) as your version control system. It supports GitHub,
+//! GitLab, Gitea/Forgejo, Bitbucket Data Center/Server, Azure DevOps and Gerrit.
 //!
 //! # Architecture
 //!
@@ -17,6 +17,7 @@
 pub mod auth;
 pub mod error;
 pub mod graph;
+pub mod output;
 pub mod platform;
 pub mod repo;
 pub mod submit;
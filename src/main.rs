@@ -3,21 +3,27 @@
 //! CLI binary for managing stacked pull requests with jj.
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
-use jj_ryu::types::Platform;
+use clap::{Args, Parser, Subcommand};
+use jj_ryu::types::{MergeMethod, Platform, PrTriage};
 use std::path::PathBuf;
 
 mod cli;
 
 #[derive(Parser)]
 #[command(name = "ryu")]
-#[command(about = "Stacked PRs for Jujutsu - GitHub & GitLab")]
+#[command(
+    about = "Stacked PRs for Jujutsu - GitHub, GitLab, Gitea, Bitbucket, Azure DevOps & Gerrit"
+)]
 #[command(version)]
 struct Cli {
     /// Path to jj repository (defaults to current directory)
     #[arg(short, long, global = true)]
     path: Option<PathBuf>,
 
+    /// Output format; json prints one versioned JSON document to stdout
+    #[arg(long, global = true, value_enum, default_value_t)]
+    output: cli::OutputFormat,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -65,13 +71,47 @@ enum Commands {
         #[arg(long, short = 'i')]
         select: bool,
 
-        /// Git remote to push to
+        /// Pick the stack to submit from every stack of your bookmarks (ryu.stacks)
+        #[arg(long, conflicts_with = "bookmark")]
+        pick_stack: bool,
+
+        /// Git remote to open PRs against (and push to, unless --push-remote is set)
         #[arg(long)]
         remote: Option<String>,
 
+        /// Git remote to push bookmarks to, e.g. your fork (defaults to ryu.push-remote, then --remote)
+        #[arg(long)]
+        push_remote: Option<String>,
+
         /// Submit all bookmarks in `trunk()`..@ (ignore tracking)
         #[arg(long, short)]
         all: bool,
+
+        #[command(flatten)]
+        triage: TriageArgs,
+
+        /// Enable auto-merge on the bottom PR (method from ryu.merge-method); sync re-arms the next one
+        #[arg(long)]
+        auto_merge: bool,
+
+        /// Create and track a bookmark for each unbookmarked change (names from ryu.bookmark-template)
+        #[arg(long)]
+        auto_bookmark: bool,
+
+        /// Submit each change as its own PR, closing the PRs of changes since squashed or abandoned
+        #[arg(long, conflicts_with = "auto_bookmark")]
+        per_change: bool,
+
+        /// Finish an interrupted submission, skipping steps that already completed
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "bookmark", "scope", "update_only", "draft", "publish", "select", "pick_stack",
+                "remote", "push_remote", "all", "reviewers", "assignees", "labels",
+                "auto_merge", "auto_bookmark", "per_change",
+            ]
+        )]
+        resume: bool,
     },
 
     /// Sync current stack with remote
@@ -88,9 +128,62 @@ enum Commands {
         #[arg(long)]
         remote: Option<String>,
 
+        /// Git remote to push bookmarks to, e.g. your fork (defaults to ryu.push-remote, then --remote)
+        #[arg(long)]
+        push_remote: Option<String>,
+
         /// Sync all bookmarks in `trunk()`..@ (ignore tracking)
         #[arg(long, short)]
         all: bool,
+
+        /// Sync every stack of your bookmarks (ryu.stacks), not just the working copy's
+        #[arg(long)]
+        all_stacks: bool,
+    },
+
+    /// Merge the bottom of the stack and restack the rest
+    Land {
+        /// Bookmark to land up to (defaults to the bottom of the stack)
+        bookmark: Option<String>,
+
+        /// Land every PR up to the bookmark, or the whole stack, one at a time
+        #[arg(long, short)]
+        all: bool,
+
+        /// Merge method: merge, squash or rebase (defaults to ryu.merge-method, then squash)
+        #[arg(long)]
+        method: Option<MergeMethod>,
+
+        /// Add the bottom PR to the merge queue once it's ready, instead of merging it
+        #[arg(long, conflicts_with_all = ["all", "method"])]
+        queue: bool,
+
+        /// Take the bookmark's PR (default: the bottom one) out of the merge queue
+        #[arg(long, conflicts_with_all = ["all", "method", "queue"])]
+        dequeue: bool,
+
+        /// Dry run - show what would be landed without merging
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Git remote the PRs are opened against
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Git remote to push bookmarks to, e.g. your fork (defaults to ryu.push-remote, then --remote)
+        #[arg(long)]
+        push_remote: Option<String>,
+    },
+
+    /// Show each bookmark's PR: state, reviews, unresolved threads and CI
+    Status {
+        /// Print the status as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Git remote the PRs are opened against
+        #[arg(long)]
+        remote: Option<String>,
     },
 
     /// Authentication management
@@ -115,6 +208,11 @@ enum Commands {
         /// Associate with specific remote
         #[arg(long, short)]
         remote: Option<String>,
+
+        /// Reviewers, assignees and labels for these bookmarks' PRs, replacing
+        /// those given to `ryu submit`
+        #[command(flatten)]
+        triage: TriageArgs,
     },
 
     /// Stop tracking bookmarks
@@ -128,6 +226,32 @@ enum Commands {
     },
 }
 
+/// Reviewers, assignees and labels for PRs
+#[derive(Args)]
+struct TriageArgs {
+    /// Request a review from a user, or a team as org/team (repeatable)
+    #[arg(long = "reviewer", value_name = "USER")]
+    reviewers: Vec<String>,
+
+    /// Assign a user; @me is you (repeatable)
+    #[arg(long = "assignee", value_name = "USER")]
+    assignees: Vec<String>,
+
+    /// Add a label (repeatable)
+    #[arg(long = "label", value_name = "LABEL")]
+    labels: Vec<String>,
+}
+
+impl From<TriageArgs> for PrTriage {
+    fn from(args: TriageArgs) -> Self {
+        Self {
+            reviewers: args.reviewers,
+            assignees: args.assignees,
+            labels: args.labels,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum AuthPlatform {
     /// GitHub authentication
@@ -140,9 +264,43 @@ enum AuthPlatform {
         #[command(subcommand)]
         action: AuthAction,
     },
+    /// Gitea / Forgejo authentication
+    Gitea {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+    /// Bitbucket Data Center / Server authentication
+    Bitbucket {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+    /// Azure DevOps authentication
+    Azure {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+    /// Gerrit authentication
+    Gerrit {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
 }
 
-#[derive(Subcommand)]
+impl AuthPlatform {
+    /// Split into the platform and the requested action
+    const fn into_parts(self) -> (Platform, AuthAction) {
+        match self {
+            Self::Github { action } => (Platform::GitHub, action),
+            Self::Gitlab { action } => (Platform::GitLab, action),
+            Self::Gitea { action } => (Platform::Gitea, action),
+            Self::Bitbucket { action } => (Platform::Bitbucket, action),
+            Self::Azure { action } => (Platform::AzureDevOps, action),
+            Self::Gerrit { action } => (Platform::Gerrit, action),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Subcommand)]
 enum AuthAction {
     /// Test authentication
     Test,
@@ -150,9 +308,20 @@ enum AuthAction {
     Setup,
 }
 
+impl AuthAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Test => "test",
+            Self::Setup => "setup",
+        }
+    }
+}
+
 #[tokio::main]
+#[allow(clippy::too_many_lines)]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    cli::set_output_format(cli.output);
     let path = cli.path.unwrap_or_else(|| PathBuf::from("."));
 
     match cli.command {
@@ -171,8 +340,15 @@ async fn main() -> Result<()> {
             draft,
             publish,
             select,
+            pick_stack,
             remote,
+            push_remote,
             all,
+            triage,
+            auto_merge,
+            auto_bookmark,
+            per_change,
+            resume,
         }) => {
             // Determine scope from mutually exclusive flags (enforced by clap arg groups)
             #[allow(clippy::option_if_let_else)]
@@ -199,7 +375,14 @@ async fn main() -> Result<()> {
                     draft,
                     publish,
                     select,
+                    pick_stack,
                     all,
+                    push_remote: push_remote.as_deref(),
+                    triage: triage.into(),
+                    auto_merge,
+                    resume,
+                    auto_bookmark,
+                    per_change,
                 },
             )
             .await?;
@@ -208,7 +391,9 @@ async fn main() -> Result<()> {
             dry_run,
             confirm,
             remote,
+            push_remote,
             all,
+            all_stacks,
         }) => {
             cli::run_sync(
                 &path,
@@ -217,33 +402,65 @@ async fn main() -> Result<()> {
                     dry_run,
                     confirm,
                     all,
+                    all_stacks,
+                    push_remote,
                 },
             )
             .await?;
         }
-        Some(Commands::Auth { platform }) => match platform {
-            AuthPlatform::Github { action } => {
-                let action_str = match action {
-                    AuthAction::Test => "test",
-                    AuthAction::Setup => "setup",
-                };
-                cli::run_auth(Platform::GitHub, action_str).await?;
-            }
-            AuthPlatform::Gitlab { action } => {
-                let action_str = match action {
-                    AuthAction::Test => "test",
-                    AuthAction::Setup => "setup",
-                };
-                cli::run_auth(Platform::GitLab, action_str).await?;
+        Some(Commands::Land {
+            bookmark,
+            all,
+            method,
+            queue,
+            dequeue,
+            dry_run,
+            remote,
+            push_remote,
+        }) => {
+            cli::run_land(
+                &path,
+                remote.as_deref(),
+                cli::LandOptions {
+                    bookmark,
+                    all,
+                    method,
+                    queue,
+                    dequeue,
+                    dry_run,
+                    push_remote,
+                },
+            )
+            .await?;
+        }
+        Some(Commands::Status { json, remote }) => {
+            cli::run_status(&path, remote.as_deref(), json).await?;
+        }
+        Some(Commands::Auth { platform }) => {
+            if cli.output == cli::OutputFormat::Json {
+                anyhow::bail!("'ryu auth' has no JSON output");
             }
-        },
+            let (platform, action) = platform.into_parts();
+            cli::run_auth(platform, action.as_str()).await?;
+        }
         Some(Commands::Track {
             bookmarks,
             all,
             force,
             remote,
+            triage,
         }) => {
-            cli::run_track(&path, &bookmarks, cli::TrackOptions { all, force, remote }).await?;
+            cli::run_track(
+                &path,
+                &bookmarks,
+                cli::TrackOptions {
+                    all,
+                    force,
+                    remote,
+                    triage: triage.into(),
+                },
+            )
+            .await?;
         }
         Some(Commands::Untrack { bookmarks, all }) => {
             cli::run_untrack(&path, &bookmarks, cli::UntrackOptions { all }).await?;
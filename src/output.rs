@@ -0,0 +1,123 @@
+//! Machine-readable output documents
+//!
+//! With `--output json`, each `ryu` command prints one JSON document to
+//! stdout; everything meant for people goes to stderr instead. Commands that
+//! stop early (nothing to do, or aborted at a prompt) print no document, and
+//! failed commands exit non-zero. Every document has the same envelope:
+//!
+//! ```json
+//! { "schema_version": 1, "kind": "plan", "data": { ... } }
+//! ```
+//!
+//! `kind` says what `data` holds:
+//!
+//! | kind       | command                              | data                        |
+//! |------------|--------------------------------------|-----------------------------|
+//! | `stack`    | `ryu`                                | [`StackDocument`]           |
+//! | `plan`     | `ryu submit`/`sync` with `--dry-run` | `SubmissionPlan`            |
+//! | `result`   | `ryu submit`/`sync`                  | `SubmissionResult`          |
+//! | `status`   | `ryu status`                         | `[BookmarkStatus]`          |
+//! | `tracking` | `ryu track`/`untrack`                | `TrackingState`             |
+//! | `land`     | `ryu land`                           | [`LandDocument`]            |
+//!
+//! Field names are the Rust field names; enums are `snake_case` strings,
+//! except execution steps and constraints, which are objects tagged with a
+//! `type` field. Adding fields or kinds keeps [`OUTPUT_SCHEMA_VERSION`];
+//! renaming or removing them, or changing their meaning, bumps it.
+
+use crate::error::{Error, Result};
+use crate::submit::LandTarget;
+use crate::tracking::CachedPr;
+use crate::types::{ChangeGraph, PullRequest};
+use serde::Serialize;
+
+/// Version of the JSON output schema
+pub const OUTPUT_SCHEMA_VERSION: u32 = 1;
+
+/// What a JSON document holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    /// The stack, for the default view
+    Stack,
+    /// A submission plan (dry runs)
+    Plan,
+    /// The result of executing a submission
+    Result,
+    /// Live PR status per bookmark
+    Status,
+    /// Tracked bookmarks
+    Tracking,
+    /// PRs landed or planned to land
+    Land,
+}
+
+/// A versioned JSON document
+#[derive(Debug, Clone, Serialize)]
+pub struct Document<T> {
+    /// Always [`OUTPUT_SCHEMA_VERSION`]
+    pub schema_version: u32,
+    /// What `data` holds
+    pub kind: DocumentKind,
+    /// The payload
+    pub data: T,
+}
+
+impl<T: Serialize> Document<T> {
+    /// Wrap `data` in the current schema version's envelope
+    pub const fn new(kind: DocumentKind, data: T) -> Self {
+        Self {
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            kind,
+            data,
+        }
+    }
+
+    /// Render as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Internal(format!("failed to serialize output: {e}")))
+    }
+}
+
+/// The stack as the default view shows it
+#[derive(Debug, Clone, Serialize)]
+pub struct StackDocument<'a> {
+    /// Bookmarks and the stack from trunk to the working copy
+    pub graph: &'a ChangeGraph,
+    /// Names of tracked bookmarks
+    pub tracked: Vec<&'a str>,
+    /// Cached PRs of tracked bookmarks
+    pub prs: Vec<&'a CachedPr>,
+}
+
+/// PRs `ryu land` merged, or would merge with `--dry-run`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LandDocument {
+    /// Whether this is a dry run, so nothing was merged
+    pub dry_run: bool,
+    /// PRs planned to land (dry run only), bottom first
+    pub planned: Vec<LandTarget>,
+    /// PRs merged, bottom first
+    pub merged: Vec<PullRequest>,
+    /// PRs added to the merge queue
+    pub queued: Vec<PullRequest>,
+    /// PRs taken out of the merge queue (or that would be, on a dry run)
+    pub dequeued: Vec<PullRequest>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_envelope() {
+        let json = Document::new(DocumentKind::Tracking, vec!["feat-a"])
+            .to_json()
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["schema_version"], OUTPUT_SCHEMA_VERSION);
+        assert_eq!(value["kind"], "tracking");
+        assert_eq!(value["data"][0], "feat-a");
+    }
+}
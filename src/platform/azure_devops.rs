@@ -0,0 +1,431 @@
+//! Azure DevOps Repos platform service implementation
+
+use crate::error::{Error, Result};
+use crate::platform::PlatformService;
+use crate::types::{ClosedPullRequest, Platform, PlatformConfig, PrComment, PullRequest};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Azure DevOps service using reqwest against the Git REST API
+pub struct AzureDevOpsService {
+    client: Client,
+    token: String,
+    host: String,
+    org: String,
+    project: String,
+    config: PlatformConfig,
+}
+
+/// List envelope used by Azure DevOps collection endpoints
+#[derive(Deserialize)]
+struct ValueList<T> {
+    value: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzurePullRequest {
+    pull_request_id: u64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    source_ref_name: String,
+    target_ref_name: String,
+    #[serde(default)]
+    is_draft: bool,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    closed_date: Option<DateTime<Utc>>,
+    repository: AzureRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureRepository {
+    web_url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureThread {
+    id: u64,
+    #[serde(default)]
+    is_deleted: bool,
+    #[serde(default)]
+    comments: Vec<AzureComment>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AzureComment {
+    id: u64,
+    content: Option<String>,
+    comment_type: Option<String>,
+}
+
+impl From<AzurePullRequest> for PullRequest {
+    fn from(pr: AzurePullRequest) -> Self {
+        Self {
+            number: pr.pull_request_id,
+            html_url: format!(
+                "{}/pullrequest/{}",
+                pr.repository.web_url, pr.pull_request_id
+            ),
+            base_ref: branch_name(&pr.target_ref_name).to_string(),
+            head_ref: branch_name(&pr.source_ref_name).to_string(),
+            title: pr.title,
+            body: pr.description.unwrap_or_default(),
+            node_id: None, // Azure DevOps doesn't use GraphQL node IDs
+            is_draft: pr.is_draft,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatePullPayload {
+    source_ref_name: String,
+    target_ref_name: String,
+    title: String,
+    description: String,
+    is_draft: bool,
+}
+
+/// REST API version sent with every request
+const API_VERSION: &str = "7.1";
+
+/// Default request timeout in seconds
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// ID of the first comment in a thread (comment IDs are per-thread)
+const FIRST_COMMENT_ID: u64 = 1;
+
+/// Thread status for the stack comment: `closed` so it never blocks completion
+/// under a "comments must be resolved" branch policy
+const STACK_THREAD_STATUS: &str = "closed";
+
+/// Strip the `refs/heads/` prefix Azure DevOps puts on branch names
+fn branch_name(ref_name: &str) -> &str {
+    ref_name.strip_prefix("refs/heads/").unwrap_or(ref_name)
+}
+
+/// Qualify a branch name as a full ref
+fn branch_ref(branch: &str) -> String {
+    format!("refs/heads/{branch}")
+}
+
+impl AzureDevOpsService {
+    /// Create a new Azure DevOps service
+    ///
+    /// `owner` is `<org>/<project>`, as produced by remote URL parsing.
+    pub fn new(token: String, owner: String, repo: String, host: Option<String>) -> Result<Self> {
+        let (org, project) = owner
+            .split_once('/')
+            .map(|(org, project)| (org.to_string(), project.to_string()))
+            .ok_or_else(|| {
+                Error::AzureDevOpsApi(format!("expected <org>/<project> owner, got: {owner}"))
+            })?;
+
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| Error::AzureDevOpsApi(format!("failed to create HTTP client: {e}")))?;
+
+        Ok(Self {
+            client,
+            token,
+            host: host.clone().unwrap_or_else(|| "dev.azure.com".to_string()),
+            org,
+            project,
+            config: PlatformConfig {
+                platform: Platform::AzureDevOps,
+                owner,
+                repo,
+                host,
+            },
+        })
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "https://{}/{}/{}/_apis/git/repositories/{}{}",
+            self.host,
+            urlencoding::encode(&self.org),
+            urlencoding::encode(&self.project),
+            urlencoding::encode(&self.config.repo),
+            path
+        )
+    }
+
+    /// Attach PAT auth (basic auth with an empty username) and the API version
+    fn authed(&self, request: RequestBuilder) -> RequestBuilder {
+        request
+            .basic_auth("", Some(&self.token))
+            .query(&[("api-version", API_VERSION)])
+    }
+
+    async fn update_pr(&self, pr_number: u64, payload: serde_json::Value) -> Result<PullRequest> {
+        let url = self.api_url(&format!("/pullrequests/{pr_number}"));
+
+        let pr: AzurePullRequest = self
+            .authed(self.client.patch(&url))
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::AzureDevOpsApi(e.to_string()))?
+            .json()
+            .await?;
+
+        Ok(pr.into())
+    }
+}
+
+#[async_trait]
+impl PlatformService for AzureDevOpsService {
+    async fn find_existing_pr(&self, head_branch: &str) -> Result<Option<PullRequest>> {
+        debug!(head_branch, "finding existing PR");
+        let url = self.api_url("/pullrequests");
+        let source_ref = branch_ref(head_branch);
+
+        let prs: ValueList<AzurePullRequest> = self
+            .authed(self.client.get(&url))
+            .query(&[
+                ("searchCriteria.sourceRefName", source_ref.as_str()),
+                ("searchCriteria.status", "active"),
+            ])
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::AzureDevOpsApi(e.to_string()))?
+            .json()
+            .await?;
+
+        let result: Option<PullRequest> = prs.value.into_iter().next().map(Into::into);
+        if let Some(ref pr) = result {
+            debug!(pr_number = pr.number, "found existing PR");
+        } else {
+            debug!("no existing PR found");
+        }
+        Ok(result)
+    }
+
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        debug!(head_branch, "finding closed PR");
+        let url = self.api_url("/pullrequests");
+        let source_ref = branch_ref(head_branch);
+
+        // `status` takes a single value, so ask for every status, newest
+        // first, and skip the active ones
+        let prs: ValueList<AzurePullRequest> = self
+            .authed(self.client.get(&url))
+            .query(&[
+                ("searchCriteria.sourceRefName", source_ref.as_str()),
+                ("searchCriteria.status", "all"),
+            ])
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::AzureDevOpsApi(e.to_string()))?
+            .json()
+            .await?;
+
+        Ok(prs
+            .value
+            .into_iter()
+            .find(|pr| pr.status == "completed" || pr.status == "abandoned")
+            .map(|pr| {
+                let merged = pr.status == "completed";
+                let closed_at = pr.closed_date;
+                ClosedPullRequest {
+                    pr: pr.into(),
+                    merged,
+                    closed_at,
+                }
+            }))
+    }
+
+    async fn create_pr_with_options(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        debug!(head, base, draft, "creating PR");
+        let url = self.api_url("/pullrequests");
+
+        let payload = CreatePullPayload {
+            source_ref_name: branch_ref(head),
+            target_ref_name: branch_ref(base),
+            title: title.to_string(),
+            description: body.to_string(),
+            is_draft: draft,
+        };
+
+        let pr: AzurePullRequest = self
+            .authed(self.client.post(&url))
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::AzureDevOpsApi(e.to_string()))?
+            .json()
+            .await?;
+
+        let pr: PullRequest = pr.into();
+        debug!(pr_number = pr.number, "created PR");
+        Ok(pr)
+    }
+
+    async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<PullRequest> {
+        debug!(pr_number, new_base, "updating PR base");
+        let pr = self
+            .update_pr(
+                pr_number,
+                serde_json::json!({ "targetRefName": branch_ref(new_base) }),
+            )
+            .await?;
+        debug!(pr_number, "updated PR base");
+        Ok(pr)
+    }
+
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        debug!(pr_number, "updating PR title and description");
+        let pr = self
+            .update_pr(
+                pr_number,
+                serde_json::json!({ "title": title, "description": body }),
+            )
+            .await?;
+        debug!(pr_number, "updated PR title and description");
+        Ok(pr)
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "abandoning PR");
+        self.update_pr(pr_number, serde_json::json!({ "status": "abandoned" }))
+            .await?;
+        debug!(pr_number, "abandoned PR");
+        Ok(())
+    }
+
+    async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
+        debug!(pr_number, "publishing PR");
+        let pr = self
+            .update_pr(pr_number, serde_json::json!({ "isDraft": false }))
+            .await?;
+        debug!(pr_number, "published PR");
+        Ok(pr)
+    }
+
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<PrComment>> {
+        debug!(pr_number, "listing PR comments");
+        let url = self.api_url(&format!("/pullRequests/{pr_number}/threads"));
+
+        let threads: ValueList<AzureThread> = self
+            .authed(self.client.get(&url))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::AzureDevOpsApi(e.to_string()))?
+            .json()
+            .await?;
+
+        // A thread is surfaced as a single comment: its ID plus the body of its
+        // first comment. System threads (votes, pushes) are skipped.
+        let result: Vec<PrComment> = threads
+            .value
+            .into_iter()
+            .filter(|t| !t.is_deleted)
+            .filter_map(|t| {
+                let first = t.comments.into_iter().find(|c| c.id == FIRST_COMMENT_ID)?;
+                if first.comment_type.as_deref() == Some("system") {
+                    return None;
+                }
+                Some(PrComment {
+                    id: t.id,
+                    body: first.content.unwrap_or_default(),
+                })
+            })
+            .collect();
+        debug!(pr_number, count = result.len(), "listed PR comments");
+        Ok(result)
+    }
+
+    async fn create_pr_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        debug!(pr_number, "creating PR comment");
+        let url = self.api_url(&format!("/pullRequests/{pr_number}/threads"));
+
+        self.authed(self.client.post(&url))
+            .json(&serde_json::json!({
+                "comments": [{ "parentCommentId": 0, "content": body, "commentType": "text" }],
+                "status": STACK_THREAD_STATUS,
+            }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::AzureDevOpsApi(e.to_string()))?;
+
+        debug!(pr_number, "created PR comment");
+        Ok(())
+    }
+
+    async fn update_pr_comment(&self, pr_number: u64, comment_id: u64, body: &str) -> Result<()> {
+        debug!(pr_number, comment_id, "updating PR comment");
+        // `comment_id` is the thread ID (see `list_pr_comments`)
+        let url = self.api_url(&format!(
+            "/pullRequests/{pr_number}/threads/{comment_id}/comments/{FIRST_COMMENT_ID}"
+        ));
+
+        self.authed(self.client.patch(&url))
+            .json(&serde_json::json!({ "content": body }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::AzureDevOpsApi(e.to_string()))?;
+
+        debug!(pr_number, comment_id, "updated PR comment");
+        Ok(())
+    }
+
+    fn config(&self) -> &PlatformConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_name_strips_refs_heads() {
+        assert_eq!(branch_name("refs/heads/feat/login"), "feat/login");
+        assert_eq!(branch_name("main"), "main");
+    }
+
+    #[test]
+    fn test_owner_split_into_org_and_project() {
+        let service = AzureDevOpsService::new(
+            "token".to_string(),
+            "contoso/Web Apps".to_string(),
+            "frontend".to_string(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            service.api_url("/pullrequests"),
+            "https://dev.azure.com/contoso/Web%20Apps/_apis/git/repositories/frontend/pullrequests"
+        );
+    }
+}
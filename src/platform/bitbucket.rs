@@ -0,0 +1,491 @@
+//! Bitbucket Data Center / Server platform service implementation
+
+use crate::error::{Error, Result};
+use crate::platform::PlatformService;
+use crate::types::{ClosedPullRequest, Platform, PlatformConfig, PrComment, PullRequest};
+use async_trait::async_trait;
+use chrono::DateTime;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Bitbucket Server service using reqwest against the REST 1.0 API
+pub struct BitbucketService {
+    client: Client,
+    token: String,
+    host: String,
+    config: PlatformConfig,
+}
+
+/// Paged response envelope used by all Bitbucket list endpoints
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PagedResponse<T> {
+    values: Vec<T>,
+    #[serde(default = "default_true")]
+    is_last_page: bool,
+    next_page_start: Option<u64>,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct BitbucketPullRequest {
+    id: u64,
+    version: u64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "fromRef")]
+    from_ref: BitbucketRef,
+    #[serde(rename = "toRef")]
+    to_ref: BitbucketRef,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    state: String,
+    /// Milliseconds since the epoch
+    #[serde(rename = "closedDate", default)]
+    closed_date: Option<i64>,
+    links: BitbucketLinks,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitbucketRef {
+    display_id: String,
+}
+
+#[derive(Deserialize)]
+struct BitbucketLinks {
+    #[serde(rename = "self", default)]
+    self_links: Vec<BitbucketLink>,
+}
+
+#[derive(Deserialize)]
+struct BitbucketLink {
+    href: String,
+}
+
+#[derive(Deserialize)]
+struct BitbucketComment {
+    id: u64,
+    version: u64,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct BitbucketActivity {
+    action: String,
+    comment: Option<BitbucketComment>,
+}
+
+impl From<BitbucketPullRequest> for PullRequest {
+    fn from(pr: BitbucketPullRequest) -> Self {
+        Self {
+            number: pr.id,
+            html_url: pr
+                .links
+                .self_links
+                .into_iter()
+                .next()
+                .map(|l| l.href)
+                .unwrap_or_default(),
+            base_ref: pr.to_ref.display_id,
+            head_ref: pr.from_ref.display_id,
+            title: pr.title,
+            body: pr.description.unwrap_or_default(),
+            node_id: None, // Bitbucket doesn't use GraphQL node IDs
+            is_draft: pr.draft,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RefPayload {
+    id: String,
+    repository: RepositoryPayload,
+}
+
+#[derive(Serialize)]
+struct RepositoryPayload {
+    slug: String,
+    project: ProjectPayload,
+}
+
+#[derive(Serialize)]
+struct ProjectPayload {
+    key: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatePullPayload {
+    title: String,
+    description: String,
+    from_ref: RefPayload,
+    to_ref: RefPayload,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    draft: Option<bool>,
+}
+
+/// Default request timeout in seconds
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Page size for list endpoints
+const PAGE_LIMIT: u64 = 100;
+
+impl BitbucketService {
+    /// Create a new Bitbucket Server service
+    ///
+    /// `owner` is the project key (or `~user` for personal repos) and `repo`
+    /// the repository slug. `host` may include a port and context path.
+    pub fn new(token: String, owner: String, repo: String, host: String) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| Error::BitbucketApi(format!("failed to create HTTP client: {e}")))?;
+
+        Ok(Self {
+            client,
+            token,
+            host: host.clone(),
+            config: PlatformConfig {
+                platform: Platform::Bitbucket,
+                owner,
+                repo,
+                host: Some(host),
+            },
+        })
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "https://{}/rest/api/1.0/projects/{}/repos/{}{}",
+            self.host,
+            urlencoding::encode(&self.config.owner),
+            urlencoding::encode(&self.config.repo),
+            path
+        )
+    }
+
+    fn ref_payload(&self, branch: &str) -> RefPayload {
+        RefPayload {
+            id: format!("refs/heads/{branch}"),
+            repository: RepositoryPayload {
+                slug: self.config.repo.clone(),
+                project: ProjectPayload {
+                    key: self.config.owner.clone(),
+                },
+            },
+        }
+    }
+
+    /// Fetch every page of a paged list endpoint
+    async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>> {
+        let mut values = Vec::new();
+        let mut start = 0u64;
+
+        loop {
+            let page: PagedResponse<T> = self
+                .client
+                .get(url)
+                .bearer_auth(&self.token)
+                .query(query)
+                .query(&[("start", start), ("limit", PAGE_LIMIT)])
+                .send()
+                .await?
+                .error_for_status()
+                .map_err(|e| Error::BitbucketApi(e.to_string()))?
+                .json()
+                .await?;
+
+            values.extend(page.values);
+
+            match page.next_page_start {
+                Some(next) if !page.is_last_page => start = next,
+                _ => break,
+            }
+        }
+
+        Ok(values)
+    }
+
+    async fn get_pr(&self, pr_number: u64) -> Result<BitbucketPullRequest> {
+        let url = self.api_url(&format!("/pull-requests/{pr_number}"));
+
+        self.client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::BitbucketApi(e.to_string()))?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Update a PR, supplying the current version for optimistic locking
+    ///
+    /// The title is required on every update; the current one is kept unless
+    /// `payload` sets it.
+    async fn update_pr(
+        &self,
+        pr_number: u64,
+        mut payload: serde_json::Value,
+    ) -> Result<PullRequest> {
+        let current = self.get_pr(pr_number).await?;
+        payload["version"] = current.version.into();
+        if payload.get("title").is_none() {
+            payload["title"] = current.title.into();
+        }
+
+        let url = self.api_url(&format!("/pull-requests/{pr_number}"));
+        let pr: BitbucketPullRequest = self
+            .client
+            .put(&url)
+            .bearer_auth(&self.token)
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::BitbucketApi(e.to_string()))?
+            .json()
+            .await?;
+
+        Ok(pr.into())
+    }
+}
+
+#[async_trait]
+impl PlatformService for BitbucketService {
+    async fn find_existing_pr(&self, head_branch: &str) -> Result<Option<PullRequest>> {
+        debug!(head_branch, "finding existing PR");
+        let url = self.api_url("/pull-requests");
+        let at = format!("refs/heads/{head_branch}");
+
+        // `at` + OUTGOING filters by source branch
+        let prs: Vec<BitbucketPullRequest> = self
+            .get_all_pages(
+                &url,
+                &[("state", "OPEN"), ("direction", "OUTGOING"), ("at", &at)],
+            )
+            .await?;
+
+        let result: Option<PullRequest> = prs
+            .into_iter()
+            .find(|pr| pr.from_ref.display_id == head_branch)
+            .map(Into::into);
+        if let Some(ref pr) = result {
+            debug!(pr_number = pr.number, "found existing PR");
+        } else {
+            debug!("no existing PR found");
+        }
+        Ok(result)
+    }
+
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        debug!(head_branch, "finding closed PR");
+        let url = self.api_url("/pull-requests");
+        let at = format!("refs/heads/{head_branch}");
+
+        // `state` takes a single value, so ask for every state, newest first,
+        // and skip the open ones
+        let prs: Vec<BitbucketPullRequest> = self
+            .get_all_pages(
+                &url,
+                &[
+                    ("state", "ALL"),
+                    ("order", "NEWEST"),
+                    ("direction", "OUTGOING"),
+                    ("at", &at),
+                ],
+            )
+            .await?;
+
+        Ok(prs
+            .into_iter()
+            .filter(|pr| pr.from_ref.display_id == head_branch)
+            .find(|pr| pr.state == "MERGED" || pr.state == "DECLINED")
+            .map(|pr| {
+                let merged = pr.state == "MERGED";
+                let closed_at = pr.closed_date.and_then(DateTime::from_timestamp_millis);
+                ClosedPullRequest {
+                    pr: pr.into(),
+                    merged,
+                    closed_at,
+                }
+            }))
+    }
+
+    async fn create_pr_with_options(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        debug!(head, base, draft, "creating PR");
+        let url = self.api_url("/pull-requests");
+
+        // Only send `draft` when set: servers before 8.18 reject unknown fields
+        let payload = CreatePullPayload {
+            title: title.to_string(),
+            description: body.to_string(),
+            from_ref: self.ref_payload(head),
+            to_ref: self.ref_payload(base),
+            draft: if draft { Some(true) } else { None },
+        };
+
+        let pr: BitbucketPullRequest = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::BitbucketApi(e.to_string()))?
+            .json()
+            .await?;
+
+        let pr: PullRequest = pr.into();
+        debug!(pr_number = pr.number, "created PR");
+        Ok(pr)
+    }
+
+    async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<PullRequest> {
+        debug!(pr_number, new_base, "updating PR base");
+        let to_ref = serde_json::to_value(self.ref_payload(new_base))?;
+        let pr = self
+            .update_pr(pr_number, serde_json::json!({ "toRef": to_ref }))
+            .await?;
+        debug!(pr_number, "updated PR base");
+        Ok(pr)
+    }
+
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        debug!(pr_number, "updating PR title and description");
+        let pr = self
+            .update_pr(
+                pr_number,
+                serde_json::json!({ "title": title, "description": body }),
+            )
+            .await?;
+        debug!(pr_number, "updated PR title and description");
+        Ok(pr)
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "declining PR");
+        let current = self.get_pr(pr_number).await?;
+        let url = self.api_url(&format!("/pull-requests/{pr_number}/decline"));
+
+        self.client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .query(&[("version", current.version)])
+            .json(&serde_json::json!({}))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::BitbucketApi(e.to_string()))?;
+
+        debug!(pr_number, "declined PR");
+        Ok(())
+    }
+
+    async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
+        debug!(pr_number, "publishing PR");
+        let pr = self
+            .update_pr(pr_number, serde_json::json!({ "draft": false }))
+            .await?;
+        debug!(pr_number, "published PR");
+        Ok(pr)
+    }
+
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<PrComment>> {
+        debug!(pr_number, "listing PR comments");
+        // Top-level comments are only listable through the activity stream
+        let url = self.api_url(&format!("/pull-requests/{pr_number}/activities"));
+
+        let activities: Vec<BitbucketActivity> = self.get_all_pages(&url, &[]).await?;
+
+        let result: Vec<PrComment> = activities
+            .into_iter()
+            .filter(|a| a.action == "COMMENTED")
+            .filter_map(|a| a.comment)
+            .map(|c| PrComment {
+                id: c.id,
+                body: c.text,
+            })
+            .collect();
+        debug!(pr_number, count = result.len(), "listed PR comments");
+        Ok(result)
+    }
+
+    async fn create_pr_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        debug!(pr_number, "creating PR comment");
+        let url = self.api_url(&format!("/pull-requests/{pr_number}/comments"));
+
+        self.client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .json(&serde_json::json!({ "text": body }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::BitbucketApi(e.to_string()))?;
+
+        debug!(pr_number, "created PR comment");
+        Ok(())
+    }
+
+    async fn update_pr_comment(&self, pr_number: u64, comment_id: u64, body: &str) -> Result<()> {
+        debug!(pr_number, comment_id, "updating PR comment");
+        let url = self.api_url(&format!("/pull-requests/{pr_number}/comments/{comment_id}"));
+
+        // Comment edits require the current version for optimistic locking
+        let current: BitbucketComment = self
+            .client
+            .get(&url)
+            .bearer_auth(&self.token)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::BitbucketApi(e.to_string()))?
+            .json()
+            .await?;
+
+        self.client
+            .put(&url)
+            .bearer_auth(&self.token)
+            .json(&serde_json::json!({ "text": body, "version": current.version }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::BitbucketApi(e.to_string()))?;
+
+        debug!(pr_number, comment_id, "updated PR comment");
+        Ok(())
+    }
+
+    fn config(&self) -> &PlatformConfig {
+        &self.config
+    }
+}
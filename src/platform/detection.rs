@@ -14,10 +14,37 @@ static RE_SSH: LazyLock<Regex> =
 static RE_HTTPS: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"https?://[^/]+/(.+?)(?:\.git)?$").unwrap());
 
-/// Detect platform (GitHub or GitLab) from a remote URL
+/// Public Gitea/Forgejo instances recognized without configuration
+const KNOWN_GITEA_HOSTS: &[&str] = &["gitea.com", "codeberg.org"];
+
+/// Default SSH port of Bitbucket Data Center / Server
+const BITBUCKET_SSH_PORT: u16 = 7999;
+
+/// Azure DevOps Services hosts (HTTPS and SSH)
+const AZURE_DEVOPS_HOSTS: &[&str] = &["dev.azure.com", "ssh.dev.azure.com"];
+
+/// Default SSH port of Gerrit
+const GERRIT_SSH_PORT: u16 = 29418;
+
+/// Suffix of Google-hosted Gerrit git hosts
+const GOOGLESOURCE_SUFFIX: &str = ".googlesource.com";
+
+/// jj config key that overrides platform detection (e.g. `ryu.platform = "local"`)
+pub const PLATFORM_CONFIG_KEY: &str = "ryu.platform";
+
+/// Detect platform (GitHub, GitLab, Gitea, Bitbucket, Azure DevOps, Gerrit or
+/// the local forge) from a remote URL
 pub fn detect_platform(url: &str) -> Option<Platform> {
+    // Local remotes have no host and are served by the local forge
+    if url.starts_with("file://") {
+        return Some(Platform::Local);
+    }
+
     let gh_host = env::var("GH_HOST").ok();
     let gitlab_host = env::var("GITLAB_HOST").ok();
+    let gitea_host = env::var("GITEA_HOST").ok();
+    let bitbucket_host = env::var("BITBUCKET_HOST").ok();
+    let gerrit_host = env::var("GERRIT_HOST").ok();
 
     let hostname = extract_hostname(url)?;
 
@@ -37,15 +64,280 @@ pub fn detect_platform(url: &str) -> Option<Platform> {
         return Some(Platform::GitLab);
     }
 
+    // Check Gitea/Forgejo
+    if KNOWN_GITEA_HOSTS.contains(&hostname.as_str())
+        || gitea_host.as_ref().is_some_and(|h| hostname == *h)
+    {
+        return Some(Platform::Gitea);
+    }
+
+    // Check Azure DevOps (including legacy `<org>.visualstudio.com` hosts)
+    if AZURE_DEVOPS_HOSTS.contains(&hostname.as_str()) || hostname.ends_with(".visualstudio.com") {
+        return Some(Platform::AzureDevOps);
+    }
+
+    // Check Gerrit (configured, Google-hosted, or SSH on Gerrit's default port)
+    if gerrit_host.as_ref().is_some_and(|h| hostname == *h)
+        || hostname.ends_with(GOOGLESOURCE_SUFFIX)
+        || url::Url::parse(url)
+            .is_ok_and(|u| u.scheme() == "ssh" && u.port() == Some(GERRIT_SSH_PORT))
+    {
+        return Some(Platform::Gerrit);
+    }
+
+    // Check Bitbucket Data Center / Server (no public host - configured or by URL shape)
+    if bitbucket_host.as_ref().is_some_and(|h| hostname == *h) || is_bitbucket_server_url(url) {
+        return Some(Platform::Bitbucket);
+    }
+
     None
 }
 
+/// Check whether a URL has the shape of a Bitbucket Server remote
+///
+/// Matches `https://host/scm/<project>/<repo>.git` and SSH remotes on
+/// Bitbucket's default port (`ssh://git@host:7999/<project>/<repo>.git`).
+fn is_bitbucket_server_url(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+
+    match parsed.scheme() {
+        "ssh" => parsed.port() == Some(BITBUCKET_SSH_PORT),
+        "http" | "https" => parsed
+            .path_segments()
+            .is_some_and(|mut segments| segments.any(|s| s == "scm")),
+        _ => false,
+    }
+}
+
+/// Parse a Bitbucket Server remote into `(api_host, project_key, repo_slug)`
+///
+/// For HTTP remotes the API host keeps any explicit port and context path
+/// (`https://host:8443/bitbucket/scm/PROJ/repo.git` → `host:8443/bitbucket`).
+/// SSH remotes use a separate port, so only the hostname is kept.
+fn parse_bitbucket_remote(url: &str) -> Result<(String, String, String)> {
+    let invalid = || Error::Parse(format!("cannot parse Bitbucket remote URL: {url}"));
+
+    let (host, path) = match url::Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "ssh" || parsed.scheme().starts_with("http") => {
+            let hostname = parsed.host_str().ok_or_else(invalid)?.to_string();
+            let segments: Vec<String> = parsed
+                .path_segments()
+                .ok_or_else(invalid)?
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+
+            if parsed.scheme() == "ssh" {
+                (hostname, segments)
+            } else {
+                // Anything before `/scm/` is the server's context path
+                let (context, repo_path) = segments
+                    .iter()
+                    .position(|s| s == "scm")
+                    .map_or((&segments[..0], &segments[..]), |idx| {
+                        (&segments[..idx], &segments[idx + 1..])
+                    });
+                let mut host = parsed
+                    .port()
+                    .map_or_else(|| hostname.clone(), |port| format!("{hostname}:{port}"));
+                for part in context {
+                    host.push('/');
+                    host.push_str(part);
+                }
+                (host, repo_path.to_vec())
+            }
+        }
+        // scp-like `git@host:PROJ/repo.git`
+        _ => {
+            let hostname = extract_hostname(url).ok_or_else(invalid)?;
+            let path = RE_SSH
+                .captures(url)
+                .and_then(|c| c.get(1))
+                .ok_or_else(invalid)?
+                .as_str();
+            (hostname, path.split('/').map(String::from).collect())
+        }
+    };
+
+    let [project, repo] = path.as_slice() else {
+        return Err(Error::Parse(format!(
+            "invalid Bitbucket repo path (expected <project>/<repo>): {url}"
+        )));
+    };
+
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+    Ok((host, project.clone(), repo.to_string()))
+}
+
+/// Parse an Azure DevOps remote into `("<org>/<project>", repo)`
+///
+/// Handles `https://dev.azure.com/<org>/<project>/_git/<repo>`,
+/// `git@ssh.dev.azure.com:v3/<org>/<project>/<repo>` and the legacy
+/// `https://<org>.visualstudio.com/[DefaultCollection/]<project>/_git/<repo>`.
+fn parse_azure_devops_remote(url: &str) -> Result<(String, String)> {
+    let invalid = || Error::Parse(format!("cannot parse Azure DevOps remote URL: {url}"));
+    let hostname = extract_hostname(url).ok_or_else(invalid)?;
+
+    let path = match url::Url::parse(url) {
+        Ok(parsed) if parsed.has_host() => parsed.path().to_string(),
+        // scp-like `git@ssh.dev.azure.com:v3/...`
+        _ => url.split_once(':').ok_or_else(invalid)?.1.to_string(),
+    };
+
+    // Project names may contain spaces, which appear percent-encoded
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            urlencoding::decode(s).map_or_else(|_| s.to_string(), std::borrow::Cow::into_owned)
+        })
+        .collect();
+
+    let (org, project, repo) = if segments.first().is_some_and(|s| s == "v3") {
+        let [_, org, project, repo] = segments.as_slice() else {
+            return Err(invalid());
+        };
+        (org.clone(), project.clone(), repo.clone())
+    } else {
+        let git_idx = segments
+            .iter()
+            .position(|s| s == "_git")
+            .ok_or_else(invalid)?;
+        let repo = segments.get(git_idx + 1).ok_or_else(invalid)?;
+
+        let (org, scope) = match hostname.strip_suffix(".visualstudio.com") {
+            Some(org) => (org.to_string(), &segments[..git_idx]),
+            None if git_idx > 0 => (segments[0].clone(), &segments[1..git_idx]),
+            None => return Err(invalid()),
+        };
+
+        // `_git` directly after the org means the project shares the repo's name
+        let project = scope
+            .iter()
+            .rfind(|s| *s != "DefaultCollection")
+            .unwrap_or(repo);
+        (org, project.clone(), repo.clone())
+    };
+
+    let repo = repo.strip_suffix(".git").unwrap_or(&repo).to_string();
+    Ok((format!("{org}/{project}"), repo))
+}
+
+/// Parse a Gerrit remote into `(rest_host, owner, repo)`
+///
+/// Gerrit project names are arbitrary paths, so `owner` is everything before
+/// the last segment (possibly empty). The authenticated `/a/` prefix of HTTP
+/// remotes is dropped, and `<x>.googlesource.com` maps to its review host
+/// `<x>-review.googlesource.com`.
+fn parse_gerrit_remote(url: &str) -> Result<(String, String, String)> {
+    let invalid = || Error::Parse(format!("cannot parse Gerrit remote URL: {url}"));
+    let hostname = extract_hostname(url).ok_or_else(invalid)?;
+
+    let path = match url::Url::parse(url) {
+        Ok(parsed) if parsed.has_host() => parsed.path().to_string(),
+        // scp-like `user@host:project`
+        _ => url.split_once(':').ok_or_else(invalid)?.1.to_string(),
+    };
+    let path = path.trim_start_matches('/');
+    let path = path.strip_prefix("a/").unwrap_or(path);
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if path.is_empty() {
+        return Err(invalid());
+    }
+
+    let (owner, repo) = path.rsplit_once('/').unwrap_or(("", path));
+
+    let rest_host = match hostname.strip_suffix(GOOGLESOURCE_SUFFIX) {
+        Some(name) if !name.ends_with("-review") => format!("{name}-review{GOOGLESOURCE_SUFFIX}"),
+        _ => hostname,
+    };
+    Ok((rest_host, owner.to_string(), repo.to_string()))
+}
+
+/// Local forge config for a remote: the repo is named after the remote's last
+/// path segment
+fn local_platform_config(url: &str) -> PlatformConfig {
+    let repo = url
+        .trim_end_matches('/')
+        .rsplit(['/', ':'])
+        .next()
+        .map(|name| name.strip_suffix(".git").unwrap_or(name))
+        .filter(|name| !name.is_empty())
+        .unwrap_or("repo");
+
+    PlatformConfig {
+        platform: Platform::Local,
+        owner: "local".to_string(),
+        repo: repo.to_string(),
+        host: None,
+    }
+}
+
+/// Resolve the platform config for a remote, honoring the
+/// [`PLATFORM_CONFIG_KEY`] setting
+///
+/// Only `"local"` can currently be forced; anything else is an error so a
+/// typo doesn't silently fall back to detection.
+pub fn resolve_platform_config(
+    url: &str,
+    platform_setting: Option<&str>,
+) -> Result<PlatformConfig> {
+    match platform_setting {
+        None => parse_repo_info(url),
+        Some("local") => Ok(local_platform_config(url)),
+        Some(other) => Err(Error::Config(format!(
+            "unsupported {PLATFORM_CONFIG_KEY} value '{other}' (expected \"local\")"
+        ))),
+    }
+}
+
 /// Parse repository info (owner/repo) from a remote URL
 pub fn parse_repo_info(url: &str) -> Result<PlatformConfig> {
     // Normalize: strip trailing slashes
     let url = url.trim_end_matches('/');
 
     let platform = detect_platform(url).ok_or(Error::NoSupportedRemotes)?;
+
+    // Bitbucket remotes carry `/scm/` prefixes and SSH ports that the
+    // generic owner/repo split would mangle
+    if platform == Platform::Bitbucket {
+        let (host, owner, repo) = parse_bitbucket_remote(url)?;
+        return Ok(PlatformConfig {
+            platform,
+            owner,
+            repo,
+            host: Some(host),
+        });
+    }
+
+    // Azure DevOps paths contain `_git` / `v3` markers and an extra project level
+    if platform == Platform::AzureDevOps {
+        let (owner, repo) = parse_azure_devops_remote(url)?;
+        return Ok(PlatformConfig {
+            platform,
+            owner,
+            repo,
+            host: None,
+        });
+    }
+
+    if platform == Platform::Local {
+        return Ok(local_platform_config(url));
+    }
+
+    // Gerrit projects may be a single path segment and live on a separate REST host
+    if platform == Platform::Gerrit {
+        let (host, owner, repo) = parse_gerrit_remote(url)?;
+        return Ok(PlatformConfig {
+            platform,
+            owner,
+            repo,
+            host: Some(host),
+        });
+    }
+
     let hostname = extract_hostname(url);
 
     let path = RE_SSH
@@ -80,6 +372,17 @@ pub fn parse_repo_info(url: &str) -> Result<PlatformConfig> {
                 None
             }
         }
+        Platform::Gitea => {
+            if hostname.as_ref().is_some_and(|h| h != "gitea.com") {
+                hostname
+            } else {
+                None
+            }
+        }
+        // Parsed above
+        Platform::Bitbucket | Platform::AzureDevOps | Platform::Gerrit | Platform::Local => {
+            hostname
+        }
     };
 
     Ok(PlatformConfig {
@@ -91,12 +394,10 @@ pub fn parse_repo_info(url: &str) -> Result<PlatformConfig> {
 }
 
 fn extract_hostname(url: &str) -> Option<String> {
-    // SSH format
-    if url.starts_with("git@") {
-        return url
-            .strip_prefix("git@")
-            .and_then(|s| s.split(':').next())
-            .map(ToString::to_string);
+    // SSH (scp-like) format: [user@]host:path
+    if !url.contains("://") {
+        let (authority, _) = url.split_once(':')?;
+        return authority.rsplit('@').next().map(ToString::to_string);
     }
 
     // HTTPS format
@@ -133,6 +434,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_codeberg() {
+        assert_eq!(
+            detect_platform("https://codeberg.org/owner/repo.git"),
+            Some(Platform::Gitea)
+        );
+    }
+
     #[test]
     fn test_parse_github_repo() {
         let config = parse_repo_info("https://github.com/owner/repo.git").unwrap();
@@ -149,4 +458,125 @@ mod tests {
         assert_eq!(config.owner, "group/subgroup");
         assert_eq!(config.repo, "repo");
     }
+
+    #[test]
+    fn test_parse_codeberg_repo() {
+        let config = parse_repo_info("git@codeberg.org:owner/repo.git").unwrap();
+        assert_eq!(config.platform, Platform::Gitea);
+        assert_eq!(config.owner, "owner");
+        assert_eq!(config.repo, "repo");
+        assert_eq!(config.host.as_deref(), Some("codeberg.org"));
+    }
+
+    #[test]
+    fn test_parse_bitbucket_https_scm() {
+        let config = parse_repo_info("https://bitbucket.example.com/scm/proj/repo.git").unwrap();
+        assert_eq!(config.platform, Platform::Bitbucket);
+        assert_eq!(config.owner, "proj");
+        assert_eq!(config.repo, "repo");
+        assert_eq!(config.host.as_deref(), Some("bitbucket.example.com"));
+    }
+
+    #[test]
+    fn test_parse_bitbucket_https_context_path() {
+        let config =
+            parse_repo_info("https://git.example.com:8443/bitbucket/scm/~jdoe/repo.git").unwrap();
+        assert_eq!(config.platform, Platform::Bitbucket);
+        assert_eq!(config.owner, "~jdoe");
+        assert_eq!(config.repo, "repo");
+        assert_eq!(
+            config.host.as_deref(),
+            Some("git.example.com:8443/bitbucket")
+        );
+    }
+
+    #[test]
+    fn test_parse_bitbucket_ssh_port() {
+        let config = parse_repo_info("ssh://git@bitbucket.example.com:7999/proj/repo.git").unwrap();
+        assert_eq!(config.platform, Platform::Bitbucket);
+        assert_eq!(config.owner, "proj");
+        assert_eq!(config.repo, "repo");
+        assert_eq!(config.host.as_deref(), Some("bitbucket.example.com"));
+    }
+
+    #[test]
+    fn test_parse_azure_devops_https() {
+        let config =
+            parse_repo_info("https://contoso@dev.azure.com/contoso/Web%20Apps/_git/frontend")
+                .unwrap();
+        assert_eq!(config.platform, Platform::AzureDevOps);
+        assert_eq!(config.owner, "contoso/Web Apps");
+        assert_eq!(config.repo, "frontend");
+        assert_eq!(config.host, None);
+    }
+
+    #[test]
+    fn test_parse_azure_devops_ssh() {
+        let config = parse_repo_info("git@ssh.dev.azure.com:v3/contoso/web/frontend").unwrap();
+        assert_eq!(config.platform, Platform::AzureDevOps);
+        assert_eq!(config.owner, "contoso/web");
+        assert_eq!(config.repo, "frontend");
+    }
+
+    #[test]
+    fn test_parse_azure_devops_legacy_and_implicit_project() {
+        let config =
+            parse_repo_info("https://contoso.visualstudio.com/DefaultCollection/web/_git/frontend")
+                .unwrap();
+        assert_eq!(config.owner, "contoso/web");
+        assert_eq!(config.repo, "frontend");
+
+        // Repos named after their project may omit the project segment
+        let config = parse_repo_info("https://dev.azure.com/contoso/_git/web").unwrap();
+        assert_eq!(config.owner, "contoso/web");
+        assert_eq!(config.repo, "web");
+    }
+
+    #[test]
+    fn test_parse_gerrit_ssh_port() {
+        let config =
+            parse_repo_info("ssh://jdoe@review.example.com:29418/platform/build.git").unwrap();
+        assert_eq!(config.platform, Platform::Gerrit);
+        assert_eq!(config.owner, "platform");
+        assert_eq!(config.repo, "build");
+        assert_eq!(config.host.as_deref(), Some("review.example.com"));
+    }
+
+    #[test]
+    fn test_parse_gerrit_googlesource() {
+        let config = parse_repo_info("https://chromium.googlesource.com/a/chromium/src").unwrap();
+        assert_eq!(config.platform, Platform::Gerrit);
+        assert_eq!(config.owner, "chromium");
+        assert_eq!(config.repo, "src");
+        assert_eq!(
+            config.host.as_deref(),
+            Some("chromium-review.googlesource.com")
+        );
+
+        // Single-segment project names have no owner
+        let config = parse_repo_info("https://gerrit.googlesource.com/gerrit").unwrap();
+        assert_eq!(config.owner, "");
+        assert_eq!(config.repo, "gerrit");
+    }
+
+    #[test]
+    fn test_parse_file_remote_is_local() {
+        let config = parse_repo_info("file:///tmp/remotes/demo.git").unwrap();
+        assert_eq!(config.platform, Platform::Local);
+        assert_eq!(config.repo, "demo");
+        assert!(config.host.is_none());
+    }
+
+    #[test]
+    fn test_resolve_platform_config_override() {
+        let config =
+            resolve_platform_config("https://github.com/owner/repo.git", Some("local")).unwrap();
+        assert_eq!(config.platform, Platform::Local);
+        assert_eq!(config.repo, "repo");
+
+        let config = resolve_platform_config("https://github.com/owner/repo.git", None).unwrap();
+        assert_eq!(config.platform, Platform::GitHub);
+
+        assert!(resolve_platform_config("https://github.com/owner/repo.git", Some("gh")).is_err());
+    }
 }
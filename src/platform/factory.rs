@@ -2,14 +2,22 @@
 //!
 //! Creates platform services based on configuration.
 
-use crate::auth::{get_github_auth, get_gitlab_auth};
-use crate::error::Result;
-use crate::platform::{GitHubService, GitLabService, PlatformService};
+use crate::auth::{
+    get_azure_devops_auth, get_bitbucket_auth, get_gerrit_auth, get_gitea_auth, get_github_auth,
+    get_gitlab_auth,
+};
+use crate::error::{Error, Result};
+use crate::platform::{
+    AzureDevOpsService, BitbucketService, GerritService, GitHubService, GitLabService,
+    GiteaService, LocalService, PlatformService,
+};
 use crate::types::{Platform, PlatformConfig};
+use std::path::Path;
 
 /// Create a platform service from configuration
 ///
-/// Handles authentication and client construction for both GitHub and GitLab.
+/// Handles authentication and client construction for GitHub, GitLab, Gitea,
+/// Bitbucket Server, Azure DevOps and Gerrit.
 pub async fn create_platform_service(config: &PlatformConfig) -> Result<Box<dyn PlatformService>> {
     match config.platform {
         Platform::GitHub => {
@@ -30,5 +38,60 @@ pub async fn create_platform_service(config: &PlatformConfig) -> Result<Box<dyn
                 Some(auth.host),
             )?))
         }
+        Platform::Gitea => {
+            let auth = get_gitea_auth(config.host.as_deref()).await?;
+            Ok(Box::new(GiteaService::new(
+                auth.token.clone(),
+                config.owner.clone(),
+                config.repo.clone(),
+                Some(auth.host),
+            )?))
+        }
+        Platform::Bitbucket => {
+            let auth = get_bitbucket_auth(config.host.as_deref()).await?;
+            Ok(Box::new(BitbucketService::new(
+                auth.token.clone(),
+                config.owner.clone(),
+                config.repo.clone(),
+                auth.host,
+            )?))
+        }
+        Platform::AzureDevOps => {
+            let auth = get_azure_devops_auth().await?;
+            Ok(Box::new(AzureDevOpsService::new(
+                auth.token,
+                config.owner.clone(),
+                config.repo.clone(),
+                config.host.clone(),
+            )?))
+        }
+        Platform::Gerrit => {
+            let auth = get_gerrit_auth(config.host.as_deref()).await?;
+            Ok(Box::new(GerritService::new(
+                auth.username,
+                auth.token,
+                config.owner.clone(),
+                config.repo.clone(),
+                auth.host,
+            )?))
+        }
+        Platform::Local => Err(Error::Config(
+            "the local forge lives in a workspace; use create_workspace_platform_service"
+                .to_string(),
+        )),
+    }
+}
+
+/// Create a platform service for a workspace
+///
+/// Like [`create_platform_service`], but also opens the local forge, whose
+/// database lives under the workspace's `.jj/repo/ryu/`.
+pub async fn create_workspace_platform_service(
+    config: &PlatformConfig,
+    workspace_root: &Path,
+) -> Result<Box<dyn PlatformService>> {
+    if config.platform == Platform::Local {
+        return Ok(Box::new(LocalService::open(workspace_root, config.clone())));
     }
+    create_platform_service(config).await
 }
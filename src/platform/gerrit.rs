@@ -0,0 +1,405 @@
+//! Gerrit platform service implementation
+//!
+//! Gerrit reviews are changes, not branches: each commit carries a
+//! `Change-Id` trailer and pushing to `refs/for/<branch>` creates or updates
+//! the matching change. The "head" of a review is therefore its `Change-Id`.
+
+use crate::error::{Error, Result};
+use crate::platform::PlatformService;
+use crate::repo::gerrit_change_id;
+use crate::types::{Bookmark, ClosedPullRequest, Platform, PlatformConfig, PrComment, PullRequest};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Gerrit service using reqwest against the authenticated REST API
+pub struct GerritService {
+    client: Client,
+    username: String,
+    token: String,
+    host: String,
+    project: String,
+    config: PlatformConfig,
+    /// `Change-Id` trailers already on commits, by jj change ID
+    change_id_trailers: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct GerritChange {
+    #[serde(rename = "_number")]
+    number: u64,
+    change_id: String,
+    branch: String,
+    subject: String,
+    #[serde(default)]
+    work_in_progress: bool,
+    #[serde(default)]
+    status: String,
+    /// `yyyy-mm-dd hh:mm:ss.fffffffff`, in UTC
+    #[serde(default)]
+    updated: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GerritMessage {
+    message: String,
+}
+
+/// Default request timeout in seconds
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Prefix Gerrit puts before every JSON response to defeat XSSI
+const XSSI_PREFIX: &str = ")]}'";
+
+/// Parse a Gerrit JSON response, stripping the XSSI guard
+fn parse_json<T: DeserializeOwned>(body: &str) -> Result<T> {
+    let json = body.trim_start().strip_prefix(XSSI_PREFIX).unwrap_or(body);
+    serde_json::from_str(json).map_err(Into::into)
+}
+
+/// Parse a Gerrit timestamp, which is always UTC
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+impl GerritService {
+    /// Create a new Gerrit service
+    ///
+    /// `owner`/`repo` are joined back into the Gerrit project name; `host` is
+    /// the REST host (which may differ from the git host, e.g. on googlesource).
+    pub fn new(
+        username: String,
+        token: String,
+        owner: String,
+        repo: String,
+        host: String,
+    ) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| Error::GerritApi(format!("failed to create HTTP client: {e}")))?;
+
+        let project = if owner.is_empty() {
+            repo.clone()
+        } else {
+            format!("{owner}/{repo}")
+        };
+
+        Ok(Self {
+            client,
+            username,
+            token,
+            host: host.clone(),
+            project,
+            config: PlatformConfig {
+                platform: Platform::Gerrit,
+                owner,
+                repo,
+                host: Some(host),
+            },
+            change_id_trailers: HashMap::new(),
+        })
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("https://{}/a{}", self.host, path)
+    }
+
+    fn authed(&self, request: RequestBuilder) -> RequestBuilder {
+        request.basic_auth(&self.username, Some(&self.token))
+    }
+
+    fn change_url(&self, number: u64) -> String {
+        format!("https://{}/c/{}/+/{number}", self.host, self.project)
+    }
+
+    fn to_pull_request(&self, change: GerritChange) -> PullRequest {
+        PullRequest {
+            number: change.number,
+            html_url: self.change_url(change.number),
+            base_ref: change.branch,
+            head_ref: change.change_id,
+            title: change.subject,
+            // The commit message doubles as the description
+            body: String::new(),
+            node_id: None, // Gerrit doesn't use GraphQL node IDs
+            is_draft: change.work_in_progress,
+        }
+    }
+
+    async fn read_json<T: DeserializeOwned>(response: Response) -> Result<T> {
+        let body = response
+            .error_for_status()
+            .map_err(|e| Error::GerritApi(e.to_string()))?
+            .text()
+            .await?;
+        parse_json(&body)
+    }
+
+    async fn get_change(&self, number: u64) -> Result<PullRequest> {
+        let url = self.api_url(&format!("/changes/{number}"));
+        let response = self.authed(self.client.get(&url)).send().await?;
+        let change: GerritChange = Self::read_json(response).await?;
+        Ok(self.to_pull_request(change))
+    }
+
+    /// POST a change action (`wip`, `ready`, `move`, `abandon`) and return the refreshed change
+    async fn change_action(
+        &self,
+        number: u64,
+        action: &str,
+        payload: serde_json::Value,
+    ) -> Result<PullRequest> {
+        let url = self.api_url(&format!("/changes/{number}/{action}"));
+
+        self.authed(self.client.post(&url))
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GerritApi(e.to_string()))?;
+
+        self.get_change(number).await
+    }
+}
+
+#[async_trait]
+impl PlatformService for GerritService {
+    async fn find_existing_pr(&self, head_branch: &str) -> Result<Option<PullRequest>> {
+        debug!(change_id = head_branch, "finding existing change");
+        let url = self.api_url("/changes/");
+        let query = format!(
+            "change:{head_branch} project:\"{}\" status:open",
+            self.project
+        );
+
+        let response = self
+            .authed(self.client.get(&url))
+            .query(&[("q", query.as_str())])
+            .send()
+            .await?;
+        let changes: Vec<GerritChange> = Self::read_json(response).await?;
+
+        let result = changes
+            .into_iter()
+            .next()
+            .map(|change| self.to_pull_request(change));
+        if let Some(ref pr) = result {
+            debug!(pr_number = pr.number, "found existing change");
+        } else {
+            debug!("no existing change found");
+        }
+        Ok(result)
+    }
+
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        debug!(change_id = head_branch, "finding closed change");
+        let url = self.api_url("/changes/");
+        let query = format!(
+            "change:{head_branch} project:\"{}\" (status:merged OR status:abandoned)",
+            self.project
+        );
+
+        let response = self
+            .authed(self.client.get(&url))
+            .query(&[("q", query.as_str())])
+            .send()
+            .await?;
+        let changes: Vec<GerritChange> = Self::read_json(response).await?;
+
+        // Gerrit lists the most recently updated change first
+        Ok(changes.into_iter().next().map(|change| {
+            let merged = change.status == "MERGED";
+            let closed_at = change.updated.as_deref().and_then(parse_timestamp);
+            ClosedPullRequest {
+                pr: self.to_pull_request(change),
+                merged,
+                closed_at,
+            }
+        }))
+    }
+
+    async fn create_pr_with_options(
+        &self,
+        head: &str,
+        base: &str,
+        _title: &str,
+        _body: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        // The push to refs/for/<base> already created the change (and its
+        // subject comes from the commit message), so just look it up
+        debug!(change_id = head, base, draft, "resolving pushed change");
+        let pr = self.find_existing_pr(head).await?.ok_or_else(|| {
+            Error::GerritApi(format!(
+                "no open change with Change-Id {head} after push to {base}"
+            ))
+        })?;
+
+        if draft && !pr.is_draft {
+            return self
+                .change_action(pr.number, "wip", serde_json::json!({}))
+                .await;
+        }
+        debug!(pr_number = pr.number, "resolved pushed change");
+        Ok(pr)
+    }
+
+    async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<PullRequest> {
+        debug!(pr_number, new_base, "moving change");
+        let pr = self
+            .change_action(
+                pr_number,
+                "move",
+                serde_json::json!({ "destination_branch": new_base }),
+            )
+            .await?;
+        debug!(pr_number, "moved change");
+        Ok(pr)
+    }
+
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        _title: &str,
+        _body: &str,
+    ) -> Result<PullRequest> {
+        // Subject and description come from the commit message, which
+        // changes with each pushed patch set
+        Err(Error::GerritApi(format!(
+            "change {pr_number} takes its subject and description from the commit message"
+        )))
+    }
+
+    async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
+        debug!(pr_number, "marking change ready for review");
+        let pr = self
+            .change_action(pr_number, "ready", serde_json::json!({}))
+            .await?;
+        debug!(pr_number, "marked change ready for review");
+        Ok(pr)
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "abandoning change");
+        self.change_action(pr_number, "abandon", serde_json::json!({}))
+            .await?;
+        debug!(pr_number, "abandoned change");
+        Ok(())
+    }
+
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<PrComment>> {
+        debug!(pr_number, "listing change messages");
+        let url = self.api_url(&format!("/changes/{pr_number}/messages"));
+        let response = self.authed(self.client.get(&url)).send().await?;
+        let messages: Vec<GerritMessage> = Self::read_json(response).await?;
+
+        // Message IDs are opaque strings; expose their position instead
+        let result: Vec<PrComment> = (0u64..)
+            .zip(messages)
+            .map(|(id, m)| PrComment {
+                id,
+                body: m.message,
+            })
+            .collect();
+        debug!(pr_number, count = result.len(), "listed change messages");
+        Ok(result)
+    }
+
+    async fn create_pr_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        debug!(pr_number, "posting change message");
+        let url = self.api_url(&format!("/changes/{pr_number}/revisions/current/review"));
+
+        self.authed(self.client.post(&url))
+            .json(&serde_json::json!({ "message": body }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GerritApi(e.to_string()))?;
+
+        debug!(pr_number, "posted change message");
+        Ok(())
+    }
+
+    async fn update_pr_comment(&self, pr_number: u64, comment_id: u64, _body: &str) -> Result<()> {
+        Err(Error::GerritApi(format!(
+            "change messages cannot be edited (change {pr_number}, message {comment_id})"
+        )))
+    }
+
+    fn config(&self) -> &PlatformConfig {
+        &self.config
+    }
+
+    fn supports_stacked_bases(&self) -> bool {
+        false
+    }
+
+    fn review_push_ref(&self, base: &str) -> Option<String> {
+        Some(format!("refs/for/{base}"))
+    }
+
+    fn review_head(&self, bookmark: &Bookmark) -> String {
+        self.change_id_trailers
+            .get(&bookmark.change_id)
+            .cloned()
+            .unwrap_or_else(|| gerrit_change_id(&bookmark.change_id))
+    }
+
+    fn set_change_id_trailers(&mut self, trailers: HashMap<String, String>) {
+        self.change_id_trailers = trailers;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_strips_xssi_prefix() {
+        let body = ")]}'\n[{\"_number\": 7, \"change_id\": \"Iabc\", \"branch\": \"main\", \"subject\": \"feat\"}]";
+        let changes: Vec<GerritChange> = parse_json(body).unwrap();
+        assert_eq!(changes[0].number, 7);
+        assert!(!changes[0].work_in_progress);
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let parsed = parse_timestamp("2024-03-01 12:34:56.789000000").unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2024-03-01T12:34:56.789+00:00");
+        assert!(parse_timestamp("yesterday").is_none());
+    }
+
+    #[test]
+    fn test_review_head_prefers_existing_trailer() {
+        let mut service = GerritService::new(
+            "user".to_string(),
+            "token".to_string(),
+            String::new(),
+            "project".to_string(),
+            "review.example.com".to_string(),
+        )
+        .unwrap();
+        let bookmark = |change_id: &str| Bookmark {
+            name: "feat".to_string(),
+            commit_id: String::new(),
+            change_id: change_id.to_string(),
+            has_remote: false,
+            is_synced: false,
+        };
+        let hook_id = "I0123456789abcdef0123456789abcdef01234567";
+        service.set_change_id_trailers([("aaaa".to_string(), hook_id.to_string())].into());
+
+        assert_eq!(service.review_head(&bookmark("aaaa")), hook_id);
+        assert_eq!(
+            service.review_head(&bookmark("bbbb")),
+            gerrit_change_id("bbbb")
+        );
+    }
+}
@@ -0,0 +1,688 @@
+//! Gitea/Forgejo platform service implementation
+
+use crate::error::{Error, Result};
+use crate::platform::PlatformService;
+use crate::types::{
+    CheckStatus, ClosedPullRequest, MergeMethod, Mergeability, Platform, PlatformConfig, PrComment,
+    PrTriage, PullRequest,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+
+/// Gitea service using reqwest against the v1 REST API
+pub struct GiteaService {
+    client: Client,
+    token: String,
+    host: String,
+    config: PlatformConfig,
+}
+
+#[derive(Deserialize)]
+struct GiteaPullRequest {
+    number: u64,
+    html_url: String,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    base: GiteaBranchRef,
+    head: GiteaBranchRef,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    merged_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    closed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    requested_reviewers: Option<Vec<GiteaUser>>,
+    #[serde(default)]
+    assignees: Option<Vec<GiteaUser>>,
+    #[serde(default)]
+    labels: Vec<GiteaLabel>,
+    #[serde(default)]
+    mergeable: bool,
+}
+
+#[derive(Deserialize)]
+struct GiteaUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaBranchRef {
+    #[serde(rename = "ref")]
+    ref_field: String,
+    #[serde(default)]
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaReview {
+    user: Option<GiteaUser>,
+    state: String,
+    #[serde(default)]
+    dismissed: bool,
+}
+
+#[derive(Deserialize)]
+struct GiteaCombinedStatus {
+    /// Empty when the commit has no statuses
+    #[serde(default)]
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct GiteaComment {
+    id: u64,
+    body: String,
+}
+
+impl From<GiteaPullRequest> for PullRequest {
+    fn from(pr: GiteaPullRequest) -> Self {
+        // Older Gitea releases have no `draft` field; drafts are expressed
+        // purely through a WIP title prefix there. The prefix is reported as
+        // `is_draft` rather than as part of the title.
+        let is_draft = pr.draft || wip_prefix_len(&pr.title).is_some();
+        Self {
+            number: pr.number,
+            html_url: pr.html_url,
+            base_ref: pr.base.ref_field,
+            head_ref: pr.head.ref_field,
+            title: strip_wip_prefix(&pr.title).to_string(),
+            body: pr.body.unwrap_or_default(),
+            node_id: None, // Gitea doesn't use GraphQL node IDs
+            is_draft,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CreatePullPayload {
+    head: String,
+    base: String,
+    title: String,
+    body: String,
+}
+
+/// Title prefix used to mark a PR as work-in-progress (Gitea's draft mechanism)
+const WIP_PREFIX: &str = "WIP: ";
+
+/// WIP prefixes recognized by Gitea's default `WORK_IN_PROGRESS_PREFIXES` setting
+const WIP_PREFIXES: &[&str] = &["wip:", "[wip]"];
+
+/// Default request timeout in seconds
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Page size for list endpoints (Gitea's default maximum)
+const PAGE_LIMIT: usize = 50;
+
+/// Pages of recently updated closed PRs searched for a finished PR
+const CLOSED_PR_PAGES: usize = 4;
+
+/// Return the byte length of a WIP prefix at the start of `title`, if any
+fn wip_prefix_len(title: &str) -> Option<usize> {
+    let lower = title.to_lowercase();
+    WIP_PREFIXES
+        .iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map(|prefix| prefix.len())
+}
+
+/// Strip a WIP prefix (and following whitespace) from a title
+fn strip_wip_prefix(title: &str) -> &str {
+    wip_prefix_len(title).map_or(title, |len| title[len..].trim_start())
+}
+
+/// Whether someone approved and nobody's latest review requests changes
+fn reviews_approve(reviews: &[GiteaReview]) -> bool {
+    let mut latest: HashMap<&str, &str> = HashMap::new();
+    for review in reviews.iter().filter(|r| !r.dismissed) {
+        if let Some(user) = &review.user
+            && matches!(review.state.as_str(), "APPROVED" | "REQUEST_CHANGES")
+        {
+            latest.insert(&user.login, &review.state);
+        }
+    }
+    latest.values().any(|s| *s == "APPROVED") && latest.values().all(|s| *s == "APPROVED")
+}
+
+impl GiteaService {
+    /// Create a new Gitea service
+    pub fn new(token: String, owner: String, repo: String, host: Option<String>) -> Result<Self> {
+        let host = host.unwrap_or_else(|| "gitea.com".to_string());
+
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| Error::GiteaApi(format!("failed to create HTTP client: {e}")))?;
+
+        let config_host = if host == "gitea.com" {
+            None
+        } else {
+            Some(host.clone())
+        };
+
+        Ok(Self {
+            client,
+            token,
+            host,
+            config: PlatformConfig {
+                platform: Platform::Gitea,
+                owner,
+                repo,
+                host: config_host,
+            },
+        })
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!(
+            "https://{}/api/v1/repos/{}/{}{}",
+            self.host,
+            urlencoding::encode(&self.config.owner),
+            urlencoding::encode(&self.config.repo),
+            path
+        )
+    }
+
+    fn auth_header(&self) -> String {
+        format!("token {}", self.token)
+    }
+
+    async fn get_pr(&self, pr_number: u64) -> Result<GiteaPullRequest> {
+        let url = self.api_url(&format!("/pulls/{pr_number}"));
+
+        self.client
+            .get(&url)
+            .header("Authorization", self.auth_header())
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// GET a repository endpoint
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.client
+            .get(self.api_url(path))
+            .header("Authorization", self.auth_header())
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// POST a JSON payload to a repository endpoint
+    async fn post(&self, path: &str, payload: &serde_json::Value) -> Result<()> {
+        self.client
+            .post(self.api_url(path))
+            .header("Authorization", self.auth_header())
+            .json(payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn edit_pr(&self, pr_number: u64, payload: serde_json::Value) -> Result<PullRequest> {
+        let url = self.api_url(&format!("/pulls/{pr_number}"));
+
+        let pr: GiteaPullRequest = self
+            .client
+            .patch(&url)
+            .header("Authorization", self.auth_header())
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?
+            .json()
+            .await?;
+
+        Ok(pr.into())
+    }
+}
+
+#[async_trait]
+impl PlatformService for GiteaService {
+    async fn find_existing_pr(&self, head_branch: &str) -> Result<Option<PullRequest>> {
+        debug!(head_branch, "finding existing PR");
+        let result = self
+            .find_existing_prs(&[head_branch])
+            .await?
+            .remove(head_branch);
+        if let Some(ref pr) = result {
+            debug!(pr_number = pr.number, "found existing PR");
+        } else {
+            debug!("no existing PR found");
+        }
+        Ok(result)
+    }
+
+    async fn find_existing_prs(
+        &self,
+        head_branches: &[&str],
+    ) -> Result<HashMap<String, PullRequest>> {
+        debug!(count = head_branches.len(), "finding existing PRs");
+        let url = self.api_url("/pulls");
+        let wanted: HashSet<&str> = head_branches.iter().copied().collect();
+        let mut result = HashMap::new();
+
+        // The list endpoint has no head-branch filter, and looking a PR up by
+        // `{base}/{head}` needs its base, so page through the open PRs once
+        // for every branch, stopping when they're all found
+        let mut page = 1usize;
+        while result.len() < wanted.len() {
+            let prs: Vec<GiteaPullRequest> = self
+                .client
+                .get(&url)
+                .header("Authorization", self.auth_header())
+                .query(&[
+                    ("state", "open".to_string()),
+                    ("limit", PAGE_LIMIT.to_string()),
+                    ("page", page.to_string()),
+                ])
+                .send()
+                .await?
+                .error_for_status()
+                .map_err(|e| Error::GiteaApi(e.to_string()))?
+                .json()
+                .await?;
+
+            let page_len = prs.len();
+            for pr in prs {
+                if wanted.contains(pr.head.ref_field.as_str())
+                    && !result.contains_key(&pr.head.ref_field)
+                {
+                    result.insert(pr.head.ref_field.clone(), pr.into());
+                }
+            }
+
+            if page_len < PAGE_LIMIT {
+                break;
+            }
+            page += 1;
+        }
+
+        debug!(found = result.len(), "found existing PRs");
+        Ok(result)
+    }
+
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        debug!(head_branch, "finding closed PR");
+        let url = self.api_url("/pulls");
+
+        // No head-branch filter here either; recently updated PRs come first,
+        // so a PR that just landed is on the first pages
+        for page in 1..=CLOSED_PR_PAGES {
+            let prs: Vec<GiteaPullRequest> = self
+                .client
+                .get(&url)
+                .header("Authorization", self.auth_header())
+                .query(&[
+                    ("state", "closed".to_string()),
+                    ("sort", "recentupdate".to_string()),
+                    ("limit", PAGE_LIMIT.to_string()),
+                    ("page", page.to_string()),
+                ])
+                .send()
+                .await?
+                .error_for_status()
+                .map_err(|e| Error::GiteaApi(e.to_string()))?
+                .json()
+                .await?;
+
+            let page_len = prs.len();
+            if let Some(pr) = prs.into_iter().find(|pr| pr.head.ref_field == head_branch) {
+                let merged = pr.merged;
+                let closed_at = pr.merged_at.or(pr.closed_at);
+                return Ok(Some(ClosedPullRequest {
+                    pr: pr.into(),
+                    merged,
+                    closed_at,
+                }));
+            }
+
+            if page_len < PAGE_LIMIT {
+                break;
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn create_pr_with_options(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        debug!(head, base, draft, "creating PR");
+        let url = self.api_url("/pulls");
+
+        let title = if draft && wip_prefix_len(title).is_none() {
+            format!("{WIP_PREFIX}{title}")
+        } else {
+            title.to_string()
+        };
+
+        let payload = CreatePullPayload {
+            head: head.to_string(),
+            base: base.to_string(),
+            title,
+            body: body.to_string(),
+        };
+
+        let pr: GiteaPullRequest = self
+            .client
+            .post(&url)
+            .header("Authorization", self.auth_header())
+            .json(&payload)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?
+            .json()
+            .await?;
+
+        let pr: PullRequest = pr.into();
+        debug!(pr_number = pr.number, "created PR");
+        Ok(pr)
+    }
+
+    async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<PullRequest> {
+        debug!(pr_number, new_base, "updating PR base");
+        let pr = self
+            .edit_pr(pr_number, serde_json::json!({ "base": new_base }))
+            .await?;
+        debug!(pr_number, "updated PR base");
+        Ok(pr)
+    }
+
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        debug!(pr_number, "updating PR title and body");
+        // Keep a draft's WIP prefix, or the edit would publish it
+        let current = self.get_pr(pr_number).await?;
+        let title = if wip_prefix_len(&current.title).is_some() {
+            format!("{WIP_PREFIX}{title}")
+        } else {
+            title.to_string()
+        };
+
+        let pr = self
+            .edit_pr(
+                pr_number,
+                serde_json::json!({ "title": title, "body": body }),
+            )
+            .await?;
+        debug!(pr_number, "updated PR title and body");
+        Ok(pr)
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "closing PR");
+        self.edit_pr(pr_number, serde_json::json!({ "state": "closed" }))
+            .await?;
+        debug!(pr_number, "closed PR");
+        Ok(())
+    }
+
+    async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
+        debug!(pr_number, "publishing PR");
+        // Gitea marks drafts with a WIP title prefix - removing it publishes the PR
+        let current = self.get_pr(pr_number).await?;
+        let title = strip_wip_prefix(&current.title).to_string();
+
+        let pr = self
+            .edit_pr(pr_number, serde_json::json!({ "title": title }))
+            .await?;
+        debug!(pr_number, "published PR");
+        Ok(pr)
+    }
+
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<PrComment>> {
+        debug!(pr_number, "listing PR comments");
+        // PR conversation comments are issue comments in Gitea
+        let url = self.api_url(&format!("/issues/{pr_number}/comments"));
+
+        // Page through like the other list calls; servers that ignore
+        // `page` return every comment each time, so stop at a repeated page
+        let mut comments: Vec<GiteaComment> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut page = 1usize;
+        loop {
+            let batch: Vec<GiteaComment> = self
+                .client
+                .get(&url)
+                .header("Authorization", self.auth_header())
+                .query(&[
+                    ("limit", PAGE_LIMIT.to_string()),
+                    ("page", page.to_string()),
+                ])
+                .send()
+                .await?
+                .error_for_status()
+                .map_err(|e| Error::GiteaApi(e.to_string()))?
+                .json()
+                .await?;
+
+            let page_len = batch.len();
+            let before = comments.len();
+            comments.extend(batch.into_iter().filter(|c| seen.insert(c.id)));
+
+            if page_len < PAGE_LIMIT || comments.len() == before {
+                break;
+            }
+            page += 1;
+        }
+
+        let result: Vec<PrComment> = comments
+            .into_iter()
+            .map(|c| PrComment {
+                id: c.id,
+                body: c.body,
+            })
+            .collect();
+        debug!(pr_number, count = result.len(), "listed PR comments");
+        Ok(result)
+    }
+
+    async fn create_pr_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        debug!(pr_number, "creating PR comment");
+        let url = self.api_url(&format!("/issues/{pr_number}/comments"));
+
+        self.client
+            .post(&url)
+            .header("Authorization", self.auth_header())
+            .json(&serde_json::json!({ "body": body }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?;
+
+        debug!(pr_number, "created PR comment");
+        Ok(())
+    }
+
+    async fn update_pr_comment(&self, _pr_number: u64, comment_id: u64, body: &str) -> Result<()> {
+        debug!(comment_id, "updating PR comment");
+        let url = self.api_url(&format!("/issues/comments/{comment_id}"));
+
+        self.client
+            .patch(&url)
+            .header("Authorization", self.auth_header())
+            .json(&serde_json::json!({ "body": body }))
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?;
+
+        debug!(comment_id, "updated PR comment");
+        Ok(())
+    }
+
+    async fn get_pr_triage(&self, pr_number: u64) -> Result<PrTriage> {
+        let pr = self.get_pr(pr_number).await?;
+        let logins = |users: Option<Vec<GiteaUser>>| {
+            users
+                .unwrap_or_default()
+                .into_iter()
+                .map(|u| u.login)
+                .collect()
+        };
+        Ok(PrTriage {
+            reviewers: logins(pr.requested_reviewers),
+            assignees: logins(pr.assignees),
+            labels: pr.labels.into_iter().map(|l| l.name).collect(),
+        })
+    }
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()> {
+        debug!(pr_number, ?reviewers, "requesting reviewers");
+        // Teams are written `org/team`; the API wants the bare team name
+        let (teams, users): (Vec<&String>, Vec<&String>) =
+            reviewers.iter().partition(|r| r.contains('/'));
+        let teams: Vec<&str> = teams
+            .into_iter()
+            .filter_map(|t| t.split_once('/').map(|(_, team)| team))
+            .collect();
+        self.post(
+            &format!("/pulls/{pr_number}/requested_reviewers"),
+            &serde_json::json!({ "reviewers": users, "team_reviewers": teams }),
+        )
+        .await
+    }
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()> {
+        debug!(pr_number, ?assignees, "adding assignees");
+        // Editing assignees replaces the list, so send the current ones too
+        let current = self.get_pr_triage(pr_number).await?;
+        let mut all = current.assignees;
+        all.extend(assignees.iter().cloned());
+        self.edit_pr(pr_number, serde_json::json!({ "assignees": all }))
+            .await?;
+        Ok(())
+    }
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()> {
+        debug!(pr_number, ?labels, "adding labels");
+        self.post(
+            &format!("/issues/{pr_number}/labels"),
+            &serde_json::json!({ "labels": labels }),
+        )
+        .await
+    }
+
+    async fn get_mergeability(&self, pr_number: u64) -> Result<Mergeability> {
+        let pr = self.get_pr(pr_number).await?;
+        let reviews: Vec<GiteaReview> = self.get(&format!("/pulls/{pr_number}/reviews")).await?;
+        let status: GiteaCombinedStatus = self
+            .get(&format!("/commits/{}/status", pr.head.sha))
+            .await?;
+
+        Ok(Mergeability {
+            approved: reviews_approve(&reviews),
+            checks: match status.state.as_str() {
+                "success" => CheckStatus::Success,
+                "pending" => CheckStatus::Pending,
+                "" => CheckStatus::None,
+                _ => CheckStatus::Failure,
+            },
+            // Gitea reports a PR whose conflict check hasn't run as unmergeable
+            mergeable: Some(pr.mergeable),
+        })
+    }
+
+    async fn merge_pr(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        debug!(pr_number, %method, "merging PR");
+        self.post(
+            &format!("/pulls/{pr_number}/merge"),
+            &serde_json::json!({ "Do": method }),
+        )
+        .await
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        let user: GiteaUser = self
+            .client
+            .get(format!("https://{}/api/v1/user", self.host))
+            .header("Authorization", self.auth_header())
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| Error::GiteaApi(e.to_string()))?
+            .json()
+            .await?;
+        Ok(user.login)
+    }
+
+    fn config(&self) -> &PlatformConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wip_prefix_detection() {
+        assert!(wip_prefix_len("WIP: add feature").is_some());
+        assert!(wip_prefix_len("[WIP] add feature").is_some());
+        assert!(wip_prefix_len("wip: add feature").is_some());
+        assert!(wip_prefix_len("add wip feature").is_none());
+    }
+
+    #[test]
+    fn test_strip_wip_prefix() {
+        assert_eq!(strip_wip_prefix("WIP: add feature"), "add feature");
+        assert_eq!(strip_wip_prefix("[WIP] add feature"), "add feature");
+        assert_eq!(strip_wip_prefix("add feature"), "add feature");
+    }
+
+    #[test]
+    fn test_reviews_approve_uses_latest_review_per_user() {
+        let review = |login: &str, state: &str| GiteaReview {
+            user: Some(GiteaUser {
+                login: login.to_string(),
+            }),
+            state: state.to_string(),
+            dismissed: false,
+        };
+        assert!(!reviews_approve(&[]));
+        assert!(reviews_approve(&[
+            review("alice", "REQUEST_CHANGES"),
+            review("alice", "COMMENT"),
+            review("alice", "APPROVED"),
+        ]));
+        assert!(!reviews_approve(&[
+            review("alice", "APPROVED"),
+            review("bob", "REQUEST_CHANGES"),
+        ]));
+    }
+}
@@ -2,10 +2,21 @@
 
 use crate::error::{Error, Result};
 use crate::platform::PlatformService;
-use crate::types::{Platform, PlatformConfig, PrComment, PullRequest};
+use crate::platform::retry::{Retrier, RetryClass, RetryPolicy, classify_response};
+use crate::submit::ProgressCallback;
+use crate::types::{
+    CheckStatus, ClosedPullRequest, MergeMethod, MergeQueueEntry, MergeQueueState, Mergeability,
+    Platform, PlatformConfig, PrComment, PrState, PrStatus, PrTriage, PullRequest, ReviewDecision,
+};
 use async_trait::async_trait;
 use octocrab::Octocrab;
+use reqwest::StatusCode;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::debug;
 
 // GraphQL response types for publish_pr mutation
@@ -41,10 +52,350 @@ struct GraphQlPullRequest {
     base_ref_name: String,
     head_ref_name: String,
     title: String,
+    #[serde(default)]
+    body: String,
     id: String,
     is_draft: bool,
 }
 
+// GraphQL response types for the batched PR lookup
+
+#[derive(Deserialize)]
+struct BatchLookupData {
+    /// One connection per aliased head branch (`h0`, `h1`, ...)
+    repository: HashMap<String, PullRequestConnection>,
+}
+
+#[derive(Deserialize)]
+struct PullRequestConnection {
+    nodes: Vec<LookupPullRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LookupPullRequest {
+    #[serde(flatten)]
+    pr: GraphQlPullRequest,
+    head_repository_owner: Option<RepositoryOwner>,
+}
+
+#[derive(Deserialize)]
+struct RepositoryOwner {
+    login: String,
+}
+
+// REST response types for reviewers, assignees and labels
+
+#[derive(Deserialize)]
+struct TriageFields {
+    #[serde(default)]
+    requested_reviewers: Vec<User>,
+    #[serde(default)]
+    requested_teams: Vec<Team>,
+    #[serde(default)]
+    assignees: Vec<User>,
+    #[serde(default)]
+    labels: Vec<Label>,
+}
+
+#[derive(Deserialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct Team {
+    slug: String,
+}
+
+#[derive(Deserialize)]
+struct Label {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Review {
+    user: Option<User>,
+}
+
+/// Reviews fetched per page, the most the REST API allows
+const REVIEWS_PER_PAGE: usize = 100;
+
+// GraphQL response types for the mergeability query
+
+const MERGEABILITY_QUERY: &str = r"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewDecision
+      mergeable
+      commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+    }
+  }
+}";
+
+#[derive(Deserialize)]
+struct MergeabilityData {
+    repository: MergeabilityRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeabilityRepository {
+    pull_request: MergeabilityPullRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeabilityPullRequest {
+    /// `None` when the branch doesn't require reviews
+    review_decision: Option<String>,
+    mergeable: String,
+    commits: MergeabilityCommits,
+}
+
+#[derive(Deserialize)]
+struct MergeabilityCommits {
+    nodes: Vec<MergeabilityCommitNode>,
+}
+
+#[derive(Deserialize)]
+struct MergeabilityCommitNode {
+    commit: MergeabilityCommit,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeabilityCommit {
+    status_check_rollup: Option<StatusCheckRollup>,
+}
+
+#[derive(Deserialize)]
+struct StatusCheckRollup {
+    state: String,
+}
+
+/// Combined status of the head commit's checks
+fn rollup_status(commits: MergeabilityCommits) -> CheckStatus {
+    let rollup = commits
+        .nodes
+        .into_iter()
+        .next()
+        .and_then(|n| n.commit.status_check_rollup);
+    match rollup.as_ref().map(|r| r.state.as_str()) {
+        Some("SUCCESS") => CheckStatus::Success,
+        Some("PENDING" | "EXPECTED") => CheckStatus::Pending,
+        Some(_) => CheckStatus::Failure,
+        None => CheckStatus::None,
+    }
+}
+
+impl From<MergeabilityPullRequest> for Mergeability {
+    fn from(pr: MergeabilityPullRequest) -> Self {
+        let checks = rollup_status(pr.commits);
+        let mergeable = match pr.mergeable.as_str() {
+            "MERGEABLE" => Some(true),
+            "CONFLICTING" => Some(false),
+            _ => None,
+        };
+        Self {
+            approved: pr.review_decision.is_none_or(|d| d == "APPROVED"),
+            checks,
+            mergeable,
+        }
+    }
+}
+
+// GraphQL response types for the PR status query
+
+const PR_STATUS_QUERY: &str = r"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      number url baseRefName headRefName title body id isDraft
+      state
+      reviewDecision
+      latestReviews(first: 100) { nodes { state } }
+      reviewThreads(first: 100) { nodes { isResolved } }
+      commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+    }
+  }
+}";
+
+#[derive(Deserialize)]
+struct PrStatusData {
+    repository: PrStatusRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrStatusRepository {
+    pull_request: PrStatusPullRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrStatusPullRequest {
+    #[serde(flatten)]
+    pr: GraphQlPullRequest,
+    /// `OPEN`, `MERGED` or `CLOSED`
+    state: String,
+    review_decision: Option<String>,
+    latest_reviews: Nodes<ReviewState>,
+    review_threads: Nodes<ReviewThread>,
+    commits: MergeabilityCommits,
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct ReviewState {
+    state: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThread {
+    is_resolved: bool,
+}
+
+impl From<PrStatusPullRequest> for PrStatus {
+    fn from(pr: PrStatusPullRequest) -> Self {
+        let state = match pr.state.as_str() {
+            "MERGED" => PrState::Merged,
+            "CLOSED" => PrState::Closed,
+            _ => PrState::Open,
+        };
+        let review_decision = match pr.review_decision.as_deref() {
+            Some("APPROVED") => ReviewDecision::Approved,
+            Some("CHANGES_REQUESTED") => ReviewDecision::ChangesRequested,
+            Some("REVIEW_REQUIRED") => ReviewDecision::ReviewRequired,
+            _ => ReviewDecision::None,
+        };
+        let approvals = pr
+            .latest_reviews
+            .nodes
+            .iter()
+            .filter(|r| r.state == "APPROVED")
+            .count();
+        let unresolved_threads = pr
+            .review_threads
+            .nodes
+            .iter()
+            .filter(|t| !t.is_resolved)
+            .count();
+        Self {
+            pr: pr.pr.into(),
+            state,
+            review_decision,
+            approvals: u32::try_from(approvals).unwrap_or(u32::MAX),
+            unresolved_threads: u32::try_from(unresolved_threads).unwrap_or(u32::MAX),
+            checks: rollup_status(pr.commits),
+        }
+    }
+}
+
+// GraphQL response types for auto-merge
+
+#[derive(Deserialize)]
+struct AutoMergeData {
+    repository: AutoMergeRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AutoMergeRepository {
+    pull_request: AutoMergePullRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AutoMergePullRequest {
+    auto_merge_request: Option<AutoMergeRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AutoMergeRequest {
+    merge_method: String,
+}
+
+// GraphQL response types for the merge queue
+
+#[derive(Deserialize)]
+struct MergeQueueData {
+    repository: MergeQueueRepository,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeQueueRepository {
+    pull_request: MergeQueuePullRequest,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeQueuePullRequest {
+    merge_queue_entry: Option<MergeQueueNode>,
+}
+
+#[derive(Deserialize)]
+struct MergeQueueNode {
+    position: Option<u64>,
+    /// `QUEUED`, `AWAITING_CHECKS`, `MERGEABLE`, `UNMERGEABLE` or `LOCKED`
+    state: String,
+}
+
+impl From<MergeQueueNode> for MergeQueueEntry {
+    fn from(node: MergeQueueNode) -> Self {
+        let state = match node.state.as_str() {
+            "AWAITING_CHECKS" => MergeQueueState::AwaitingChecks,
+            "MERGEABLE" => MergeQueueState::Mergeable,
+            "UNMERGEABLE" => MergeQueueState::Unmergeable,
+            "LOCKED" => MergeQueueState::Locked,
+            _ => MergeQueueState::Queued,
+        };
+        Self {
+            position: node.position,
+            state,
+        }
+    }
+}
+
+/// GraphQL `PullRequestMergeMethod` for a merge method
+const fn graphql_merge_method(method: MergeMethod) -> &'static str {
+    match method {
+        MergeMethod::Merge => "MERGE",
+        MergeMethod::Squash => "SQUASH",
+        MergeMethod::Rebase => "REBASE",
+    }
+}
+
+/// Maximum head branches looked up per GraphQL query
+const LOOKUP_BATCH_SIZE: usize = 50;
+
+/// Build a query with one aliased `pullRequests` connection per head branch
+fn batch_lookup_query(count: usize) -> String {
+    let mut variables = String::new();
+    let mut fields = String::new();
+    for i in 0..count {
+        let _ = write!(variables, ", $h{i}: String!");
+        let _ = writeln!(
+            fields,
+            "h{i}: pullRequests(headRefName: $h{i}, states: OPEN, first: 10) {{ nodes {{ \
+             number url baseRefName headRefName title body id isDraft \
+             headRepositoryOwner {{ login }} }} }}"
+        );
+    }
+    format!(
+        "query($owner: String!, $repo: String!{variables}) {{\n\
+         repository(owner: $owner, name: $repo) {{\n{fields}}}\n}}"
+    )
+}
+
 impl From<GraphQlPullRequest> for PullRequest {
     fn from(pr: GraphQlPullRequest) -> Self {
         Self {
@@ -53,16 +404,54 @@ impl From<GraphQlPullRequest> for PullRequest {
             base_ref: pr.base_ref_name,
             head_ref: pr.head_ref_name,
             title: pr.title,
+            body: pr.body,
             node_id: Some(pr.id),
             is_draft: pr.is_draft,
         }
     }
 }
 
+/// Wait after a secondary rate limit that doesn't say how long to wait
+///
+/// GitHub documents at least a minute.
+const SECONDARY_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// HTTP method of a GitHub API request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    Get,
+    Patch,
+    Post,
+    /// PUT that must not be repeated (merging)
+    Put,
+    /// POST that is safe to repeat (GraphQL queries, idempotent mutations)
+    IdempotentPost,
+}
+
+impl Method {
+    /// Whether repeating the request after an ambiguous failure is safe
+    const fn is_idempotent(self) -> bool {
+        !matches!(self, Self::Post | Self::Put)
+    }
+}
+
+/// Classify a request that failed before GitHub responded
+const fn classify_client_error(error: &octocrab::Error, idempotent: bool) -> RetryClass {
+    match error {
+        octocrab::Error::Hyper { .. } | octocrab::Error::Service { .. } if idempotent => {
+            RetryClass::Transient
+        }
+        _ => RetryClass::Fatal,
+    }
+}
+
 /// GitHub service using octocrab
 pub struct GitHubService {
     client: Octocrab,
     config: PlatformConfig,
+    /// Owner of the fork PR heads live in, if not this repository
+    head_owner: Option<String>,
+    retrier: Retrier,
 }
 
 impl GitHubService {
@@ -89,8 +478,118 @@ impl GitHubService {
                 repo,
                 host,
             },
+            head_owner: None,
+            retrier: Retrier::default(),
         })
     }
+
+    /// Replace the limits for retrying failed requests
+    pub const fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retrier.set_policy(policy);
+    }
+
+    /// Head ref for a branch: `owner:branch` when PRs come from a fork
+    fn head_ref(&self, branch: &str) -> String {
+        self.head_owner
+            .as_ref()
+            .map_or_else(|| branch.to_string(), |owner| format!("{owner}:{branch}"))
+    }
+
+    /// API route under this repository
+    fn repo_route(&self, path: &str) -> String {
+        format!("/repos/{}/{}{path}", self.config.owner, self.config.repo)
+    }
+
+    /// Send an API request, retrying transient failures and rate limits
+    ///
+    /// octocrab's typed builders drop the response headers, so requests go
+    /// through its raw methods to read `Retry-After` and `X-RateLimit-*`.
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: Method,
+        route: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<R> {
+        let idempotent = method.is_idempotent();
+        let mut retry = 0;
+        loop {
+            let sent = match method {
+                Method::Get => self.client._get(route).await,
+                Method::Patch => self.client._patch(route, body).await,
+                Method::Post | Method::IdempotentPost => self.client._post(route, body).await,
+                Method::Put => self.client._put(route, body).await,
+            };
+
+            let (class, error) = match sent {
+                Ok(response) if response.status().is_success() => {
+                    let text = self.client.body_to_string(response).await?;
+                    return Ok(serde_json::from_str(&text)?);
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let mut class = classify_response(status, response.headers(), idempotent);
+                    let error = match octocrab::map_github_error(response).await {
+                        Ok(_) => Error::GitHubApi(format!("unexpected status {status}")),
+                        Err(e) => {
+                            if class == RetryClass::Fatal
+                                && status == StatusCode::FORBIDDEN
+                                && e.to_string().contains("secondary rate limit")
+                            {
+                                class = RetryClass::RateLimited(Some(SECONDARY_RATE_LIMIT_WAIT));
+                            }
+                            e.into()
+                        }
+                    };
+                    (class, error)
+                }
+                Err(e) => (classify_client_error(&e, idempotent), e.into()),
+            };
+
+            if !self.retrier.wait("GitHub", class, &mut retry).await {
+                return Err(error);
+            }
+        }
+    }
+
+    /// GraphQL node ID of a PR, for mutations
+    async fn pr_node_id(&self, pr_number: u64) -> Result<String> {
+        let pr: octocrab::models::pulls::PullRequest = self
+            .request(
+                Method::Get,
+                &self.repo_route(&format!("/pulls/{pr_number}")),
+                None,
+            )
+            .await?;
+
+        pr.node_id
+            .ok_or_else(|| Error::GitHubApi("PR missing node_id for GraphQL mutation".to_string()))
+    }
+
+    /// Send a GraphQL request, failing on GraphQL-level errors
+    async fn graphql<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        payload: &serde_json::Value,
+    ) -> Result<T> {
+        let response: GraphQlResponse<T> = self
+            .request(method, "/graphql", Some(payload))
+            .await
+            .map_err(|e| Error::GitHubApi(format!("GraphQL request failed: {e}")))?;
+
+        if let Some(errors) = response.errors
+            && !errors.is_empty()
+        {
+            let messages: Vec<_> = errors.into_iter().map(|e| e.message).collect();
+            return Err(Error::GitHubApi(format!(
+                "GraphQL error: {}",
+                messages.join(", ")
+            )));
+        }
+
+        response
+            .data
+            .ok_or_else(|| Error::GitHubApi("No data in GraphQL response".to_string()))
+    }
 }
 
 /// Helper to convert octocrab PR to our `PullRequest` type
@@ -105,6 +604,7 @@ fn pr_from_octocrab(pr: &octocrab::models::pulls::PullRequest) -> PullRequest {
         base_ref: pr.base.ref_field.clone(),
         head_ref: pr.head.ref_field.clone(),
         title: pr.title.as_deref().unwrap_or_default().to_string(),
+        body: pr.body.as_deref().unwrap_or_default().to_string(),
         node_id: pr.node_id.clone(),
         is_draft: pr.draft.unwrap_or(false),
     }
@@ -114,18 +614,17 @@ fn pr_from_octocrab(pr: &octocrab::models::pulls::PullRequest) -> PullRequest {
 impl PlatformService for GitHubService {
     async fn find_existing_pr(&self, head_branch: &str) -> Result<Option<PullRequest>> {
         debug!(head_branch, "finding existing PR");
-        let head = format!("{}:{}", &self.config.owner, head_branch);
-
-        let prs = self
-            .client
-            .pulls(&self.config.owner, &self.config.repo)
-            .list()
-            .head(head)
-            .state(octocrab::params::State::Open)
-            .send()
-            .await?;
+        let owner = self.head_owner.as_ref().unwrap_or(&self.config.owner);
+        let head = format!("{owner}:{head_branch}");
+
+        let route = self.repo_route(&format!(
+            "/pulls?state=open&head={}",
+            urlencoding::encode(&head)
+        ));
+        let prs: Vec<octocrab::models::pulls::PullRequest> =
+            self.request(Method::Get, &route, None).await?;
 
-        let result = prs.items.first().map(pr_from_octocrab);
+        let result = prs.first().map(pr_from_octocrab);
         if let Some(ref pr) = result {
             debug!(pr_number = pr.number, "found existing PR");
         } else {
@@ -134,20 +633,93 @@ impl PlatformService for GitHubService {
         Ok(result)
     }
 
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        debug!(head_branch, "finding closed PR");
+        let owner = self.head_owner.as_ref().unwrap_or(&self.config.owner);
+        let head = format!("{owner}:{head_branch}");
+
+        let route = self.repo_route(&format!(
+            "/pulls?state=closed&head={}&sort=updated&direction=desc&per_page=1",
+            urlencoding::encode(&head)
+        ));
+        let prs: Vec<octocrab::models::pulls::PullRequest> =
+            self.request(Method::Get, &route, None).await?;
+
+        // Squash and rebase merges also set merged_at
+        Ok(prs.first().map(|pr| ClosedPullRequest {
+            pr: pr_from_octocrab(pr),
+            merged: pr.merged_at.is_some(),
+            closed_at: pr.merged_at.or(pr.closed_at),
+        }))
+    }
+
+    async fn find_existing_prs(
+        &self,
+        head_branches: &[&str],
+    ) -> Result<HashMap<String, PullRequest>> {
+        debug!(count = head_branches.len(), "finding existing PRs");
+        let head_owner = self.head_owner.as_ref().unwrap_or(&self.config.owner);
+        let mut result = HashMap::new();
+
+        for batch in head_branches.chunks(LOOKUP_BATCH_SIZE) {
+            let mut variables = serde_json::json!({
+                "owner": self.config.owner,
+                "repo": self.config.repo,
+            });
+            for (i, head) in batch.iter().enumerate() {
+                variables[format!("h{i}")] = serde_json::json!(head);
+            }
+
+            let mut data: BatchLookupData = self
+                .graphql(
+                    Method::IdempotentPost,
+                    &serde_json::json!({
+                        "query": batch_lookup_query(batch.len()),
+                        "variables": variables,
+                    }),
+                )
+                .await?;
+
+            for (i, head) in batch.iter().enumerate() {
+                // Same-named branches in other forks also match headRefName
+                let pr = data
+                    .repository
+                    .remove(&format!("h{i}"))
+                    .into_iter()
+                    .flat_map(|connection| connection.nodes)
+                    .find(|node| {
+                        node.head_repository_owner
+                            .as_ref()
+                            .is_some_and(|owner| owner.login.eq_ignore_ascii_case(head_owner))
+                    });
+                if let Some(node) = pr {
+                    result.insert((*head).to_string(), node.pr.into());
+                }
+            }
+        }
+
+        debug!(found = result.len(), "found existing PRs");
+        Ok(result)
+    }
+
     async fn create_pr_with_options(
         &self,
         head: &str,
         base: &str,
         title: &str,
+        body: &str,
         draft: bool,
     ) -> Result<PullRequest> {
         debug!(head, base, draft, "creating PR");
-        let pr = self
-            .client
-            .pulls(&self.config.owner, &self.config.repo)
-            .create(title, head, base)
-            .draft(draft)
-            .send()
+        let payload = serde_json::json!({
+            "title": title,
+            "body": body,
+            "head": self.head_ref(head),
+            "base": base,
+            "draft": draft,
+        });
+        let pr: octocrab::models::pulls::PullRequest = self
+            .request(Method::Post, &self.repo_route("/pulls"), Some(&payload))
             .await?;
 
         let result = pr_from_octocrab(&pr);
@@ -157,35 +729,52 @@ impl PlatformService for GitHubService {
 
     async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<PullRequest> {
         debug!(pr_number, new_base, "updating PR base");
-        let pr = self
-            .client
-            .pulls(&self.config.owner, &self.config.repo)
-            .update(pr_number)
-            .base(new_base)
-            .send()
-            .await?;
+        let route = self.repo_route(&format!("/pulls/{pr_number}"));
+        let body = serde_json::json!({ "base": new_base });
+        let pr: octocrab::models::pulls::PullRequest =
+            self.request(Method::Patch, &route, Some(&body)).await?;
 
         debug!(pr_number, "updated PR base");
         Ok(pr_from_octocrab(&pr))
     }
 
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        debug!(pr_number, "updating PR title and body");
+        let route = self.repo_route(&format!("/pulls/{pr_number}"));
+        let payload = serde_json::json!({ "title": title, "body": body });
+        let pr: octocrab::models::pulls::PullRequest =
+            self.request(Method::Patch, &route, Some(&payload)).await?;
+
+        debug!(pr_number, "updated PR title and body");
+        Ok(pr_from_octocrab(&pr))
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "closing PR");
+        let route = self.repo_route(&format!("/pulls/{pr_number}"));
+        let body = serde_json::json!({ "state": "closed" });
+        let _: octocrab::models::pulls::PullRequest =
+            self.request(Method::Patch, &route, Some(&body)).await?;
+
+        debug!(pr_number, "closed PR");
+        Ok(())
+    }
+
     async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
         debug!(pr_number, "publishing PR");
-        // Fetch PR to get node_id for GraphQL mutation
-        let pr = self
-            .client
-            .pulls(&self.config.owner, &self.config.repo)
-            .get(pr_number)
-            .await?;
-
-        let node_id = pr.node_id.as_ref().ok_or_else(|| {
-            Error::GitHubApi("PR missing node_id for GraphQL mutation".to_string())
-        })?;
+        let node_id = self.pr_node_id(pr_number).await?;
 
         // Execute GraphQL mutation to mark PR as ready for review
-        let response: GraphQlResponse<MarkReadyForReviewData> = self
-            .client
-            .graphql(&serde_json::json!({
+        // (marking a PR ready twice is harmless, so it can be retried)
+        let data: MarkReadyForReviewData = self
+            .graphql(
+                Method::IdempotentPost,
+                &serde_json::json!({
                 "query": r"
                     mutation MarkPullRequestReadyForReview($pullRequestId: ID!) {
                         markPullRequestReadyForReview(input: { pullRequestId: $pullRequestId }) {
@@ -195,6 +784,7 @@ impl PlatformService for GitHubService {
                                 baseRefName
                                 headRefName
                                 title
+                                body
                                 id
                                 isDraft
                             }
@@ -204,25 +794,9 @@ impl PlatformService for GitHubService {
                 "variables": {
                     "pullRequestId": node_id
                 }
-            }))
-            .await
-            .map_err(|e| Error::GitHubApi(format!("GraphQL mutation failed: {e}")))?;
-
-        // Check for GraphQL errors
-        if let Some(errors) = response.errors
-            && !errors.is_empty()
-        {
-            let messages: Vec<_> = errors.into_iter().map(|e| e.message).collect();
-            return Err(Error::GitHubApi(format!(
-                "GraphQL error: {}",
-                messages.join(", ")
-            )));
-        }
-
-        // Extract typed response
-        let data = response
-            .data
-            .ok_or_else(|| Error::GitHubApi("No data in GraphQL response".to_string()))?;
+                }),
+            )
+            .await?;
 
         debug!(pr_number, "published PR");
         Ok(data.mark_pull_request_ready_for_review.pull_request.into())
@@ -230,15 +804,11 @@ impl PlatformService for GitHubService {
 
     async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<PrComment>> {
         debug!(pr_number, "listing PR comments");
-        let comments = self
-            .client
-            .issues(&self.config.owner, &self.config.repo)
-            .list_comments(pr_number)
-            .send()
-            .await?;
+        let route = self.repo_route(&format!("/issues/{pr_number}/comments"));
+        let comments: Vec<octocrab::models::issues::Comment> =
+            self.request(Method::Get, &route, None).await?;
 
         let result: Vec<PrComment> = comments
-            .items
             .into_iter()
             .map(|c| PrComment {
                 id: c.id.0,
@@ -251,25 +821,407 @@ impl PlatformService for GitHubService {
 
     async fn create_pr_comment(&self, pr_number: u64, body: &str) -> Result<()> {
         debug!(pr_number, "creating PR comment");
-        self.client
-            .issues(&self.config.owner, &self.config.repo)
-            .create_comment(pr_number, body)
-            .await?;
+        let route = self.repo_route(&format!("/issues/{pr_number}/comments"));
+        let body = serde_json::json!({ "body": body });
+        let _: serde::de::IgnoredAny = self.request(Method::Post, &route, Some(&body)).await?;
         debug!(pr_number, "created PR comment");
         Ok(())
     }
 
     async fn update_pr_comment(&self, _pr_number: u64, comment_id: u64, body: &str) -> Result<()> {
         debug!(comment_id, "updating PR comment");
-        self.client
-            .issues(&self.config.owner, &self.config.repo)
-            .update_comment(octocrab::models::CommentId(comment_id), body)
-            .await?;
+        let route = self.repo_route(&format!("/issues/comments/{comment_id}"));
+        let body = serde_json::json!({ "body": body });
+        let _: serde::de::IgnoredAny = self.request(Method::Patch, &route, Some(&body)).await?;
         debug!(comment_id, "updated PR comment");
         Ok(())
     }
 
+    async fn get_pr_triage(&self, pr_number: u64) -> Result<PrTriage> {
+        let route = self.repo_route(&format!("/pulls/{pr_number}"));
+        let fields: TriageFields = self.request(Method::Get, &route, None).await?;
+        // A submitted review removes the reviewer from the requested list
+        let mut reviews: Vec<Review> = Vec::new();
+        for page in 1.. {
+            let route = self.repo_route(&format!(
+                "/pulls/{pr_number}/reviews?per_page={REVIEWS_PER_PAGE}&page={page}"
+            ));
+            let batch: Vec<Review> = self.request(Method::Get, &route, None).await?;
+            let last = batch.len() < REVIEWS_PER_PAGE;
+            reviews.extend(batch);
+            if last {
+                break;
+            }
+        }
+
+        let mut reviewers: Vec<String> = fields
+            .requested_reviewers
+            .into_iter()
+            .chain(reviews.into_iter().filter_map(|r| r.user))
+            .map(|u| u.login)
+            .collect();
+        reviewers.extend(
+            fields
+                .requested_teams
+                .into_iter()
+                .map(|t| format!("{}/{}", self.config.owner, t.slug)),
+        );
+        Ok(PrTriage {
+            reviewers,
+            assignees: fields.assignees.into_iter().map(|u| u.login).collect(),
+            labels: fields.labels.into_iter().map(|l| l.name).collect(),
+        })
+    }
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()> {
+        debug!(pr_number, ?reviewers, "requesting reviewers");
+        // Teams are written `org/team`; the API wants the bare slug
+        let (teams, users): (Vec<&String>, Vec<&String>) =
+            reviewers.iter().partition(|r| r.contains('/'));
+        let teams: Vec<&str> = teams
+            .into_iter()
+            .filter_map(|t| t.split_once('/').map(|(_, slug)| slug))
+            .collect();
+        let route = self.repo_route(&format!("/pulls/{pr_number}/requested_reviewers"));
+        let body = serde_json::json!({ "reviewers": users, "team_reviewers": teams });
+        let _: serde::de::IgnoredAny = self
+            .request(Method::IdempotentPost, &route, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()> {
+        debug!(pr_number, ?assignees, "adding assignees");
+        let route = self.repo_route(&format!("/issues/{pr_number}/assignees"));
+        let body = serde_json::json!({ "assignees": assignees });
+        let _: serde::de::IgnoredAny = self
+            .request(Method::IdempotentPost, &route, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()> {
+        debug!(pr_number, ?labels, "adding labels");
+        let route = self.repo_route(&format!("/issues/{pr_number}/labels"));
+        let body = serde_json::json!({ "labels": labels });
+        let _: serde::de::IgnoredAny = self
+            .request(Method::IdempotentPost, &route, Some(&body))
+            .await?;
+        Ok(())
+    }
+
+    async fn get_mergeability(&self, pr_number: u64) -> Result<Mergeability> {
+        let payload = serde_json::json!({
+            "query": MERGEABILITY_QUERY,
+            "variables": {
+                "owner": self.config.owner,
+                "repo": self.config.repo,
+                "number": pr_number,
+            },
+        });
+        let data: MergeabilityData = self.graphql(Method::IdempotentPost, &payload).await?;
+        Ok(data.repository.pull_request.into())
+    }
+
+    async fn get_pr_status(&self, pr_number: u64) -> Result<PrStatus> {
+        let payload = serde_json::json!({
+            "query": PR_STATUS_QUERY,
+            "variables": {
+                "owner": self.config.owner,
+                "repo": self.config.repo,
+                "number": pr_number,
+            },
+        });
+        let data: PrStatusData = self.graphql(Method::IdempotentPost, &payload).await?;
+        Ok(data.repository.pull_request.into())
+    }
+
+    async fn merge_pr(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        debug!(pr_number, %method, "merging PR");
+        let route = self.repo_route(&format!("/pulls/{pr_number}/merge"));
+        let body = serde_json::json!({ "merge_method": method });
+        let _: serde::de::IgnoredAny = self.request(Method::Put, &route, Some(&body)).await?;
+        Ok(())
+    }
+
+    async fn get_auto_merge(&self, pr_number: u64) -> Result<Option<MergeMethod>> {
+        let payload = serde_json::json!({
+            "query": r"
+                query($owner: String!, $repo: String!, $number: Int!) {
+                    repository(owner: $owner, name: $repo) {
+                        pullRequest(number: $number) { autoMergeRequest { mergeMethod } }
+                    }
+                }
+            ",
+            "variables": {
+                "owner": self.config.owner,
+                "repo": self.config.repo,
+                "number": pr_number,
+            },
+        });
+        let data: AutoMergeData = self.graphql(Method::IdempotentPost, &payload).await?;
+        data.repository
+            .pull_request
+            .auto_merge_request
+            .map(|request| request.merge_method.parse())
+            .transpose()
+    }
+
+    async fn enable_auto_merge(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        debug!(pr_number, %method, "enabling auto-merge");
+        let node_id = self.pr_node_id(pr_number).await?;
+        // Enabling auto-merge twice is harmless, so it can be retried
+        let _: serde::de::IgnoredAny = self
+            .graphql(
+                Method::IdempotentPost,
+                &serde_json::json!({
+                    "query": r"
+                        mutation($pullRequestId: ID!, $mergeMethod: PullRequestMergeMethod!) {
+                            enablePullRequestAutoMerge(
+                                input: { pullRequestId: $pullRequestId, mergeMethod: $mergeMethod }
+                            ) {
+                                clientMutationId
+                            }
+                        }
+                    ",
+                    "variables": {
+                        "pullRequestId": node_id,
+                        "mergeMethod": graphql_merge_method(method),
+                    },
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_merge_queue_entry(&self, pr_number: u64) -> Result<Option<MergeQueueEntry>> {
+        let payload = serde_json::json!({
+            "query": r"
+                query($owner: String!, $repo: String!, $number: Int!) {
+                    repository(owner: $owner, name: $repo) {
+                        pullRequest(number: $number) { mergeQueueEntry { position state } }
+                    }
+                }
+            ",
+            "variables": {
+                "owner": self.config.owner,
+                "repo": self.config.repo,
+                "number": pr_number,
+            },
+        });
+        let data: MergeQueueData = self.graphql(Method::IdempotentPost, &payload).await?;
+        Ok(data
+            .repository
+            .pull_request
+            .merge_queue_entry
+            .map(Into::into))
+    }
+
+    async fn enqueue_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "adding PR to the merge queue");
+        let node_id = self.pr_node_id(pr_number).await?;
+        // Enqueuing twice fails, so an ambiguous failure isn't retried
+        let _: serde::de::IgnoredAny = self
+            .graphql(
+                Method::Post,
+                &serde_json::json!({
+                    "query": r"
+                        mutation($pullRequestId: ID!) {
+                            enqueuePullRequest(input: { pullRequestId: $pullRequestId }) {
+                                mergeQueueEntry { position }
+                            }
+                        }
+                    ",
+                    "variables": { "pullRequestId": node_id },
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn dequeue_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "removing PR from the merge queue");
+        let node_id = self.pr_node_id(pr_number).await?;
+        let _: serde::de::IgnoredAny = self
+            .graphql(
+                Method::Post,
+                &serde_json::json!({
+                    "query": r"
+                        mutation($id: ID!) {
+                            dequeuePullRequest(input: { id: $id }) {
+                                mergeQueueEntry { position }
+                            }
+                        }
+                    ",
+                    "variables": { "id": node_id },
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        let user: User = self.request(Method::Get, "/user", None).await?;
+        Ok(user.login)
+    }
+
     fn config(&self) -> &PlatformConfig {
         &self.config
     }
+
+    fn set_retry_progress(&mut self, progress: Arc<dyn ProgressCallback>) {
+        self.retrier.set_progress(progress);
+    }
+
+    fn supports_pr_status(&self) -> bool {
+        true
+    }
+
+    fn supports_stacked_bases(&self) -> bool {
+        // Bases must be branches of this repository, but stack bookmarks
+        // only exist in the fork
+        self.head_owner.is_none()
+    }
+
+    async fn set_head_repository(&mut self, head: &PlatformConfig) -> Result<()> {
+        if head.host != self.config.host {
+            return Err(Error::GitHubApi(format!(
+                "fork {}/{} is not on the same host as {}/{}",
+                head.owner, head.repo, self.config.owner, self.config.repo
+            )));
+        }
+        self.head_owner = (head.owner != self.config.owner).then(|| head.owner.clone());
+        debug!(head_owner = ?self.head_owner, "using head repository");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_lookup_query_aliases_each_head() {
+        let query = batch_lookup_query(2);
+        assert!(
+            query.starts_with("query($owner: String!, $repo: String!, $h0: String!, $h1: String!)")
+        );
+        assert!(query.contains("h0: pullRequests(headRefName: $h0"));
+        assert!(query.contains("h1: pullRequests(headRefName: $h1"));
+        assert!(!query.contains("$h2"));
+    }
+
+    #[test]
+    fn test_batch_lookup_response_parses() {
+        let body = serde_json::json!({
+            "repository": {
+                "h0": { "nodes": [{
+                    "number": 7, "url": "https://github.com/o/r/pull/7",
+                    "baseRefName": "main", "headRefName": "feat-a", "title": "Feat A",
+                    "id": "PR_7", "isDraft": true,
+                    "headRepositoryOwner": { "login": "o" }
+                }] },
+                "h1": { "nodes": [] }
+            }
+        });
+        let data: BatchLookupData = serde_json::from_value(body).unwrap();
+        let node = &data.repository["h0"].nodes[0];
+        assert_eq!(node.pr.number, 7);
+        assert_eq!(node.head_repository_owner.as_ref().unwrap().login, "o");
+        assert!(data.repository["h1"].nodes.is_empty());
+    }
+
+    #[test]
+    fn test_mergeability_response_parses() {
+        let body = serde_json::json!({
+            "repository": { "pullRequest": {
+                "reviewDecision": "REVIEW_REQUIRED",
+                "mergeable": "MERGEABLE",
+                "commits": { "nodes": [{ "commit": {
+                    "statusCheckRollup": { "state": "PENDING" }
+                } }] }
+            } }
+        });
+        let data: MergeabilityData = serde_json::from_value(body).unwrap();
+        let mergeability = Mergeability::from(data.repository.pull_request);
+        assert!(!mergeability.approved);
+        assert_eq!(mergeability.checks, CheckStatus::Pending);
+        assert_eq!(mergeability.mergeable, Some(true));
+
+        let body = serde_json::json!({
+            "reviewDecision": null,
+            "mergeable": "UNKNOWN",
+            "commits": { "nodes": [{ "commit": { "statusCheckRollup": null } }] }
+        });
+        let pr: MergeabilityPullRequest = serde_json::from_value(body).unwrap();
+        let mergeability = Mergeability::from(pr);
+        assert!(mergeability.approved);
+        assert_eq!(mergeability.checks, CheckStatus::None);
+        assert_eq!(mergeability.mergeable, None);
+    }
+
+    #[test]
+    fn test_pr_status_response_parses() {
+        let body = serde_json::json!({
+            "repository": { "pullRequest": {
+                "number": 7,
+                "url": "https://github.com/o/r/pull/7",
+                "baseRefName": "main",
+                "headRefName": "feat-a",
+                "title": "Add feature",
+                "body": "",
+                "id": "PR_7",
+                "isDraft": true,
+                "state": "OPEN",
+                "reviewDecision": "CHANGES_REQUESTED",
+                "latestReviews": { "nodes": [
+                    { "state": "APPROVED" },
+                    { "state": "CHANGES_REQUESTED" },
+                    { "state": "APPROVED" }
+                ] },
+                "reviewThreads": { "nodes": [
+                    { "isResolved": true },
+                    { "isResolved": false }
+                ] },
+                "commits": { "nodes": [{ "commit": {
+                    "statusCheckRollup": { "state": "FAILURE" }
+                } }] }
+            } }
+        });
+        let data: PrStatusData = serde_json::from_value(body).unwrap();
+        let status = PrStatus::from(data.repository.pull_request);
+        assert_eq!(status.pr.number, 7);
+        assert!(status.pr.is_draft);
+        assert_eq!(status.state, PrState::Open);
+        assert_eq!(status.review_decision, ReviewDecision::ChangesRequested);
+        assert_eq!(status.approvals, 2);
+        assert_eq!(status.unresolved_threads, 1);
+        assert_eq!(status.checks, CheckStatus::Failure);
+    }
+
+    #[test]
+    fn test_merge_queue_response_parses() {
+        let body = serde_json::json!({
+            "repository": { "pullRequest": {
+                "mergeQueueEntry": { "position": 2, "state": "AWAITING_CHECKS" }
+            } }
+        });
+        let data: MergeQueueData = serde_json::from_value(body).unwrap();
+        let entry = data
+            .repository
+            .pull_request
+            .merge_queue_entry
+            .map(MergeQueueEntry::from);
+        assert_eq!(
+            entry,
+            Some(MergeQueueEntry {
+                position: Some(2),
+                state: MergeQueueState::AwaitingChecks,
+            })
+        );
+
+        let body = serde_json::json!({ "mergeQueueEntry": null });
+        let pr: MergeQueuePullRequest = serde_json::from_value(body).unwrap();
+        assert!(pr.merge_queue_entry.is_none());
+    }
 }
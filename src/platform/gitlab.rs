@@ -2,10 +2,18 @@
 
 use crate::error::{Error, Result};
 use crate::platform::PlatformService;
-use crate::types::{Platform, PlatformConfig, PrComment, PullRequest};
+use crate::platform::retry::{Retrier, RetryPolicy, classify_response, classify_transport_error};
+use crate::submit::ProgressCallback;
+use crate::types::{
+    CheckStatus, ClosedPullRequest, MergeMethod, Mergeability, Platform, PlatformConfig, PrComment,
+    PrState, PrStatus, PrTriage, PullRequest, ReviewDecision,
+};
 use async_trait::async_trait;
-use reqwest::Client;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tracing::debug;
 
 /// GitLab service using reqwest
@@ -15,9 +23,28 @@ pub struct GitLabService {
     host: String,
     config: PlatformConfig,
     project_path: String,
+    /// Fork MR heads live in, if not this project
+    fork: Option<GitLabFork>,
+    retrier: Retrier,
+}
+
+/// Source project of cross-project (fork) merge requests
+struct GitLabFork {
+    /// Path of the fork, where MRs are created
+    source_path: String,
+    /// ID of the fork, used to match existing MRs
+    source_project_id: u64,
+    /// ID of this (upstream) project, the MR target
+    target_project_id: u64,
+}
+
+#[derive(Deserialize)]
+struct Project {
+    id: u64,
 }
 
 #[derive(Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 struct MergeRequest {
     iid: u64,
     web_url: String,
@@ -25,7 +52,75 @@ struct MergeRequest {
     target_branch: String,
     title: String,
     #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
     draft: bool,
+    #[serde(default)]
+    source_project_id: u64,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    merged_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    closed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    reviewers: Vec<GitLabUser>,
+    #[serde(default)]
+    assignees: Vec<GitLabUser>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    has_conflicts: bool,
+    /// e.g. `mergeable`, `checking`, `unchecked`
+    #[serde(default)]
+    detailed_merge_status: String,
+    #[serde(default)]
+    head_pipeline: Option<Pipeline>,
+    #[serde(default)]
+    merge_when_pipeline_succeeds: bool,
+    #[serde(default)]
+    squash: bool,
+}
+
+#[derive(Deserialize)]
+struct Pipeline {
+    status: String,
+}
+
+#[derive(Deserialize)]
+struct Approvals {
+    approved: bool,
+    #[serde(default)]
+    approved_by: Vec<serde::de::IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+struct Discussion {
+    notes: Vec<DiscussionNote>,
+}
+
+#[derive(Deserialize)]
+struct DiscussionNote {
+    #[serde(default)]
+    resolvable: bool,
+    #[serde(default)]
+    resolved: bool,
+}
+
+/// CI status of an MR's head pipeline
+fn pipeline_checks(pipeline: Option<&Pipeline>) -> CheckStatus {
+    match pipeline.map(|p| p.status.as_str()) {
+        Some("success") => CheckStatus::Success,
+        Some("failed" | "canceled") => CheckStatus::Failure,
+        Some("skipped") | None => CheckStatus::None,
+        Some(_) => CheckStatus::Pending,
+    }
+}
+
+#[derive(Deserialize)]
+struct GitLabUser {
+    id: u64,
+    username: String,
 }
 
 #[derive(Deserialize)]
@@ -37,12 +132,19 @@ struct MrNote {
 
 impl From<MergeRequest> for PullRequest {
     fn from(mr: MergeRequest) -> Self {
+        // A draft's title carries a draft prefix; `is_draft` reports it instead
+        let title = if mr.draft {
+            strip_draft_prefix(&mr.title).to_string()
+        } else {
+            mr.title
+        };
         Self {
             number: mr.iid,
             html_url: mr.web_url,
             base_ref: mr.target_branch,
             head_ref: mr.source_branch,
-            title: mr.title,
+            title,
+            body: mr.description.unwrap_or_default(),
             node_id: None, // GitLab doesn't use GraphQL node IDs
             is_draft: mr.draft,
         }
@@ -54,13 +156,40 @@ struct CreateMrPayload {
     source_branch: String,
     target_branch: String,
     title: String,
+    description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     draft: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_project_id: Option<u64>,
+}
+
+/// Title prefixes GitLab uses to mark a draft MR (matched case-insensitively)
+const DRAFT_PREFIXES: &[&str] = &["draft:", "[draft]", "(draft)"];
+
+/// Title prefix ryu writes to keep an MR a draft
+const DRAFT_PREFIX: &str = "Draft: ";
+
+/// Strip a draft prefix (and following whitespace) from an MR title
+fn strip_draft_prefix(title: &str) -> &str {
+    DRAFT_PREFIXES
+        .iter()
+        .find(|prefix| {
+            title
+                .get(..prefix.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+        })
+        .map_or(title, |prefix| title[prefix.len()..].trim_start())
 }
 
 /// Default request timeout in seconds
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
+/// Page size for listing open MRs in batched lookups (GitLab's maximum)
+const LOOKUP_PAGE_SIZE: u32 = 100;
+
+/// Pages of open MRs scanned before falling back to per-branch lookups
+const MAX_LOOKUP_PAGES: u32 = 10;
+
 impl GitLabService {
     /// Create a new GitLab service
     pub fn new(token: String, owner: String, repo: String, host: Option<String>) -> Result<Self> {
@@ -89,9 +218,46 @@ impl GitLabService {
                 host: config_host,
             },
             project_path,
+            fork: None,
+            retrier: Retrier::default(),
         })
     }
 
+    /// Replace the limits for retrying failed requests
+    pub const fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retrier.set_policy(policy);
+    }
+
+    /// Send an authenticated request, retrying transient failures and rate limits
+    ///
+    /// `request` builds a fresh request for each attempt. Server errors are
+    /// only retried when the request is `idempotent`.
+    async fn send(
+        &self,
+        idempotent: bool,
+        request: impl Fn() -> RequestBuilder + Send + Sync,
+    ) -> Result<Response> {
+        let mut retry = 0;
+        loop {
+            let sent = request().header("PRIVATE-TOKEN", &self.token).send().await;
+            let (class, error) = match sent {
+                Ok(response) => {
+                    let class =
+                        classify_response(response.status(), response.headers(), idempotent);
+                    match response.error_for_status() {
+                        Ok(response) => return Ok(response),
+                        Err(e) => (class, Error::GitLabApi(e.to_string())),
+                    }
+                }
+                Err(e) => (classify_transport_error(&e, idempotent), e.into()),
+            };
+
+            if !self.retrier.wait("GitLab", class, &mut retry).await {
+                return Err(error);
+            }
+        }
+    }
+
     fn api_url(&self, path: &str) -> String {
         format!("https://{}/api/v4{}", self.host, path)
     }
@@ -99,6 +265,132 @@ impl GitLabService {
     fn encoded_project(&self) -> String {
         urlencoding::encode(&self.project_path).into_owned()
     }
+
+    /// Look up a project's numeric ID by path
+    async fn project_id(&self, path: &str) -> Result<u64> {
+        let url = self.api_url(&format!("/projects/{}", urlencoding::encode(path)));
+
+        let project: Project = self
+            .send(true, || self.client.get(&url))
+            .await?
+            .json()
+            .await?;
+
+        Ok(project.id)
+    }
+
+    /// Fetch a merge request of this project
+    async fn get_mr(&self, iid: u64) -> Result<MergeRequest> {
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}",
+            self.encoded_project(),
+            iid
+        ));
+        Ok(self
+            .send(true, || self.client.get(&url))
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Approval state of an MR
+    async fn approvals(&self, iid: u64) -> Result<Approvals> {
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/approvals",
+            self.encoded_project(),
+            iid
+        ));
+        Ok(self
+            .send(true, || self.client.get(&url))
+            .await?
+            .json()
+            .await?)
+    }
+
+    /// Look up a user's numeric ID by username
+    async fn user_id(&self, username: &str) -> Result<u64> {
+        let url = self.api_url(&format!(
+            "/users?username={}",
+            urlencoding::encode(username)
+        ));
+        let users: Vec<GitLabUser> = self
+            .send(true, || self.client.get(&url))
+            .await?
+            .json()
+            .await?;
+        users
+            .into_iter()
+            .next()
+            .map(|u| u.id)
+            .ok_or_else(|| Error::GitLabApi(format!("no GitLab user named '{username}'")))
+    }
+
+    /// IDs of `current` users plus those of `usernames`
+    async fn merged_user_ids(
+        &self,
+        current: &[GitLabUser],
+        usernames: &[String],
+    ) -> Result<Vec<u64>> {
+        let mut ids: Vec<u64> = current.iter().map(|u| u.id).collect();
+        for username in usernames {
+            let id = self.user_id(username).await?;
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Update fields of a merge request
+    async fn put_mr(&self, iid: u64, fields: &serde_json::Value) -> Result<()> {
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}",
+            self.encoded_project(),
+            iid
+        ));
+        self.send(true, || self.client.put(&url).json(fields))
+            .await?;
+        Ok(())
+    }
+
+    /// Merge an MR, now or once its pipeline succeeds
+    async fn accept_mr(
+        &self,
+        iid: u64,
+        method: MergeMethod,
+        when_pipeline_succeeds: bool,
+    ) -> Result<()> {
+        // Merge commit vs fast-forward is a project setting; only squashing
+        // can be chosen per MR
+        let squash = match method {
+            MergeMethod::Merge => false,
+            MergeMethod::Squash => true,
+            MergeMethod::Rebase => {
+                return Err(Error::GitLabApi(
+                    "rebase merges are set per project on GitLab; use merge or squash".to_string(),
+                ));
+            }
+        };
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/merge",
+            self.encoded_project(),
+            iid
+        ));
+        let body = serde_json::json!({
+            "squash": squash,
+            "merge_when_pipeline_succeeds": when_pipeline_succeeds,
+        });
+        self.send(false, || self.client.put(&url).json(&body))
+            .await?;
+        Ok(())
+    }
+
+    /// Whether an MR comes from the project PR heads live in
+    fn is_from_head_project(&self, mr: &MergeRequest) -> bool {
+        self.fork
+            .as_ref()
+            .is_none_or(|fork| mr.source_project_id == fork.source_project_id)
+    }
 }
 
 #[async_trait]
@@ -111,18 +403,20 @@ impl PlatformService for GitLabService {
         ));
 
         let mrs: Vec<MergeRequest> = self
-            .client
-            .get(&url)
-            .header("PRIVATE-TOKEN", &self.token)
-            .query(&[("source_branch", head_branch), ("state", "opened")])
-            .send()
+            .send(true, || {
+                self.client
+                    .get(&url)
+                    .query(&[("source_branch", head_branch), ("state", "opened")])
+            })
             .await?
-            .error_for_status()
-            .map_err(|e| Error::GitLabApi(e.to_string()))?
             .json()
             .await?;
 
-        let result: Option<PullRequest> = mrs.into_iter().next().map(Into::into);
+        // Branch names aren't unique across forks; match the source project
+        let result: Option<PullRequest> = mrs
+            .into_iter()
+            .find(|mr| self.is_from_head_project(mr))
+            .map(Into::into);
         if let Some(ref pr) = result {
             debug!(mr_iid = pr.number, "found existing MR");
         } else {
@@ -131,35 +425,134 @@ impl PlatformService for GitLabService {
         Ok(result)
     }
 
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        debug!(head_branch, "finding closed MR");
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests",
+            self.encoded_project()
+        ));
+
+        // Newest first; both merged and closed MRs count as finished
+        let mrs: Vec<MergeRequest> = self
+            .send(true, || {
+                self.client.get(&url).query(&[
+                    ("source_branch", head_branch),
+                    ("order_by", "updated_at"),
+                    ("sort", "desc"),
+                ])
+            })
+            .await?
+            .json()
+            .await?;
+
+        Ok(mrs
+            .into_iter()
+            .filter(|mr| self.is_from_head_project(mr))
+            .find(|mr| mr.state == "merged" || mr.state == "closed")
+            .map(|mr| {
+                let merged = mr.state == "merged";
+                let closed_at = mr.merged_at.or(mr.closed_at);
+                ClosedPullRequest {
+                    pr: mr.into(),
+                    merged,
+                    closed_at,
+                }
+            }))
+    }
+
+    async fn find_existing_prs(
+        &self,
+        head_branches: &[&str],
+    ) -> Result<HashMap<String, PullRequest>> {
+        debug!(count = head_branches.len(), "finding existing MRs");
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests",
+            self.encoded_project()
+        ));
+        let wanted: HashSet<&str> = head_branches.iter().copied().collect();
+        let mut result = HashMap::new();
+
+        // `source_branch` only takes one value, so scan the open MRs instead
+        // (newest first, matching `find_existing_pr`)
+        let mut page = 1;
+        let mut complete = false;
+        while page <= MAX_LOOKUP_PAGES && result.len() < wanted.len() {
+            let response = self
+                .send(true, || {
+                    self.client.get(&url).query(&[
+                        ("state", "opened".to_string()),
+                        ("per_page", LOOKUP_PAGE_SIZE.to_string()),
+                        ("page", page.to_string()),
+                    ])
+                })
+                .await?;
+
+            let next_page = response
+                .headers()
+                .get("x-next-page")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u32>().ok());
+            let mrs: Vec<MergeRequest> = response.json().await?;
+
+            for mr in mrs {
+                if wanted.contains(mr.source_branch.as_str())
+                    && !result.contains_key(&mr.source_branch)
+                    && self.is_from_head_project(&mr)
+                {
+                    result.insert(mr.source_branch.clone(), mr.into());
+                }
+            }
+
+            let Some(next) = next_page else {
+                complete = true;
+                break;
+            };
+            page = next;
+        }
+
+        // Listing capped before reaching the end: look up the rest one by one
+        if !complete && result.len() < wanted.len() {
+            for head in head_branches {
+                if !result.contains_key(*head)
+                    && let Some(pr) = self.find_existing_pr(head).await?
+                {
+                    result.insert((*head).to_string(), pr);
+                }
+            }
+        }
+
+        debug!(found = result.len(), "found existing MRs");
+        Ok(result)
+    }
+
     async fn create_pr_with_options(
         &self,
         head: &str,
         base: &str,
         title: &str,
+        body: &str,
         draft: bool,
     ) -> Result<PullRequest> {
         debug!(head, base, draft, "creating MR");
-        let url = self.api_url(&format!(
-            "/projects/{}/merge_requests",
-            self.encoded_project()
-        ));
+        // Cross-project MRs are created on the source project
+        let source_project = self.fork.as_ref().map_or_else(
+            || self.encoded_project(),
+            |fork| urlencoding::encode(&fork.source_path).into_owned(),
+        );
+        let url = self.api_url(&format!("/projects/{source_project}/merge_requests"));
 
         let payload = CreateMrPayload {
             source_branch: head.to_string(),
             target_branch: base.to_string(),
             title: title.to_string(),
+            description: body.to_string(),
             draft: if draft { Some(true) } else { None },
+            target_project_id: self.fork.as_ref().map(|fork| fork.target_project_id),
         };
 
         let mr: MergeRequest = self
-            .client
-            .post(&url)
-            .header("PRIVATE-TOKEN", &self.token)
-            .json(&payload)
-            .send()
+            .send(false, || self.client.post(&url).json(&payload))
             .await?
-            .error_for_status()
-            .map_err(|e| Error::GitLabApi(e.to_string()))?
             .json()
             .await?;
 
@@ -177,14 +570,12 @@ impl PlatformService for GitLabService {
         ));
 
         let mr: MergeRequest = self
-            .client
-            .put(&url)
-            .header("PRIVATE-TOKEN", &self.token)
-            .json(&serde_json::json!({ "target_branch": new_base }))
-            .send()
+            .send(true, || {
+                self.client
+                    .put(&url)
+                    .json(&serde_json::json!({ "target_branch": new_base }))
+            })
             .await?
-            .error_for_status()
-            .map_err(|e| Error::GitLabApi(e.to_string()))?
             .json()
             .await?;
 
@@ -192,6 +583,64 @@ impl PlatformService for GitLabService {
         Ok(mr.into())
     }
 
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        debug!(mr_iid = pr_number, "updating MR title and description");
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}",
+            self.encoded_project(),
+            pr_number
+        ));
+
+        // Drafts are marked by a title prefix, so keep one on a draft
+        let current: MergeRequest = self
+            .send(true, || self.client.get(&url))
+            .await?
+            .json()
+            .await?;
+        let title = if current.draft {
+            format!("{DRAFT_PREFIX}{title}")
+        } else {
+            title.to_string()
+        };
+
+        let mr: MergeRequest = self
+            .send(true, || {
+                self.client
+                    .put(&url)
+                    .json(&serde_json::json!({ "title": title, "description": body }))
+            })
+            .await?
+            .json()
+            .await?;
+
+        debug!(mr_iid = pr_number, "updated MR title and description");
+        Ok(mr.into())
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(mr_iid = pr_number, "closing MR");
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}",
+            self.encoded_project(),
+            pr_number
+        ));
+
+        self.send(true, || {
+            self.client
+                .put(&url)
+                .json(&serde_json::json!({ "state_event": "close" }))
+        })
+        .await?;
+
+        debug!(mr_iid = pr_number, "closed MR");
+        Ok(())
+    }
+
     async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
         debug!(mr_iid = pr_number, "publishing MR");
         // GitLab: Use state_event to mark MR as ready
@@ -204,14 +653,12 @@ impl PlatformService for GitLabService {
 
         // GitLab uses state_event: "ready" to mark as ready for review
         let mr: MergeRequest = self
-            .client
-            .put(&url)
-            .header("PRIVATE-TOKEN", &self.token)
-            .json(&serde_json::json!({ "state_event": "ready" }))
-            .send()
+            .send(true, || {
+                self.client
+                    .put(&url)
+                    .json(&serde_json::json!({ "state_event": "ready" }))
+            })
             .await?
-            .error_for_status()
-            .map_err(|e| Error::GitLabApi(e.to_string()))?
             .json()
             .await?;
 
@@ -228,13 +675,8 @@ impl PlatformService for GitLabService {
         ));
 
         let notes: Vec<MrNote> = self
-            .client
-            .get(&url)
-            .header("PRIVATE-TOKEN", &self.token)
-            .send()
+            .send(true, || self.client.get(&url))
             .await?
-            .error_for_status()
-            .map_err(|e| Error::GitLabApi(e.to_string()))?
             .json()
             .await?;
 
@@ -262,14 +704,12 @@ impl PlatformService for GitLabService {
             pr_number
         ));
 
-        self.client
-            .post(&url)
-            .header("PRIVATE-TOKEN", &self.token)
-            .json(&serde_json::json!({ "body": body }))
-            .send()
-            .await?
-            .error_for_status()
-            .map_err(|e| Error::GitLabApi(e.to_string()))?;
+        self.send(false, || {
+            self.client
+                .post(&url)
+                .json(&serde_json::json!({ "body": body }))
+        })
+        .await?;
 
         debug!(mr_iid = pr_number, "created MR comment");
         Ok(())
@@ -284,20 +724,188 @@ impl PlatformService for GitLabService {
             comment_id
         ));
 
-        self.client
-            .put(&url)
-            .header("PRIVATE-TOKEN", &self.token)
-            .json(&serde_json::json!({ "body": body }))
-            .send()
-            .await?
-            .error_for_status()
-            .map_err(|e| Error::GitLabApi(e.to_string()))?;
+        self.send(true, || {
+            self.client
+                .put(&url)
+                .json(&serde_json::json!({ "body": body }))
+        })
+        .await?;
 
         debug!(mr_iid = pr_number, comment_id, "updated MR comment");
         Ok(())
     }
 
+    async fn get_pr_triage(&self, pr_number: u64) -> Result<PrTriage> {
+        let mr = self.get_mr(pr_number).await?;
+        Ok(PrTriage {
+            reviewers: mr.reviewers.into_iter().map(|u| u.username).collect(),
+            assignees: mr.assignees.into_iter().map(|u| u.username).collect(),
+            labels: mr.labels,
+        })
+    }
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()> {
+        debug!(mr_iid = pr_number, ?reviewers, "requesting reviewers");
+        // Setting reviewer_ids replaces the list, so send the current ones too
+        let mr = self.get_mr(pr_number).await?;
+        let ids = self.merged_user_ids(&mr.reviewers, reviewers).await?;
+        self.put_mr(pr_number, &serde_json::json!({ "reviewer_ids": ids }))
+            .await
+    }
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()> {
+        debug!(mr_iid = pr_number, ?assignees, "adding assignees");
+        let mr = self.get_mr(pr_number).await?;
+        let ids = self.merged_user_ids(&mr.assignees, assignees).await?;
+        self.put_mr(pr_number, &serde_json::json!({ "assignee_ids": ids }))
+            .await
+    }
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()> {
+        debug!(mr_iid = pr_number, ?labels, "adding labels");
+        self.put_mr(
+            pr_number,
+            &serde_json::json!({ "add_labels": labels.join(",") }),
+        )
+        .await
+    }
+
+    async fn get_pr_status(&self, pr_number: u64) -> Result<PrStatus> {
+        let mr = self.get_mr(pr_number).await?;
+        let approvals = self.approvals(pr_number).await?;
+        let url = self.api_url(&format!(
+            "/projects/{}/merge_requests/{}/discussions?per_page=100",
+            self.encoded_project(),
+            pr_number
+        ));
+        let discussions: Vec<Discussion> = self
+            .send(true, || self.client.get(&url))
+            .await?
+            .json()
+            .await?;
+
+        let state = match mr.state.as_str() {
+            "merged" => PrState::Merged,
+            "closed" => PrState::Closed,
+            _ => PrState::Open,
+        };
+        let review_decision = if !approvals.approved {
+            ReviewDecision::ReviewRequired
+        } else if approvals.approved_by.is_empty() {
+            ReviewDecision::None
+        } else {
+            ReviewDecision::Approved
+        };
+        // A thread is unresolved while any of its resolvable notes is
+        let unresolved_threads = discussions
+            .iter()
+            .filter(|d| d.notes.iter().any(|n| n.resolvable && !n.resolved))
+            .count();
+        let checks = pipeline_checks(mr.head_pipeline.as_ref());
+        Ok(PrStatus {
+            pr: mr.into(),
+            state,
+            review_decision,
+            approvals: u32::try_from(approvals.approved_by.len()).unwrap_or(u32::MAX),
+            unresolved_threads: u32::try_from(unresolved_threads).unwrap_or(u32::MAX),
+            checks,
+        })
+    }
+
+    async fn get_mergeability(&self, pr_number: u64) -> Result<Mergeability> {
+        let mr = self.get_mr(pr_number).await?;
+        let approvals = self.approvals(pr_number).await?;
+
+        let checks = pipeline_checks(mr.head_pipeline.as_ref());
+        let mergeable = if mr.has_conflicts {
+            Some(false)
+        } else if matches!(
+            mr.detailed_merge_status.as_str(),
+            "checking" | "unchecked" | "preparing"
+        ) {
+            None
+        } else {
+            Some(true)
+        };
+        Ok(Mergeability {
+            approved: approvals.approved,
+            checks,
+            mergeable,
+        })
+    }
+
+    async fn merge_pr(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        debug!(mr_iid = pr_number, %method, "merging MR");
+        self.accept_mr(pr_number, method, false).await
+    }
+
+    async fn get_auto_merge(&self, pr_number: u64) -> Result<Option<MergeMethod>> {
+        let mr = self.get_mr(pr_number).await?;
+        Ok(mr.merge_when_pipeline_succeeds.then_some(if mr.squash {
+            MergeMethod::Squash
+        } else {
+            MergeMethod::Merge
+        }))
+    }
+
+    async fn enable_auto_merge(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        debug!(mr_iid = pr_number, %method, "enabling merge when pipeline succeeds");
+        self.accept_mr(pr_number, method, true).await
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        let url = self.api_url("/user");
+        let user: GitLabUser = self
+            .send(true, || self.client.get(&url))
+            .await?
+            .json()
+            .await?;
+        Ok(user.username)
+    }
+
     fn config(&self) -> &PlatformConfig {
         &self.config
     }
+
+    fn set_retry_progress(&mut self, progress: Arc<dyn ProgressCallback>) {
+        self.retrier.set_progress(progress);
+    }
+
+    fn supports_pr_status(&self) -> bool {
+        true
+    }
+
+    fn supports_stacked_bases(&self) -> bool {
+        // Targets must be branches of this project, but stack bookmarks
+        // only exist in the fork
+        self.fork.is_none()
+    }
+
+    async fn set_head_repository(&mut self, head: &PlatformConfig) -> Result<()> {
+        if head.host != self.config.host {
+            return Err(Error::GitLabApi(format!(
+                "fork {}/{} is not on the same host as {}",
+                head.owner, head.repo, self.project_path
+            )));
+        }
+
+        let source_path = format!("{}/{}", head.owner, head.repo);
+        if source_path == self.project_path {
+            self.fork = None;
+            return Ok(());
+        }
+
+        let source_project_id = self.project_id(&source_path).await?;
+        let target_project_id = self.project_id(&self.project_path).await?;
+        debug!(
+            source_project_id,
+            target_project_id, "using fork as MR source"
+        );
+        self.fork = Some(GitLabFork {
+            source_path,
+            source_project_id,
+            target_project_id,
+        });
+        Ok(())
+    }
 }
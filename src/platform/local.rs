@@ -0,0 +1,545 @@
+//! Local file-backed "fake forge" platform service
+//!
+//! Stores PRs and comments in a JSON database under `.jj/repo/ryu/`, so the
+//! whole submit pipeline can run offline (demos, sandboxed CI, airplanes).
+
+use crate::error::{Error, Result};
+use crate::platform::PlatformService;
+use crate::types::{ClosedPullRequest, Platform, PlatformConfig, PrComment, PrTriage, PullRequest};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::debug;
+
+/// Filename of the local forge database within `.jj/repo/ryu/`
+const LOCAL_FORGE_FILE: &str = "forge.json";
+
+/// Current version of the local forge database format
+pub const LOCAL_FORGE_VERSION: u32 = 1;
+
+/// Get path to the local forge database for a workspace
+pub fn local_forge_path(workspace_root: &Path) -> PathBuf {
+    workspace_root
+        .join(".jj")
+        .join("repo")
+        .join("ryu")
+        .join(LOCAL_FORGE_FILE)
+}
+
+/// Local forge service backed by a JSON file
+pub struct LocalService {
+    path: PathBuf,
+    /// Serializes read-modify-write cycles on the database file
+    lock: Mutex<()>,
+    config: PlatformConfig,
+}
+
+/// On-disk database of the local forge
+#[derive(Debug, Serialize, Deserialize)]
+struct LocalDatabase {
+    version: u32,
+    next_pr_number: u64,
+    next_comment_id: u64,
+    #[serde(default)]
+    prs: Vec<LocalPullRequest>,
+}
+
+impl Default for LocalDatabase {
+    fn default() -> Self {
+        Self {
+            version: LOCAL_FORGE_VERSION,
+            next_pr_number: 1,
+            next_comment_id: 1,
+            prs: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LocalPullRequest {
+    number: u64,
+    head_ref: String,
+    base_ref: String,
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    is_draft: bool,
+    #[serde(default)]
+    state: LocalPrState,
+    #[serde(default)]
+    comments: Vec<LocalComment>,
+    #[serde(default, skip_serializing_if = "PrTriage::is_empty")]
+    triage: PrTriage,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LocalPrState {
+    #[default]
+    Open,
+    Closed,
+    Merged,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LocalComment {
+    id: u64,
+    body: String,
+}
+
+impl LocalService {
+    /// Open (or lazily create) the local forge database of a workspace
+    pub fn open(workspace_root: &Path, config: PlatformConfig) -> Self {
+        Self::with_path(local_forge_path(workspace_root), config)
+    }
+
+    /// Use a database at an explicit path
+    pub fn with_path(path: PathBuf, config: PlatformConfig) -> Self {
+        Self {
+            path,
+            lock: Mutex::new(()),
+            config: PlatformConfig {
+                platform: Platform::Local,
+                ..config
+            },
+        }
+    }
+
+    /// URL of a PR: the database file, with the PR number as fragment
+    fn pr_url(&self, number: u64) -> String {
+        url::Url::from_file_path(&self.path).map_or_else(
+            |()| format!("file://{}#pr-{number}", self.path.display()),
+            |mut url| {
+                url.set_fragment(Some(&format!("pr-{number}")));
+                url.to_string()
+            },
+        )
+    }
+
+    fn to_pull_request(&self, pr: &LocalPullRequest) -> PullRequest {
+        PullRequest {
+            number: pr.number,
+            html_url: self.pr_url(pr.number),
+            base_ref: pr.base_ref.clone(),
+            head_ref: pr.head_ref.clone(),
+            title: pr.title.clone(),
+            body: pr.body.clone(),
+            node_id: None, // The local forge doesn't use GraphQL node IDs
+            is_draft: pr.is_draft,
+        }
+    }
+
+    fn load(&self) -> Result<LocalDatabase> {
+        if !self.path.exists() {
+            return Ok(LocalDatabase::default());
+        }
+
+        let content = fs::read_to_string(&self.path).map_err(|e| {
+            Error::LocalForge(format!("failed to read {}: {e}", self.path.display()))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::LocalForge(format!("failed to parse {}: {e}", self.path.display())))
+    }
+
+    fn save(&self, db: &LocalDatabase) -> Result<()> {
+        if let Some(dir) = self.path.parent()
+            && !dir.exists()
+        {
+            fs::create_dir_all(dir).map_err(|e| {
+                Error::LocalForge(format!("failed to create {}: {e}", dir.display()))
+            })?;
+        }
+
+        let content = serde_json::to_string_pretty(db)
+            .map_err(|e| Error::LocalForge(format!("failed to serialize database: {e}")))?;
+        fs::write(&self.path, content)
+            .map_err(|e| Error::LocalForge(format!("failed to write {}: {e}", self.path.display())))
+    }
+
+    /// Run a read-modify-write transaction against the database
+    fn transact<T>(&self, f: impl FnOnce(&mut LocalDatabase) -> Result<T>) -> Result<T> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| Error::Internal("local forge lock poisoned".to_string()))?;
+        let mut db = self.load()?;
+        let result = f(&mut db)?;
+        self.save(&db)?;
+        Ok(result)
+    }
+
+    /// Read-only access to the database
+    fn read<T>(&self, f: impl FnOnce(&LocalDatabase) -> T) -> Result<T> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| Error::Internal("local forge lock poisoned".to_string()))?;
+        Ok(f(&self.load()?))
+    }
+
+    /// Add the reviewers, assignees and labels a PR doesn't have yet
+    fn add_triage(&self, pr_number: u64, add: &PrTriage) -> Result<()> {
+        self.transact(|db| {
+            let triage = &mut find_pr_mut(db, pr_number)?.triage;
+            let missing = add.missing_from(triage);
+            triage.reviewers.extend(missing.reviewers);
+            triage.assignees.extend(missing.assignees);
+            triage.labels.extend(missing.labels);
+            Ok(())
+        })
+    }
+
+    /// Apply a change to a PR and return its new state
+    fn update_pr(
+        &self,
+        pr_number: u64,
+        f: impl FnOnce(&mut LocalPullRequest),
+    ) -> Result<PullRequest> {
+        self.transact(|db| {
+            let pr = find_pr_mut(db, pr_number)?;
+            f(pr);
+            Ok(self.to_pull_request(pr))
+        })
+    }
+}
+
+fn find_pr_mut(db: &mut LocalDatabase, pr_number: u64) -> Result<&mut LocalPullRequest> {
+    db.prs
+        .iter_mut()
+        .find(|pr| pr.number == pr_number)
+        .ok_or_else(|| Error::LocalForge(format!("PR #{pr_number} not found")))
+}
+
+#[async_trait]
+impl PlatformService for LocalService {
+    async fn find_existing_pr(&self, head_branch: &str) -> Result<Option<PullRequest>> {
+        debug!(head_branch, "finding existing PR");
+        let result = self.read(|db| {
+            db.prs
+                .iter()
+                .find(|pr| pr.head_ref == head_branch && pr.state == LocalPrState::Open)
+                .map(|pr| self.to_pull_request(pr))
+        })?;
+        if let Some(ref pr) = result {
+            debug!(pr_number = pr.number, "found existing PR");
+        } else {
+            debug!("no existing PR found");
+        }
+        Ok(result)
+    }
+
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        debug!(head_branch, "finding closed PR");
+        // The newest PR wins; merges and closes are recorded by editing the
+        // database (`"state": "merged"`)
+        self.read(|db| {
+            db.prs
+                .iter()
+                .rev()
+                .find(|pr| pr.head_ref == head_branch && pr.state != LocalPrState::Open)
+                .map(|pr| ClosedPullRequest {
+                    pr: self.to_pull_request(pr),
+                    merged: pr.state == LocalPrState::Merged,
+                    closed_at: None,
+                })
+        })
+    }
+
+    async fn create_pr_with_options(
+        &self,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        debug!(head, base, draft, "creating PR");
+        let pr = self.transact(|db| {
+            if db
+                .prs
+                .iter()
+                .any(|pr| pr.head_ref == head && pr.state == LocalPrState::Open)
+            {
+                return Err(Error::LocalForge(format!(
+                    "an open PR already exists for {head}"
+                )));
+            }
+
+            let pr = LocalPullRequest {
+                number: db.next_pr_number,
+                head_ref: head.to_string(),
+                base_ref: base.to_string(),
+                title: title.to_string(),
+                body: body.to_string(),
+                is_draft: draft,
+                state: LocalPrState::Open,
+                comments: Vec::new(),
+                triage: PrTriage::default(),
+            };
+            db.next_pr_number += 1;
+            let created = self.to_pull_request(&pr);
+            db.prs.push(pr);
+            Ok(created)
+        })?;
+        debug!(pr_number = pr.number, "created PR");
+        Ok(pr)
+    }
+
+    async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<PullRequest> {
+        debug!(pr_number, new_base, "updating PR base");
+        let pr = self.update_pr(pr_number, |pr| pr.base_ref = new_base.to_string())?;
+        debug!(pr_number, "updated PR base");
+        Ok(pr)
+    }
+
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        debug!(pr_number, "updating PR title and body");
+        let pr = self.update_pr(pr_number, |pr| {
+            pr.title = title.to_string();
+            pr.body = body.to_string();
+        })?;
+        debug!(pr_number, "updated PR title and body");
+        Ok(pr)
+    }
+
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        debug!(pr_number, "closing PR");
+        self.update_pr(pr_number, |pr| pr.state = LocalPrState::Closed)?;
+        debug!(pr_number, "closed PR");
+        Ok(())
+    }
+
+    async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
+        debug!(pr_number, "publishing PR");
+        let pr = self.update_pr(pr_number, |pr| pr.is_draft = false)?;
+        debug!(pr_number, "published PR");
+        Ok(pr)
+    }
+
+    async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<PrComment>> {
+        debug!(pr_number, "listing PR comments");
+        let result = self.read(|db| {
+            db.prs
+                .iter()
+                .find(|pr| pr.number == pr_number)
+                .map(|pr| {
+                    pr.comments
+                        .iter()
+                        .map(|c| PrComment {
+                            id: c.id,
+                            body: c.body.clone(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .ok_or_else(|| Error::LocalForge(format!("PR #{pr_number} not found")))
+        })??;
+        debug!(pr_number, count = result.len(), "listed PR comments");
+        Ok(result)
+    }
+
+    async fn create_pr_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        debug!(pr_number, "creating PR comment");
+        self.transact(|db| {
+            let id = db.next_comment_id;
+            find_pr_mut(db, pr_number)?.comments.push(LocalComment {
+                id,
+                body: body.to_string(),
+            });
+            db.next_comment_id += 1;
+            Ok(())
+        })?;
+        debug!(pr_number, "created PR comment");
+        Ok(())
+    }
+
+    async fn update_pr_comment(&self, pr_number: u64, comment_id: u64, body: &str) -> Result<()> {
+        debug!(pr_number, comment_id, "updating PR comment");
+        self.transact(|db| {
+            let comment = find_pr_mut(db, pr_number)?
+                .comments
+                .iter_mut()
+                .find(|c| c.id == comment_id)
+                .ok_or_else(|| {
+                    Error::LocalForge(format!("comment {comment_id} not found on PR #{pr_number}"))
+                })?;
+            comment.body = body.to_string();
+            Ok(())
+        })?;
+        debug!(pr_number, comment_id, "updated PR comment");
+        Ok(())
+    }
+
+    async fn get_pr_triage(&self, pr_number: u64) -> Result<PrTriage> {
+        self.read(|db| {
+            db.prs
+                .iter()
+                .find(|pr| pr.number == pr_number)
+                .map(|pr| pr.triage.clone())
+                .ok_or_else(|| Error::LocalForge(format!("PR #{pr_number} not found")))
+        })?
+    }
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()> {
+        debug!(pr_number, ?reviewers, "requesting reviewers");
+        self.add_triage(
+            pr_number,
+            &PrTriage {
+                reviewers: reviewers.to_vec(),
+                ..PrTriage::default()
+            },
+        )
+    }
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()> {
+        debug!(pr_number, ?assignees, "adding assignees");
+        self.add_triage(
+            pr_number,
+            &PrTriage {
+                assignees: assignees.to_vec(),
+                ..PrTriage::default()
+            },
+        )
+    }
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()> {
+        debug!(pr_number, ?labels, "adding labels");
+        self.add_triage(
+            pr_number,
+            &PrTriage {
+                labels: labels.to_vec(),
+                ..PrTriage::default()
+            },
+        )
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        // There are no accounts; the local user stands in
+        Ok(std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "local".to_string()))
+    }
+
+    fn config(&self) -> &PlatformConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_service(temp: &TempDir) -> LocalService {
+        LocalService::open(
+            temp.path(),
+            PlatformConfig {
+                platform: Platform::Local,
+                owner: "local".to_string(),
+                repo: "repo".to_string(),
+                host: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_local_forge_path() {
+        let temp = TempDir::new().unwrap();
+        assert!(local_forge_path(temp.path()).ends_with(".jj/repo/ryu/forge.json"));
+    }
+
+    #[tokio::test]
+    async fn test_pr_lifecycle_persists() {
+        let temp = TempDir::new().unwrap();
+        let service = make_service(&temp);
+
+        let first = service
+            .create_pr_with_options("feat-a", "main", "Add A", "Adds A.", true)
+            .await
+            .unwrap();
+        let second = service
+            .create_pr("feat-b", "feat-a", "Add B")
+            .await
+            .unwrap();
+        assert_eq!((first.number, second.number), (1, 2));
+        assert!(first.is_draft);
+        assert!(first.html_url.starts_with("file://"));
+        assert!(first.html_url.ends_with("#pr-1"));
+
+        service.update_pr_base(2, "main").await.unwrap();
+        service.publish_pr(1).await.unwrap();
+        service.create_pr_comment(2, "stack").await.unwrap();
+        let comment_id = service.list_pr_comments(2).await.unwrap()[0].id;
+        service
+            .update_pr_comment(2, comment_id, "updated")
+            .await
+            .unwrap();
+
+        // A fresh service sees everything through the file
+        let reopened = make_service(&temp);
+        let b = reopened.find_existing_pr("feat-b").await.unwrap().unwrap();
+        assert_eq!(b.base_ref, "main");
+        assert!(
+            !reopened
+                .find_existing_pr("feat-a")
+                .await
+                .unwrap()
+                .unwrap()
+                .is_draft
+        );
+        assert_eq!(
+            reopened.list_pr_comments(2).await.unwrap()[0].body,
+            "updated"
+        );
+        assert!(reopened.find_existing_pr("feat-c").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_triage_added_once() {
+        let temp = TempDir::new().unwrap();
+        let service = make_service(&temp);
+        service.create_pr("feat-a", "main", "Add A").await.unwrap();
+
+        let labels = ["stacked".to_string()];
+        service.add_labels(1, &labels).await.unwrap();
+        service.add_labels(1, &labels).await.unwrap();
+        service
+            .request_reviewers(1, &["alice".to_string()])
+            .await
+            .unwrap();
+
+        let triage = make_service(&temp).get_pr_triage(1).await.unwrap();
+        assert_eq!(triage.labels, labels);
+        assert_eq!(triage.reviewers, ["alice"]);
+        assert!(triage.assignees.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_closed_pr_is_no_longer_open() {
+        let temp = TempDir::new().unwrap();
+        let service = make_service(&temp);
+        service.create_pr("feat-a", "main", "Add A").await.unwrap();
+        service.close_pr(1).await.unwrap();
+
+        let reopened = make_service(&temp);
+        assert!(reopened.find_existing_pr("feat-a").await.unwrap().is_none());
+        let closed = reopened.find_closed_pr("feat-a").await.unwrap().unwrap();
+        assert_eq!(closed.pr.number, 1);
+        assert!(!closed.merged);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_open_pr_rejected() {
+        let temp = TempDir::new().unwrap();
+        let service = make_service(&temp);
+        service.create_pr("feat-a", "main", "Add A").await.unwrap();
+        assert!(service.create_pr("feat-a", "main", "Again").await.is_err());
+    }
+}
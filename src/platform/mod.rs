@@ -1,45 +1,97 @@
-//! Platform services for GitHub and GitLab
+//! Platform services for GitHub, GitLab, Gitea, Bitbucket Server, Azure DevOps,
+//! Gerrit and a local file-backed forge
 //!
 //! Provides a unified interface for PR/MR operations across platforms.
 
+mod azure_devops;
+mod bitbucket;
 mod detection;
 mod factory;
+mod gerrit;
+mod gitea;
 mod github;
 mod gitlab;
+mod local;
+mod retry;
 
-pub use detection::{detect_platform, parse_repo_info};
-pub use factory::create_platform_service;
+pub use azure_devops::AzureDevOpsService;
+pub use bitbucket::BitbucketService;
+pub use detection::{
+    PLATFORM_CONFIG_KEY, detect_platform, parse_repo_info, resolve_platform_config,
+};
+pub use factory::{create_platform_service, create_workspace_platform_service};
+pub use gerrit::GerritService;
+pub use gitea::GiteaService;
 pub use github::GitHubService;
 pub use gitlab::GitLabService;
+pub use local::{LOCAL_FORGE_VERSION, LocalService, local_forge_path};
+pub use retry::RetryPolicy;
 
-use crate::error::Result;
-use crate::types::{PlatformConfig, PrComment, PullRequest};
+use crate::error::{Error, Result};
+use crate::submit::ProgressCallback;
+use crate::types::{
+    Bookmark, ClosedPullRequest, MergeMethod, MergeQueueEntry, Mergeability, PlatformConfig,
+    PrComment, PrStatus, PrTriage, PullRequest,
+};
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Platform service trait for PR/MR operations
 ///
-/// This trait abstracts GitHub and GitLab operations, allowing the same
-/// submission logic to work with either platform.
+/// This trait abstracts GitHub, GitLab, Gitea, Bitbucket Server, Azure DevOps
+/// and Gerrit operations, allowing the same
+/// submission logic to work with any supported platform.
 #[async_trait]
 pub trait PlatformService: Send + Sync {
     /// Find an existing open PR for a head branch
     async fn find_existing_pr(&self, head_branch: &str) -> Result<Option<PullRequest>>;
 
-    /// Create a new PR with default options (non-draft).
+    /// Find existing open PRs for several head branches at once
+    ///
+    /// Returns a map from head branch to PR; branches without an open PR are
+    /// absent. The default looks each branch up with [`find_existing_pr`];
+    /// platforms override it to save round trips.
+    ///
+    /// [`find_existing_pr`]: Self::find_existing_pr
+    async fn find_existing_prs(
+        &self,
+        head_branches: &[&str],
+    ) -> Result<HashMap<String, PullRequest>> {
+        let mut prs = HashMap::new();
+        for head in head_branches {
+            if let Some(pr) = self.find_existing_pr(head).await? {
+                prs.insert((*head).to_string(), pr);
+            }
+        }
+        Ok(prs)
+    }
+
+    /// Find the most recently merged or closed PR for a head branch
+    ///
+    /// Sync uses this to notice bookmarks whose PRs landed, however they were
+    /// merged. The default returns `None`, so a platform that can't tell
+    /// never has its stacks rebased; every built-in platform can.
+    async fn find_closed_pr(&self, _head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        Ok(None)
+    }
+
+    /// Create a new PR with default options (empty body, non-draft).
     ///
     /// This is a convenience method that delegates to [`create_pr_with_options`]
-    /// with `draft: false`. Implementors should override `create_pr_with_options`,
-    /// not this method.
+    /// with an empty body and `draft: false`. Implementors should override
+    /// `create_pr_with_options`, not this method.
     ///
     /// [`create_pr_with_options`]: Self::create_pr_with_options
     async fn create_pr(&self, head: &str, base: &str, title: &str) -> Result<PullRequest> {
-        self.create_pr_with_options(head, base, title, false).await
+        self.create_pr_with_options(head, base, title, "", false)
+            .await
     }
 
-    /// Create a new PR with explicit draft option.
+    /// Create a new PR with an explicit body (Markdown) and draft option.
     ///
     /// Implementors must provide this method. The default [`create_pr`] method
-    /// delegates here with `draft: false`.
+    /// delegates here with an empty body and `draft: false`.
     ///
     /// [`create_pr`]: Self::create_pr
     async fn create_pr_with_options(
@@ -47,15 +99,105 @@ pub trait PlatformService: Send + Sync {
         head: &str,
         base: &str,
         title: &str,
+        body: &str,
         draft: bool,
     ) -> Result<PullRequest>;
 
     /// Update the base branch of an existing PR
     async fn update_pr_base(&self, pr_number: u64, new_base: &str) -> Result<PullRequest>;
 
+    /// Replace the title and body of an existing PR
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest>;
+
     /// Publish a draft PR (convert to ready for review)
     async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest>;
 
+    /// Close a PR without merging it
+    async fn close_pr(&self, _pr_number: u64) -> Result<()> {
+        Err(unsupported(self.config(), "closing PRs"))
+    }
+
+    /// A PR's state, reviews and CI status, whether it's open or not
+    ///
+    /// Only called when [`supports_pr_status`] is `true`.
+    ///
+    /// [`supports_pr_status`]: Self::supports_pr_status
+    async fn get_pr_status(&self, _pr_number: u64) -> Result<PrStatus> {
+        Err(unsupported(self.config(), "PR status"))
+    }
+
+    /// Reviewers, assignees and labels currently on a PR
+    ///
+    /// Reviewers include users who already reviewed, so asking for them again
+    /// doesn't re-request a review. The default reports nothing.
+    async fn get_pr_triage(&self, _pr_number: u64) -> Result<PrTriage> {
+        Ok(PrTriage::default())
+    }
+
+    /// Request reviews from users (and, on GitHub, `org/team` teams)
+    async fn request_reviewers(&self, _pr_number: u64, _reviewers: &[String]) -> Result<()> {
+        Err(unsupported(self.config(), "requesting reviewers"))
+    }
+
+    /// Add users to a PR's assignees
+    async fn add_assignees(&self, _pr_number: u64, _assignees: &[String]) -> Result<()> {
+        Err(unsupported(self.config(), "assignees"))
+    }
+
+    /// Add labels to a PR
+    async fn add_labels(&self, _pr_number: u64, _labels: &[String]) -> Result<()> {
+        Err(unsupported(self.config(), "labels"))
+    }
+
+    /// Username of the authenticated user, which `@me` stands for
+    async fn current_user(&self) -> Result<String> {
+        Err(unsupported(self.config(), "@me"))
+    }
+
+    /// Whether a PR is approved, green and free of conflicts
+    async fn get_mergeability(&self, _pr_number: u64) -> Result<Mergeability> {
+        Err(unsupported(self.config(), "checking mergeability"))
+    }
+
+    /// Merge a PR into its base
+    async fn merge_pr(&self, _pr_number: u64, _method: MergeMethod) -> Result<()> {
+        Err(unsupported(self.config(), "merging PRs"))
+    }
+
+    /// Method auto-merge will merge a PR with, if it's enabled
+    ///
+    /// Platforms without auto-merge return `None`.
+    async fn get_auto_merge(&self, _pr_number: u64) -> Result<Option<MergeMethod>> {
+        Ok(None)
+    }
+
+    /// Merge a PR as soon as its checks pass and it's approved
+    ///
+    /// GitHub auto-merge, or GitLab's merge when pipeline succeeds.
+    async fn enable_auto_merge(&self, _pr_number: u64, _method: MergeMethod) -> Result<()> {
+        Err(unsupported(self.config(), "auto-merge"))
+    }
+
+    /// A PR's entry in the base branch's merge queue, if it's queued
+    async fn get_merge_queue_entry(&self, _pr_number: u64) -> Result<Option<MergeQueueEntry>> {
+        Err(unsupported(self.config(), "merge queues"))
+    }
+
+    /// Add a PR to the base branch's merge queue
+    async fn enqueue_pr(&self, _pr_number: u64) -> Result<()> {
+        Err(unsupported(self.config(), "merge queues"))
+    }
+
+    /// Take a PR out of the merge queue
+    async fn dequeue_pr(&self, _pr_number: u64) -> Result<()> {
+        Err(unsupported(self.config(), "merge queues"))
+    }
+
     /// List comments on a PR
     async fn list_pr_comments(&self, pr_number: u64) -> Result<Vec<PrComment>>;
 
@@ -67,4 +209,75 @@ pub trait PlatformService: Send + Sync {
 
     /// Get the platform configuration
     fn config(&self) -> &PlatformConfig;
+
+    /// Report retry and rate-limit waits to `progress`
+    ///
+    /// GitHub and GitLab retry transient failures and wait out rate limits,
+    /// announcing each wait through [`ProgressCallback::on_message`]. Other
+    /// platforms ignore it.
+    fn set_retry_progress(&mut self, _progress: Arc<dyn ProgressCallback>) {}
+
+    /// Ref that reviews are created by pushing to, for change-based platforms
+    ///
+    /// Gerrit returns `refs/for/<base>`: every review targets trunk, stacking is
+    /// expressed through commit parents and the relation chain replaces the
+    /// stack comment. Branch-based platforms return `None` and push the
+    /// bookmark itself.
+    fn review_push_ref(&self, _base: &str) -> Option<String> {
+        None
+    }
+
+    /// Whether a PR can target another bookmark's branch, forming a stack
+    ///
+    /// When `false` (Gerrit, or PRs opened from a fork) every PR targets the
+    /// default branch and the stack comment alone records the order.
+    fn supports_stacked_bases(&self) -> bool {
+        true
+    }
+
+    /// Whether [`get_pr_status`] reports reviews and CI status
+    ///
+    /// When `false`, status falls back to whether each PR is open, merged or
+    /// closed.
+    ///
+    /// [`get_pr_status`]: Self::get_pr_status
+    fn supports_pr_status(&self) -> bool {
+        false
+    }
+
+    /// Open PRs from the branches of another repository (a fork) of this one
+    ///
+    /// `head` is the config of the push remote. Platforms without cross-repo
+    /// PRs only accept their own repository.
+    async fn set_head_repository(&mut self, head: &PlatformConfig) -> Result<()> {
+        let config = self.config();
+        if head.owner == config.owner && head.repo == config.repo && head.host == config.host {
+            return Ok(());
+        }
+        Err(Error::Platform(format!(
+            "{} does not support PRs from a fork ({}/{})",
+            config.platform, head.owner, head.repo
+        )))
+    }
+
+    /// Head identifier used to find and create the review for a bookmark
+    ///
+    /// Branch-based platforms use the bookmark name; Gerrit uses the
+    /// `Change-Id` trailer of the bookmark's commit, or the one derived from
+    /// its change.
+    fn review_head(&self, bookmark: &Bookmark) -> String {
+        bookmark.name.clone()
+    }
+
+    /// Tell a change-based platform the `Change-Id` trailers commits already
+    /// carry, by jj change ID
+    ///
+    /// Gerrit finds reviews by these rather than the derived IDs. Other
+    /// platforms ignore them.
+    fn set_change_id_trailers(&mut self, _trailers: HashMap<String, String>) {}
+}
+
+/// Error for an operation a platform doesn't support
+fn unsupported(config: &PlatformConfig, what: &str) -> Error {
+    Error::Platform(format!("{} does not support {what}", config.platform))
 }
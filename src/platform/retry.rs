@@ -0,0 +1,305 @@
+//! Retry, backoff and rate-limit handling for platform API calls
+//!
+//! Shared by the GitHub and GitLab services. A failed request is classified
+//! from its status code and headers; transient failures are retried with
+//! bounded exponential backoff, and rate limits wait for the time the server
+//! asks for (`Retry-After`, `X-RateLimit-Reset`, GitLab's `RateLimit-*`).
+
+use crate::submit::ProgressCallback;
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Limits for retrying failed platform requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt before giving up
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubled for each further retry
+    pub base_delay: Duration,
+    /// Upper bound for exponential backoff
+    pub max_backoff: Duration,
+    /// Longest rate-limit wait honored; longer resets fail immediately
+    pub max_rate_limit_wait: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 4,
+            base_delay: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            max_rate_limit_wait: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Backoff before retry number `retry` (0-based)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+/// How a failed request may be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryClass {
+    /// Not retryable (client error, or unsafe to repeat)
+    Fatal,
+    /// Transient failure (5xx, connection error): retry with backoff
+    Transient,
+    /// Rate limited: retry after the given wait, or with backoff if unknown
+    RateLimited(Option<Duration>),
+}
+
+/// Classify a failed response
+///
+/// Server errors are only retried for `idempotent` requests, since a
+/// create that timed out at a proxy may still have happened. Rate-limited
+/// requests were rejected outright and are always safe to repeat.
+pub fn classify_response(status: StatusCode, headers: &HeaderMap, idempotent: bool) -> RetryClass {
+    let exhausted = ["x-ratelimit-remaining", "ratelimit-remaining"]
+        .iter()
+        .any(|name| header_str(headers, name) == Some("0"));
+
+    if status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN
+            && (exhausted || headers.contains_key(reqwest::header::RETRY_AFTER)))
+    {
+        return RetryClass::RateLimited(rate_limit_wait(headers, Utc::now()));
+    }
+
+    match status {
+        StatusCode::INTERNAL_SERVER_ERROR
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT
+            if idempotent =>
+        {
+            RetryClass::Transient
+        }
+        _ => RetryClass::Fatal,
+    }
+}
+
+/// Classify a request that failed before a response arrived
+pub fn classify_transport_error(error: &reqwest::Error, idempotent: bool) -> RetryClass {
+    // A refused connection never reached the server
+    if error.is_connect() || (idempotent && (error.is_timeout() || error.is_request())) {
+        RetryClass::Transient
+    } else {
+        RetryClass::Fatal
+    }
+}
+
+/// Wait requested by rate-limit headers, relative to `now`
+///
+/// `Retry-After` (seconds or an HTTP date) wins; otherwise the reset time
+/// from `X-RateLimit-Reset` (GitHub) or `RateLimit-Reset` /
+/// `RateLimit-ResetTime` (GitLab) is used.
+pub fn rate_limit_wait(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    if let Some(value) = header_str(headers, "retry-after") {
+        if let Ok(secs) = value.trim().parse::<u64>() {
+            return Some(Duration::from_secs(secs));
+        }
+        if let Ok(at) = DateTime::parse_from_rfc2822(value.trim()) {
+            return Some(until(at.with_timezone(&Utc), now));
+        }
+    }
+
+    let reset_epoch = ["x-ratelimit-reset", "ratelimit-reset"]
+        .iter()
+        .find_map(|name| header_str(headers, name)?.trim().parse::<i64>().ok())
+        .and_then(|secs| DateTime::from_timestamp(secs, 0));
+    let reset_time = || {
+        header_str(headers, "ratelimit-resettime")
+            .and_then(|value| DateTime::parse_from_rfc2822(value.trim()).ok())
+            .map(|at| at.with_timezone(&Utc))
+    };
+
+    reset_epoch.or_else(reset_time).map(|at| until(at, now))
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn until(at: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    (at - now).to_std().unwrap_or_default()
+}
+
+/// Retry state shared by a platform service's requests
+#[derive(Default)]
+pub struct Retrier {
+    policy: RetryPolicy,
+    progress: Option<Arc<dyn ProgressCallback>>,
+}
+
+impl Retrier {
+    /// Replace the retry limits
+    pub const fn set_policy(&mut self, policy: RetryPolicy) {
+        self.policy = policy;
+    }
+
+    /// Report waits to `progress`
+    pub fn set_progress(&mut self, progress: Arc<dyn ProgressCallback>) {
+        self.progress = Some(progress);
+    }
+
+    /// Wait before retrying a failed request
+    ///
+    /// `retry` counts the retries made so far and is incremented. Returns
+    /// `false` when the failure is fatal or the retries are used up, in which
+    /// case the caller should return the error.
+    pub async fn wait(&self, platform: &str, class: RetryClass, retry: &mut u32) -> bool {
+        if *retry >= self.policy.max_retries {
+            return false;
+        }
+
+        let (delay, reason) = match class {
+            RetryClass::Fatal => return false,
+            RetryClass::Transient => (self.policy.backoff(*retry), "request failed"),
+            RetryClass::RateLimited(Some(wait)) if wait > self.policy.max_rate_limit_wait => {
+                debug!(?wait, "rate limit resets too late; giving up");
+                return false;
+            }
+            // Allow a little slack past the reset for clock skew
+            RetryClass::RateLimited(Some(wait)) => (wait + Duration::from_secs(1), "rate limited"),
+            RetryClass::RateLimited(None) => (self.policy.backoff(*retry), "rate limited"),
+        };
+
+        *retry += 1;
+        debug!(platform, ?delay, retry = *retry, reason, "retrying request");
+        if let Some(progress) = &self.progress {
+            progress
+                .on_message(&format!(
+                    "{platform} {reason}; retrying in {}s ({}/{})",
+                    delay.as_secs().max(1),
+                    *retry,
+                    self.policy.max_retries
+                ))
+                .await;
+        }
+        tokio::time::sleep(delay).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_classify_server_errors_only_when_idempotent() {
+        let none = HeaderMap::new();
+        assert_eq!(
+            classify_response(StatusCode::BAD_GATEWAY, &none, true),
+            RetryClass::Transient
+        );
+        assert_eq!(
+            classify_response(StatusCode::BAD_GATEWAY, &none, false),
+            RetryClass::Fatal
+        );
+        assert_eq!(
+            classify_response(StatusCode::NOT_FOUND, &none, true),
+            RetryClass::Fatal
+        );
+        // A plain 403 is a permission problem, not a rate limit
+        assert_eq!(
+            classify_response(StatusCode::FORBIDDEN, &none, true),
+            RetryClass::Fatal
+        );
+    }
+
+    #[test]
+    fn test_classify_rate_limits() {
+        let retry_after = headers(&[("retry-after", "30")]);
+        assert_eq!(
+            classify_response(StatusCode::FORBIDDEN, &retry_after, false),
+            RetryClass::RateLimited(Some(Duration::from_secs(30)))
+        );
+        assert_eq!(
+            classify_response(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), false),
+            RetryClass::RateLimited(None)
+        );
+        let exhausted = headers(&[("x-ratelimit-remaining", "0")]);
+        assert!(matches!(
+            classify_response(StatusCode::FORBIDDEN, &exhausted, true),
+            RetryClass::RateLimited(_)
+        ));
+    }
+
+    #[test]
+    fn test_rate_limit_wait_headers() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let github = headers(&[("x-ratelimit-reset", "1700000042")]);
+        assert_eq!(rate_limit_wait(&github, now), Some(Duration::from_secs(42)));
+
+        let gitlab = headers(&[("ratelimit-reset", "1700000010")]);
+        assert_eq!(rate_limit_wait(&gitlab, now), Some(Duration::from_secs(10)));
+
+        let gitlab_time = headers(&[("ratelimit-resettime", "Tue, 14 Nov 2023 22:13:27 GMT")]);
+        assert_eq!(
+            rate_limit_wait(&gitlab_time, now),
+            Some(Duration::from_secs(7))
+        );
+
+        let http_date = headers(&[("retry-after", "Tue, 14 Nov 2023 22:13:25 GMT")]);
+        assert_eq!(
+            rate_limit_wait(&http_date, now),
+            Some(Duration::from_secs(5))
+        );
+
+        // Resets in the past mean retry now
+        let past = headers(&[("x-ratelimit-reset", "1699999000")]);
+        assert_eq!(rate_limit_wait(&past, now), Some(Duration::ZERO));
+
+        assert_eq!(rate_limit_wait(&HeaderMap::new(), now), None);
+    }
+
+    #[test]
+    fn test_backoff_is_bounded() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(8));
+        assert_eq!(policy.backoff(10), policy.max_backoff);
+        assert_eq!(policy.backoff(u32::MAX), policy.max_backoff);
+    }
+
+    #[tokio::test]
+    async fn test_retrier_gives_up() {
+        let mut retrier = Retrier::default();
+        retrier.set_policy(RetryPolicy {
+            base_delay: Duration::ZERO,
+            ..RetryPolicy::default()
+        });
+        let mut retry = 0;
+
+        assert!(!retrier.wait("GitHub", RetryClass::Fatal, &mut retry).await);
+        assert_eq!(retry, 0);
+
+        let too_long = RetryClass::RateLimited(Some(Duration::from_secs(3600)));
+        assert!(!retrier.wait("GitHub", too_long, &mut retry).await);
+
+        while retrier
+            .wait("GitHub", RetryClass::Transient, &mut retry)
+            .await
+        {}
+        assert_eq!(retry, RetryPolicy::default().max_retries);
+    }
+}
@@ -0,0 +1,155 @@
+//! Gerrit `Change-Id` trailers derived from jj change IDs
+
+/// Trailer key Gerrit uses to associate commits with reviews
+pub const CHANGE_ID_TRAILER: &str = "Change-Id";
+
+/// Padding that fills a 32-hex-digit jj change ID to Gerrit's 40 digits
+///
+/// Matches `jj gerrit upload`, so trailers written by either tool agree.
+const CHANGE_ID_PADDING: &str = "6a6a6964";
+
+/// Derive the Gerrit `Change-Id` for a jj change ID (hex)
+pub fn gerrit_change_id(change_id_hex: &str) -> String {
+    format!("I{CHANGE_ID_PADDING}{change_id_hex}")
+}
+
+/// Whether a `Change-Id` trailer value is one Gerrit accepts: `I` and 40
+/// hex digits
+pub fn is_gerrit_change_id(value: &str) -> bool {
+    value
+        .strip_prefix('I')
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Find the `Change-Id` trailer value in a commit description, if any
+pub fn find_change_id_trailer(description: &str) -> Option<&str> {
+    let prefix = format!("{CHANGE_ID_TRAILER}:");
+    last_paragraph(description)
+        .lines()
+        .rev()
+        .find_map(|line| line.strip_prefix(prefix.as_str()))
+        .map(str::trim)
+}
+
+/// Append a `Change-Id` trailer to a commit description
+///
+/// The trailer joins an existing trailer block, otherwise it starts a new
+/// paragraph after the message body.
+pub fn append_change_id_trailer(description: &str, change_id: &str) -> String {
+    let body = description.trim_end();
+    let trailer = format!("{CHANGE_ID_TRAILER}: {change_id}");
+
+    let separator = if is_trailer_block(last_paragraph(body)) && body.contains("\n\n") {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{body}{separator}{trailer}\n")
+}
+
+/// Replace the value of the `Change-Id` trailer in a commit description
+///
+/// Descriptions without one are returned unchanged.
+pub fn replace_change_id_trailer(description: &str, change_id: &str) -> String {
+    let body = description.trim_end();
+    let (head, last) = body.rsplit_once("\n\n").unwrap_or(("", body));
+    let prefix = format!("{CHANGE_ID_TRAILER}:");
+    let last: Vec<String> = last
+        .lines()
+        .map(|line| {
+            if line.starts_with(prefix.as_str()) {
+                format!("{CHANGE_ID_TRAILER}: {change_id}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    let last = last.join("\n");
+    if head.is_empty() {
+        format!("{last}\n")
+    } else {
+        format!("{head}\n\n{last}\n")
+    }
+}
+
+fn last_paragraph(description: &str) -> &str {
+    let trimmed = description.trim_end();
+    trimmed.rsplit("\n\n").next().unwrap_or(trimmed)
+}
+
+/// Whether every line of a paragraph looks like a `Key: value` trailer
+fn is_trailer_block(paragraph: &str) -> bool {
+    !paragraph.is_empty()
+        && paragraph.lines().all(|line| {
+            line.split_once(": ").is_some_and(|(key, _)| {
+                !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gerrit_change_id_is_40_hex_digits() {
+        let id = gerrit_change_id("0123456789abcdef0123456789abcdef");
+        assert_eq!(id, "I6a6a69640123456789abcdef0123456789abcdef");
+        assert_eq!(id.len(), 41);
+    }
+
+    #[test]
+    fn test_is_gerrit_change_id() {
+        assert!(is_gerrit_change_id(
+            "I0123456789abcdef0123456789abcdef01234567"
+        ));
+        assert!(!is_gerrit_change_id("Iabc"));
+        assert!(!is_gerrit_change_id(
+            "0123456789abcdef0123456789abcdef01234567"
+        ));
+        assert!(!is_gerrit_change_id(
+            "I0123456789abcdef0123456789abcdef0123456z"
+        ));
+    }
+
+    #[test]
+    fn test_append_change_id_trailer() {
+        assert_eq!(
+            append_change_id_trailer("feat: add auth\n", "Iabc"),
+            "feat: add auth\n\nChange-Id: Iabc\n"
+        );
+        // Joins an existing trailer block
+        assert_eq!(
+            append_change_id_trailer("feat: add auth\n\nSigned-off-by: A <a@b>\n", "Iabc"),
+            "feat: add auth\n\nSigned-off-by: A <a@b>\nChange-Id: Iabc\n"
+        );
+        // A subject that looks like a trailer is not a trailer block
+        assert_eq!(
+            append_change_id_trailer("fix: crash", "Iabc"),
+            "fix: crash\n\nChange-Id: Iabc\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_change_id_trailer() {
+        assert_eq!(
+            replace_change_id_trailer(
+                "feat: add auth\n\nBody.\n\nChange-Id: Ihook\nSigned-off-by: A <a@b>\n",
+                "Iabc"
+            ),
+            "feat: add auth\n\nBody.\n\nChange-Id: Iabc\nSigned-off-by: A <a@b>\n"
+        );
+    }
+
+    #[test]
+    fn test_find_change_id_trailer() {
+        let desc = "feat: add auth\n\nBody text.\n\nChange-Id: Iabc\n";
+        assert_eq!(find_change_id_trailer(desc), Some("Iabc"));
+        assert_eq!(find_change_id_trailer("feat: add auth\n"), None);
+        // Only the trailer block counts
+        assert_eq!(
+            find_change_id_trailer("Change-Id: Iabc\n\nmore body\n"),
+            None
+        );
+    }
+}
@@ -2,6 +2,11 @@
 //!
 //! Provides a high-level interface to jj repository operations.
 
+mod change_id;
 mod workspace;
 
+pub use change_id::{
+    CHANGE_ID_TRAILER, append_change_id_trailer, find_change_id_trailer, gerrit_change_id,
+    is_gerrit_change_id, replace_change_id_trailer,
+};
 pub use workspace::{JjWorkspace, select_remote};
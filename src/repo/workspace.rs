@@ -1,28 +1,43 @@
 //! `JjWorkspace` - wrapper around jj-lib for repository operations
 
 use crate::error::{Error, Result};
+use crate::repo::change_id::{
+    append_change_id_trailer, find_change_id_trailer, gerrit_change_id, is_gerrit_change_id,
+    replace_change_id_trailer,
+};
 use crate::types::{Bookmark, GitRemote, LogEntry};
 use chrono::{DateTime, TimeZone, Utc};
-use jj_lib::backend::Timestamp;
+use futures::StreamExt as _;
+use jj_lib::backend::{CommitId, Timestamp, TreeValue};
 use jj_lib::commit::Commit;
 use jj_lib::config::{ConfigLayer, ConfigSource, StackedConfig};
+use jj_lib::fileset::{self, FilesetDiagnostics};
 use jj_lib::git::{
     self, GitFetch, GitImportOptions, GitRefUpdate, GitSettings, RemoteCallbacks,
     expand_fetch_refspecs,
 };
+use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::matchers::{EverythingMatcher, Matcher, NothingMatcher};
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId;
-use jj_lib::op_store::{RemoteRef, RemoteRefState};
-use jj_lib::ref_name::{RefName, RemoteName};
-use jj_lib::repo::{Repo, StoreFactories};
-use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::op_store::{RefTarget, RemoteRef, RemoteRefState};
+use jj_lib::ref_name::{RefName, RemoteName, RemoteNameBuf};
+use jj_lib::repo::{ReadonlyRepo, Repo, StoreFactories};
+use jj_lib::repo_path::{RepoPathBuf, RepoPathUiConverter};
 use jj_lib::revset::{
     self, RevsetExtensions, RevsetParseContext, RevsetWorkspaceContext, SymbolResolver,
 };
-use jj_lib::settings::UserSettings;
+use jj_lib::rewrite::{EmptyBehavior, RebaseOptions, RewriteRefsOptions};
+use jj_lib::settings::{HumanByteSize, UserSettings};
 use jj_lib::str_util::{StringExpression, StringMatcher, StringPattern};
+use jj_lib::transaction::Transaction;
+use jj_lib::working_copy::{SnapshotOptions, WorkingCopyFreshness};
 use jj_lib::workspace::{Workspace, default_working_copy_factories};
-use std::path::Path;
+use pollster::FutureExt as _;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio::io::AsyncReadExt as _;
 
 /// Wrapper around jj-lib workspace and repository
 pub struct JjWorkspace {
@@ -57,6 +72,57 @@ fn create_user_settings() -> Result<UserSettings> {
         .map_err(|e| Error::Config(format!("Failed to create settings: {e}")))
 }
 
+/// Layer the repo's own config (`jj config set --repo`) over user settings
+fn with_repo_config(settings: UserSettings, repo_path: &Path) -> Result<UserSettings> {
+    let repo_config = repo_path.join("config.toml");
+    if !repo_config.is_file() {
+        return Ok(settings);
+    }
+
+    let mut config = settings.config().clone();
+    config
+        .load_file(ConfigSource::Repo, &repo_config)
+        .map_err(|e| Error::Config(format!("Failed to load repo config: {e}")))?;
+    UserSettings::from_config(config)
+        .map_err(|e| Error::Config(format!("Failed to create settings: {e}")))
+}
+
+/// Largest untracked file a snapshot starts tracking, as in `jj`'s default
+/// `snapshot.max-new-file-size`
+const DEFAULT_MAX_NEW_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Ignore patterns from outside the workspace: git's `core.excludesFile`
+/// and the backing repo's `info/exclude`
+///
+/// `.gitignore` files in the workspace are read while snapshotting.
+fn base_ignores(repo: &ReadonlyRepo) -> Result<Arc<GitIgnoreFile>> {
+    let ignores = GitIgnoreFile::empty();
+    let Ok(git_repo) = git::get_git_repo(repo.store()) else {
+        return Ok(ignores);
+    };
+    let excludes_file = git_repo
+        .config_snapshot()
+        .trusted_path("core.excludesFile")
+        .and_then(std::result::Result::ok)
+        .map(std::borrow::Cow::into_owned)
+        .or_else(|| {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+                .map(|dir| dir.join("git").join("ignore"))
+        });
+    let chain = |ignores: Arc<GitIgnoreFile>, file| {
+        ignores
+            .chain_with_file("", file)
+            .map_err(|e| Error::Workspace(format!("Failed to read ignore file: {e}")))
+    };
+    let ignores = match excludes_file {
+        Some(file) => chain(ignores, file)?,
+        None => ignores,
+    };
+    chain(ignores, git_repo.common_dir().join("info").join("exclude"))
+}
+
 /// Finds the nearest workspace root by walking up the directory tree.
 fn find_workspace_dir(path: &Path) -> &Path {
     path.ancestors()
@@ -77,6 +143,7 @@ impl JjWorkspace {
             &default_working_copy_factories(),
         )
         .map_err(|e| Error::Workspace(format!("Failed to open workspace: {e}")))?;
+        let settings = with_repo_config(settings, workspace.repo_path())?;
 
         Ok(Self {
             workspace,
@@ -92,6 +159,12 @@ impl JjWorkspace {
             .map_err(|e| Error::Workspace(format!("Failed to load repo: {e}")))
     }
 
+    /// Read a string setting from jj config (user or repo level), e.g. `ryu.platform`
+    pub fn config_string(&self, key: &str) -> Option<String> {
+        let path: Vec<&str> = key.split('.').collect();
+        self.settings.get_string(path.as_slice()).ok()
+    }
+
     /// Get git settings from user settings
     fn git_settings(&self) -> Result<GitSettings> {
         GitSettings::from_settings(&self.settings)
@@ -407,6 +480,7 @@ impl JjWorkspace {
             author_name: author.name.clone(),
             author_email: author.email.clone(),
             description_first_line,
+            description: description.to_string(),
             parents,
             local_bookmarks,
             remote_bookmarks,
@@ -569,6 +643,472 @@ impl JjWorkspace {
         Ok(())
     }
 
+    /// Push a bookmark's commit to a review ref (e.g. Gerrit's `refs/for/<branch>`)
+    ///
+    /// Unlike [`git_push`](Self::git_push) this creates no remote bookmark. A push
+    /// the server rejects as "no new changes" counts as success, since the review
+    /// is already up to date.
+    pub fn git_push_for_review(
+        &mut self,
+        bookmark: &str,
+        remote: &str,
+        target_ref: &str,
+    ) -> Result<()> {
+        let repo = self.repo()?;
+        let git_settings = self.git_settings()?;
+
+        let target = repo.view().get_local_bookmark(RefName::new(bookmark));
+        let commit_id = target
+            .as_normal()
+            .cloned()
+            .ok_or_else(|| Error::BookmarkNotFound(bookmark.to_string()))?;
+
+        // Review refs are virtual, so the lease expects them not to exist
+        let update = GitRefUpdate {
+            qualified_name: target_ref.into(),
+            expected_current_target: None,
+            new_target: Some(commit_id),
+        };
+
+        let stats = git::push_updates(
+            repo.as_ref(),
+            git_settings.to_subprocess_options(),
+            RemoteName::new(remote),
+            &[update],
+            RemoteCallbacks::default(),
+        )
+        .map_err(|e| Error::Git(format!("Failed to push: {e}")))?;
+
+        let rejection = stats
+            .rejected
+            .iter()
+            .chain(&stats.remote_rejected)
+            .find(|(_, reason)| {
+                !reason
+                    .as_deref()
+                    .is_some_and(|r| r.contains("no new changes"))
+            });
+        if let Some((_, reason)) = rejection {
+            return Err(Error::Git(format!(
+                "{target_ref} rejected: {}",
+                reason.as_deref().unwrap_or("no reason given")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Give commits in a revset the Gerrit `Change-Id` trailer of their change
+    ///
+    /// Trailers are derived from each commit's jj change ID and persisted by
+    /// rewriting the commit description; descendants (and bookmarks) are rebased
+    /// onto the rewritten commits. A well-formed trailer already there, e.g.
+    /// from a commit-msg hook, is kept, as `jj gerrit upload` does, since
+    /// Gerrit may already have a review under it; a malformed one is replaced.
+    /// Fails if a commit under a bookmark has no description to carry the
+    /// trailer; others without one (such as the working copy) are left alone.
+    /// Returns the number of commits rewritten.
+    pub fn add_change_id_trailers(&mut self, revset: &str) -> Result<usize> {
+        let repo = self.snapshot_working_copy()?;
+        let entries = self.resolve_revset(revset)?;
+        let pushed: HashSet<String> = self
+            .resolve_revset(&format!("({revset}) & ::bookmarks()"))?
+            .into_iter()
+            .map(|c| c.commit_id)
+            .collect();
+        let mut tx = repo.start_transaction();
+        let mut rewritten = 0;
+
+        for entry in entries {
+            let commit_id = CommitId::try_from_hex(&entry.commit_id).ok_or_else(|| {
+                Error::Internal(format!("invalid commit id: {}", entry.commit_id))
+            })?;
+            let commit = repo
+                .store()
+                .get_commit(&commit_id)
+                .map_err(|e| Error::Workspace(format!("Failed to get commit: {e}")))?;
+
+            let description = commit.description();
+            if description.trim().is_empty() {
+                if pushed.contains(&entry.commit_id) {
+                    return Err(Error::Workspace(format!(
+                        "commit {} has no description for its Change-Id trailer; \
+                         describe it with 'jj describe' before pushing to Gerrit",
+                        &entry.commit_id[..entry.commit_id.len().min(12)]
+                    )));
+                }
+                continue;
+            }
+
+            let change_id = gerrit_change_id(&commit.change_id().hex());
+            let description = match find_change_id_trailer(description) {
+                Some(existing) if is_gerrit_change_id(existing) => continue,
+                Some(_) => replace_change_id_trailer(description, &change_id),
+                None => append_change_id_trailer(description, &change_id),
+            };
+            tx.repo_mut()
+                .rewrite_commit(&commit)
+                .set_description(description)
+                .write()
+                .map_err(|e| Error::Workspace(format!("Failed to rewrite commit: {e}")))?;
+            rewritten += 1;
+        }
+
+        if rewritten > 0 {
+            tx.repo_mut()
+                .rebase_descendants()
+                .map_err(|e| Error::Workspace(format!("Failed to rebase descendants: {e}")))?;
+            self.commit_rewrite(&repo, tx, "add Change-Id trailers")?;
+        }
+
+        Ok(rewritten)
+    }
+
+    /// Well-formed `Change-Id` trailers of the commits in a revset, by jj
+    /// change ID
+    ///
+    /// Commits without one are left out.
+    pub fn change_id_trailers(&self, revset: &str) -> Result<HashMap<String, String>> {
+        Ok(self
+            .resolve_revset(revset)?
+            .into_iter()
+            .filter_map(|entry| {
+                let trailer = find_change_id_trailer(&entry.description)
+                    .filter(|t| is_gerrit_change_id(t))?
+                    .to_string();
+                Some((entry.change_id, trailer))
+            })
+            .collect())
+    }
+
+    /// Create local bookmarks, each given as a name and the commit it points to
+    ///
+    /// Fails without creating any if one of the names is already taken.
+    pub fn create_bookmarks(&mut self, bookmarks: &[(String, String)]) -> Result<()> {
+        if bookmarks.is_empty() {
+            return Ok(());
+        }
+        let repo = self.repo()?;
+        let mut tx = repo.start_transaction();
+
+        for (name, commit_id) in bookmarks {
+            let ref_name = RefName::new(name);
+            if tx.repo().view().get_local_bookmark(ref_name).is_present() {
+                return Err(Error::Workspace(format!(
+                    "Bookmark '{name}' already exists"
+                )));
+            }
+            tx.repo_mut().set_local_bookmark_target(
+                ref_name,
+                RefTarget::normal(parse_commit_id(commit_id)?),
+            );
+        }
+
+        self.commit_rewrite(&repo, tx, "create bookmarks")
+    }
+
+    /// Forget local bookmarks: delete them and untrack them on every remote
+    ///
+    /// The remote branches are left alone. Names that don't exist are skipped.
+    pub fn forget_bookmarks(&mut self, bookmarks: &[String]) -> Result<()> {
+        if bookmarks.is_empty() {
+            return Ok(());
+        }
+        let repo = self.repo()?;
+        let mut tx = repo.start_transaction();
+        for name in bookmarks {
+            forget_bookmark(&mut tx, name);
+        }
+        self.commit_rewrite(&repo, tx, "forget bookmarks")
+    }
+
+    /// Drop landed commits from the stack and rebase the rest onto trunk
+    ///
+    /// Used by sync once PRs at the bottom of the stack have merged. Each
+    /// commit in `commit_ids` is abandoned with `trunk()` as its new parent,
+    /// so its descendants are rebased onto trunk whether the PRs were merged,
+    /// squashed or rebased. Descendants that become empty (their changes are
+    /// already in trunk) are abandoned too. Commits that are now in trunk's
+    /// history (merged with a merge commit or fast-forwarded) are left alone.
+    /// `bookmarks` are forgotten: deleted locally and untracked on every
+    /// remote. Edits in the working copy are snapshotted first, so they're
+    /// rebased with it. Returns the number of commits rebased.
+    pub fn drop_landed_commits(
+        &mut self,
+        commit_ids: &[String],
+        bookmarks: &[String],
+    ) -> Result<usize> {
+        let repo = self.snapshot_working_copy()?;
+        let trunk = self
+            .resolve_revset("trunk()")?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Workspace("trunk() resolved to no commits".to_string()))?;
+        let trunk_id = parse_commit_id(&trunk.commit_id)?;
+        let above_trunk: HashSet<String> = self
+            .resolve_revset("trunk()..")?
+            .into_iter()
+            .map(|c| c.commit_id)
+            .collect();
+
+        let mut tx = repo.start_transaction();
+
+        for commit_id in commit_ids.iter().filter(|id| above_trunk.contains(*id)) {
+            tx.repo_mut().record_abandoned_commit_with_parents(
+                parse_commit_id(commit_id)?,
+                [trunk_id.clone()],
+            );
+        }
+
+        for name in bookmarks {
+            forget_bookmark(&mut tx, name);
+        }
+
+        let options = RebaseOptions {
+            empty: EmptyBehavior::AbandonNewlyEmpty,
+            rewrite_refs: RewriteRefsOptions {
+                delete_abandoned_bookmarks: true,
+            },
+            simplify_ancestor_merge: false,
+        };
+        let mut rebased = 0;
+        tx.repo_mut()
+            .rebase_descendants_with_options(&options, |_, _| rebased += 1)
+            .map_err(|e| Error::Workspace(format!("Failed to rebase descendants: {e}")))?;
+
+        self.commit_rewrite(&repo, tx, "drop landed changes")?;
+        Ok(rebased)
+    }
+
+    /// Record edits in the working copy into its commit, as `jj` does before
+    /// every command, and return the repo with them
+    ///
+    /// Rewriting the working-copy commit checks the new one out, which would
+    /// otherwise overwrite edits jj hasn't snapshotted yet.
+    fn snapshot_working_copy(&mut self) -> Result<Arc<ReadonlyRepo>> {
+        let repo = self.repo()?;
+        let workspace_name = self.workspace.workspace_name().to_owned();
+        let Some(wc_commit_id) = repo.view().get_wc_commit_id(&workspace_name) else {
+            return Ok(repo);
+        };
+        let wc_commit = repo
+            .store()
+            .get_commit(wc_commit_id)
+            .map_err(|e| Error::Workspace(format!("Failed to get commit: {e}")))?;
+
+        let base_ignores = base_ignores(&repo)?;
+        let auto_track = self.auto_track_matcher()?;
+        let max_new_file_size = self
+            .settings
+            .get_value_with(["snapshot", "max-new-file-size"], HumanByteSize::try_from)
+            .map_or(DEFAULT_MAX_NEW_FILE_SIZE, |size| size.0);
+        let options = SnapshotOptions {
+            base_ignores,
+            progress: None,
+            start_tracking_matcher: auto_track.as_ref(),
+            force_tracking_matcher: &NothingMatcher,
+            max_new_file_size,
+        };
+
+        let mut locked_ws = self
+            .workspace
+            .start_working_copy_mutation()
+            .map_err(|e| Error::Workspace(format!("Failed to lock working copy: {e}")))?;
+        let freshness = WorkingCopyFreshness::check_stale(locked_ws.locked_wc(), &wc_commit, &repo)
+            .map_err(|e| Error::Workspace(format!("Failed to check working copy: {e}")))?;
+        if freshness != WorkingCopyFreshness::Fresh {
+            return Err(Error::Workspace(
+                "the working copy is stale; run 'jj workspace update-stale' first".to_string(),
+            ));
+        }
+        let (tree, _) = locked_ws
+            .locked_wc()
+            .snapshot(&options)
+            .block_on()
+            .map_err(|e| Error::Workspace(format!("Failed to snapshot working copy: {e}")))?;
+
+        let repo = if tree.tree_ids_and_labels() == wc_commit.tree().tree_ids_and_labels() {
+            repo
+        } else {
+            let mut tx = repo.start_transaction();
+            tx.repo_mut()
+                .rewrite_commit(&wc_commit)
+                .set_tree(tree)
+                .write()
+                .map_err(|e| Error::Workspace(format!("Failed to rewrite commit: {e}")))?;
+            tx.repo_mut()
+                .rebase_descendants()
+                .map_err(|e| Error::Workspace(format!("Failed to rebase descendants: {e}")))?;
+            tx.commit("snapshot working copy")
+                .map_err(|e| Error::Workspace(format!("Failed to commit transaction: {e}")))?
+        };
+        locked_ws
+            .finish(repo.op_id().clone())
+            .map_err(|e| Error::Workspace(format!("Failed to update working copy: {e}")))?;
+        Ok(repo)
+    }
+
+    /// Files `jj` starts tracking when they appear, per `snapshot.auto-track`
+    fn auto_track_matcher(&self) -> Result<Box<dyn Matcher>> {
+        let pattern = self
+            .settings
+            .get_string(["snapshot", "auto-track"])
+            .unwrap_or_else(|_| "all()".to_string());
+        let workspace_root = self.workspace.workspace_root().to_path_buf();
+        let path_converter = RepoPathUiConverter::Fs {
+            cwd: workspace_root.clone(),
+            base: workspace_root,
+        };
+        let expression = fileset::parse(&mut FilesetDiagnostics::new(), &pattern, &path_converter)
+            .map_err(|e| Error::Config(format!("Invalid snapshot.auto-track: {e}")))?;
+        Ok(expression.to_matcher())
+    }
+
+    /// Commit a transaction that rewrote commits, updating the working copy
+    /// if its commit was rewritten
+    ///
+    /// `old_repo` should come from [`Self::snapshot_working_copy`], so that
+    /// edits made to the working copy since are detected rather than lost.
+    fn commit_rewrite(
+        &mut self,
+        old_repo: &Arc<ReadonlyRepo>,
+        tx: Transaction,
+        description: &str,
+    ) -> Result<()> {
+        let new_repo = tx
+            .commit(description)
+            .map_err(|e| Error::Workspace(format!("Failed to commit transaction: {e}")))?;
+
+        let workspace_name = self.workspace.workspace_name().to_owned();
+        let old_wc = old_repo.view().get_wc_commit_id(&workspace_name);
+        let new_wc = new_repo.view().get_wc_commit_id(&workspace_name);
+
+        if let Some(new_wc) = new_wc
+            && old_wc != Some(new_wc)
+        {
+            let old_tree = old_wc
+                .map(|id| old_repo.store().get_commit(id))
+                .transpose()
+                .map_err(|e| Error::Workspace(format!("Failed to get commit: {e}")))?
+                .map(|commit| commit.tree());
+            let commit = new_repo
+                .store()
+                .get_commit(new_wc)
+                .map_err(|e| Error::Workspace(format!("Failed to get commit: {e}")))?;
+            self.workspace
+                .check_out(new_repo.op_id().clone(), old_tree.as_ref(), &commit)
+                .map_err(|e| Error::Workspace(format!("Failed to update working copy: {e}")))?;
+        }
+
+        Ok(())
+    }
+
+    /// Tree of the `trunk()` commit
+    fn trunk_tree(&self) -> Result<(Arc<ReadonlyRepo>, MergedTree)> {
+        let trunk = self
+            .resolve_revset("trunk()")?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Revset("trunk() resolved to no commit".to_string()))?;
+
+        let repo = self.repo()?;
+        let commit = repo
+            .store()
+            .get_commit(&parse_commit_id(&trunk.commit_id)?)
+            .map_err(|e| Error::Workspace(format!("Failed to get commit: {e}")))?;
+        let tree = commit.tree();
+        Ok((repo, tree))
+    }
+
+    /// Read a text file (repo-relative, `/`-separated) from the `trunk()` tree
+    ///
+    /// Returns `None` when there is no such file, or it is conflicted.
+    pub fn read_trunk_file(&self, path: &str) -> Result<Option<String>> {
+        let (repo, tree) = self.trunk_tree()?;
+        let path = RepoPathBuf::from_internal_string(path)
+            .map_err(|e| Error::Workspace(format!("Invalid path {path}: {e}")))?;
+
+        let value = tree
+            .path_value(&path)
+            .map_err(|e| Error::Workspace(format!("Failed to read tree: {e}")))?;
+        let Some(Some(TreeValue::File { id, .. })) = value.as_resolved() else {
+            return Ok(None);
+        };
+
+        let mut content = Vec::new();
+        repo.store()
+            .read_file(&path, id)
+            .block_on()
+            .map_err(|e| Error::Workspace(format!("Failed to read {path:?}: {e}")))?
+            .read_to_end(&mut content)
+            .block_on()
+            .map_err(|e| Error::Workspace(format!("Failed to read {path:?}: {e}")))?;
+
+        Ok(Some(String::from_utf8_lossy(&content).into_owned()))
+    }
+
+    /// Names of the files directly inside a directory of the `trunk()` tree
+    ///
+    /// Returns an empty list when there is no such directory.
+    pub fn list_trunk_dir(&self, dir: &str) -> Result<Vec<String>> {
+        let (repo, tree) = self.trunk_tree()?;
+        let dir = RepoPathBuf::from_internal_string(dir)
+            .map_err(|e| Error::Workspace(format!("Invalid path {dir}: {e}")))?;
+
+        let value = tree
+            .path_value(&dir)
+            .map_err(|e| Error::Workspace(format!("Failed to read tree: {e}")))?;
+        let Some(Some(TreeValue::Tree(id))) = value.as_resolved() else {
+            return Ok(Vec::new());
+        };
+
+        let subtree = repo
+            .store()
+            .get_tree(dir, id)
+            .map_err(|e| Error::Workspace(format!("Failed to read tree: {e}")))?;
+        let mut names: Vec<String> = subtree
+            .entries_non_recursive()
+            .filter(|entry| matches!(entry.value(), TreeValue::File { .. }))
+            .map(|entry| entry.name().as_internal_str().to_string())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Files changed by a range of commits (repo-relative, `/`-separated)
+    ///
+    /// Compares the parent tree of `first_commit_id` with the tree of
+    /// `last_commit_id`, so the result covers every commit from the first to
+    /// the last, like a PR's diff against its base.
+    pub fn changed_files(
+        &self,
+        first_commit_id: &str,
+        last_commit_id: &str,
+    ) -> Result<Vec<String>> {
+        let repo = self.repo()?;
+        let get_commit = |hex: &str| {
+            repo.store()
+                .get_commit(&parse_commit_id(hex)?)
+                .map_err(|e| Error::Workspace(format!("Failed to get commit: {e}")))
+        };
+        let base = get_commit(first_commit_id)?
+            .parent_tree(repo.as_ref())
+            .map_err(|e| Error::Workspace(format!("Failed to read parent tree: {e}")))?;
+        let tip = get_commit(last_commit_id)?.tree();
+
+        base.diff_stream(&tip, &EverythingMatcher)
+            .map(|entry| {
+                entry
+                    .values
+                    .map(|_| entry.path.as_internal_file_string().to_string())
+                    .map_err(|e| Error::Workspace(format!("Failed to diff trees: {e}")))
+            })
+            .collect::<Vec<_>>()
+            .block_on()
+            .into_iter()
+            .collect()
+    }
+
     /// Get the default branch name by checking remote HEAD first, then common names
     pub fn default_branch(&self) -> Result<String> {
         let repo = self.repo()?;
@@ -599,6 +1139,31 @@ impl JjWorkspace {
     }
 }
 
+/// Parse a hex commit ID
+fn parse_commit_id(hex: &str) -> Result<CommitId> {
+    CommitId::try_from_hex(hex).ok_or_else(|| Error::Internal(format!("invalid commit id: {hex}")))
+}
+
+/// Delete a local bookmark in `tx` and untrack it on every remote
+fn forget_bookmark(tx: &mut Transaction, name: &str) {
+    let ref_name = RefName::new(name);
+    tx.repo_mut()
+        .set_local_bookmark_target(ref_name, RefTarget::absent());
+
+    let name_matcher = StringPattern::exact(name).to_matcher();
+    let tracked_remotes: Vec<RemoteNameBuf> = tx
+        .repo()
+        .view()
+        .remote_bookmarks_matching(&name_matcher, &StringMatcher::All)
+        .filter(|(symbol, remote_ref)| symbol.remote.as_str() != "git" && remote_ref.is_tracked())
+        .map(|(symbol, _)| symbol.remote.to_owned())
+        .collect();
+    for remote in &tracked_remotes {
+        tx.repo_mut()
+            .untrack_remote_bookmark(ref_name.to_remote_symbol(remote));
+    }
+}
+
 /// Select a remote from a list of available remotes
 ///
 /// - If `specified` is provided and exists, use it
@@ -3,26 +3,94 @@
 //! Identifies what needs to be submitted for a given target bookmark.
 
 use crate::error::{Error, Result};
-use crate::types::{Bookmark, BookmarkSegment, ChangeGraph, NarrowedBookmarkSegment};
+use crate::types::{Bookmark, BookmarkSegment, BranchStack, ChangeGraph, NarrowedBookmarkSegment};
+use std::collections::HashMap;
+use std::fmt::Write;
 
 /// Result of submission analysis
 #[derive(Debug, Clone)]
 pub struct SubmissionAnalysis {
     /// Target bookmark name
     pub target_bookmark: String,
-    /// Segments to submit (from trunk towards target), each narrowed to one bookmark
+    /// Segments to submit (from trunk towards target), each narrowed to one
+    /// bookmark; in a branching stack, each segment comes after its parent
     pub segments: Vec<NarrowedBookmarkSegment>,
+    /// Bookmark each segment's bookmark builds on (see [`parent_bookmark`])
+    pub parents: HashMap<String, String>,
+}
+
+impl SubmissionAnalysis {
+    /// Keep only `bookmark` and the segments below it
+    ///
+    /// Returns false, changing nothing, if `bookmark` isn't being submitted.
+    pub fn truncate_to(&mut self, bookmark: &str) -> bool {
+        if !self.segments.iter().any(|s| s.bookmark.name == bookmark) {
+            return false;
+        }
+        let mut keep = vec![bookmark.to_string()];
+        let mut current = bookmark;
+        while let Ok(Some(parent)) = parent_bookmark(current, &self.segments, &self.parents) {
+            keep.push(parent.to_string());
+            current = parent;
+        }
+        self.segments.retain(|s| keep.contains(&s.bookmark.name));
+        self.target_bookmark = bookmark.to_string();
+        true
+    }
 }
 
 /// Analyze what needs to be submitted for a given bookmark
 ///
-/// Works with single-stack semantics: the graph contains only one stack
-/// from trunk to working copy. If `target_bookmark` is None, submits the
-/// entire stack (leaf bookmark). If specified, submits up to that bookmark.
+/// Works on the graph's stack, from trunk to working copy. If
+/// `target_bookmark` is None, submits the entire stack, every branch of it.
+/// If specified, submits that bookmark and the segments below it.
 pub fn analyze_submission(
     graph: &ChangeGraph,
     target_bookmark: Option<&str>,
 ) -> Result<SubmissionAnalysis> {
+    let stack = stack_of(graph)?;
+
+    let indices: Vec<usize> = if let Some(target) = target_bookmark {
+        // The target and its ancestors, trunk first
+        let mut index = segment_index(stack, target)?;
+        let mut indices = vec![index];
+        while let Some(parent) = stack.parent_index(index) {
+            indices.push(parent);
+            index = parent;
+        }
+        indices.reverse();
+        indices
+    } else {
+        // No target specified - the whole stack
+        (0..stack.segments.len()).collect()
+    };
+
+    Ok(narrow_segments(stack, &indices, target_bookmark))
+}
+
+/// Analyze a submission of `target_bookmark` and everything stacked on it
+///
+/// In a branching stack that's every branch above the target.
+pub fn analyze_upstack(graph: &ChangeGraph, target_bookmark: &str) -> Result<SubmissionAnalysis> {
+    let stack = stack_of(graph)?;
+    let target = segment_index(stack, target_bookmark)?;
+
+    // Segments follow their parents, so one pass finds every descendant
+    let mut indices = vec![target];
+    for index in target + 1..stack.segments.len() {
+        if stack
+            .parent_index(index)
+            .is_some_and(|parent| indices.contains(&parent))
+        {
+            indices.push(index);
+        }
+    }
+
+    Ok(narrow_segments(stack, &indices, Some(target_bookmark)))
+}
+
+/// The graph's stack, if it has one with segments
+fn stack_of(graph: &ChangeGraph) -> Result<&BranchStack> {
     let stack = graph
         .stack
         .as_ref()
@@ -31,26 +99,31 @@ pub fn analyze_submission(
     if stack.segments.is_empty() {
         return Err(Error::NoStack("Stack has no segments".to_string()));
     }
+    Ok(stack)
+}
 
-    // Determine target index
-    let target_index = if let Some(target) = target_bookmark {
-        stack
-            .segments
-            .iter()
-            .position(|segment| segment.bookmarks.iter().any(|b| b.name == target))
-            .ok_or_else(|| Error::BookmarkNotFound(target.to_string()))?
-    } else {
-        // No target specified - use leaf (last segment)
-        stack.segments.len() - 1
-    };
-
-    // Get segments from trunk (index 0) to target (inclusive)
-    let relevant_segments = &stack.segments[0..=target_index];
+/// Index of the segment with `bookmark`
+fn segment_index(stack: &BranchStack, bookmark: &str) -> Result<usize> {
+    stack
+        .segments
+        .iter()
+        .position(|segment| segment.bookmarks.iter().any(|b| b.name == bookmark))
+        .ok_or_else(|| Error::BookmarkNotFound(bookmark.to_string()))
+}
 
+/// Narrow the segments at `indices` to one bookmark each
+///
+/// The last segment's bookmark becomes the target.
+fn narrow_segments(
+    stack: &BranchStack,
+    indices: &[usize],
+    target_bookmark: Option<&str>,
+) -> SubmissionAnalysis {
     // Narrow each segment to a single bookmark using heuristics
-    let narrowed: Vec<NarrowedBookmarkSegment> = relevant_segments
+    let narrowed: Vec<NarrowedBookmarkSegment> = indices
         .iter()
-        .map(|segment| {
+        .map(|&index| {
+            let segment = &stack.segments[index];
             let bookmark = select_bookmark_for_segment(segment, target_bookmark);
 
             NarrowedBookmarkSegment {
@@ -60,16 +133,31 @@ pub fn analyze_submission(
         })
         .collect();
 
+    // Parents by the selected bookmark names, where both are submitted
+    let parents = indices
+        .iter()
+        .zip(&narrowed)
+        .filter_map(|(&index, segment)| {
+            let parent = stack.parent_index(index)?;
+            let position = indices.iter().position(|&i| i == parent)?;
+            Some((
+                segment.bookmark.name.clone(),
+                narrowed[position].bookmark.name.clone(),
+            ))
+        })
+        .collect();
+
     // Use the actual selected bookmark name for the target
     let actual_target = narrowed
         .last()
         .map(|s| s.bookmark.name.clone())
         .unwrap_or_default();
 
-    Ok(SubmissionAnalysis {
+    SubmissionAnalysis {
         target_bookmark: actual_target,
         segments: narrowed,
-    })
+        parents,
+    }
 }
 
 /// Select a single bookmark from a segment using heuristics
@@ -129,27 +217,53 @@ fn is_temporary_bookmark(name: &str) -> bool {
         || lower.starts_with("wip/")
 }
 
+/// Get the bookmark in `segments` that a bookmark builds on
+///
+/// With an entry in `parents` (a branching stack), that's its nearest
+/// ancestor still in `segments`; without one, the segment before it.
+/// Returns `None` when it builds on trunk.
+#[allow(clippy::implicit_hasher)]
+pub fn parent_bookmark<'a>(
+    bookmark_name: &str,
+    segments: &'a [NarrowedBookmarkSegment],
+    parents: &'a HashMap<String, String>,
+) -> Result<Option<&'a str>> {
+    let index = segments
+        .iter()
+        .position(|s| s.bookmark.name == bookmark_name)
+        .ok_or_else(|| Error::BookmarkNotFound(bookmark_name.to_string()))?;
+
+    let Some(mut parent) = parents.get(bookmark_name) else {
+        return Ok(index
+            .checked_sub(1)
+            .map(|i| segments[i].bookmark.name.as_str()));
+    };
+    // Skip ancestors left out of the submission (e.g. untracked ones)
+    loop {
+        if let Some(segment) = segments.iter().find(|s| &s.bookmark.name == parent) {
+            return Ok(Some(&segment.bookmark.name));
+        }
+        match parents.get(parent) {
+            Some(grandparent) => parent = grandparent,
+            None => return Ok(None),
+        }
+    }
+}
+
 /// Get the expected base branch for a bookmark in a submission
 ///
-/// Returns the bookmark name that this bookmark should be based on,
-/// or the default branch name if it's the first in the stack.
+/// Returns the bookmark name that this bookmark should be based on (see
+/// [`parent_bookmark`]), or the default branch name if it builds on trunk.
+#[allow(clippy::implicit_hasher)]
 pub fn get_base_branch(
     bookmark_name: &str,
     segments: &[NarrowedBookmarkSegment],
+    parents: &HashMap<String, String>,
     default_branch: &str,
 ) -> Result<String> {
-    for (i, segment) in segments.iter().enumerate() {
-        if segment.bookmark.name == bookmark_name {
-            if i == 0 {
-                // First segment is based on default branch
-                return Ok(default_branch.to_string());
-            }
-            // Otherwise, based on previous segment's bookmark
-            return Ok(segments[i - 1].bookmark.name.clone());
-        }
-    }
-
-    Err(Error::BookmarkNotFound(bookmark_name.to_string()))
+    Ok(parent_bookmark(bookmark_name, segments, parents)?
+        .unwrap_or(default_branch)
+        .to_string())
 }
 
 /// Generate a PR title from the bookmark's commits
@@ -184,6 +298,92 @@ pub fn generate_pr_title(
     }
 }
 
+/// Generate a PR body from the bookmark's commits
+///
+/// The root commit's first line is the title (see [`generate_pr_title`]);
+/// the rest of its description becomes the body. Any other commits in the
+/// segment are listed after it, oldest first.
+pub fn generate_pr_body(
+    bookmark_name: &str,
+    segments: &[NarrowedBookmarkSegment],
+) -> Result<String> {
+    let segment = segments
+        .iter()
+        .find(|s| s.bookmark.name == bookmark_name)
+        .ok_or_else(|| Error::BookmarkNotFound(bookmark_name.to_string()))?;
+
+    // changes[0] is newest, changes[last] is oldest/root
+    let Some((root_commit, others)) = segment.changes.split_last() else {
+        return Ok(String::new());
+    };
+
+    let mut body = root_commit
+        .description
+        .split_once('\n')
+        .map_or("", |(_, rest)| rest)
+        .trim()
+        .to_string();
+
+    if !others.is_empty() {
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str("Also in this PR:\n");
+        for change in others.iter().rev() {
+            let line = if change.description_first_line.is_empty() {
+                "(no description)"
+            } else {
+                &change.description_first_line
+            };
+            let _ = writeln!(body, "- {line}");
+        }
+    }
+
+    Ok(body.trim_end().to_string())
+}
+
+/// Marker opening the generated section of a PR body
+pub const PR_BODY_START: &str = "<!-- ryu:generated -->";
+
+/// Marker closing the generated section of a PR body
+pub const PR_BODY_END: &str = "<!-- /ryu:generated -->";
+
+/// Wrap a generated PR body in the generated-section markers
+///
+/// Text outside the markers belongs to whoever edits the PR, so later
+/// submissions only ever rewrite what's between them.
+pub fn wrap_generated_body(body: &str) -> String {
+    if body.is_empty() {
+        format!("{PR_BODY_START}\n{PR_BODY_END}")
+    } else {
+        format!("{PR_BODY_START}\n{body}\n{PR_BODY_END}")
+    }
+}
+
+/// The live PR body with its generated section replaced by `generated`
+///
+/// Returns `None` when there is nothing to change: the section is already
+/// up to date, or the body has no generated section at all (the PR was
+/// written by hand, so it is left alone).
+pub fn updated_pr_body(live: &str, generated: &str) -> Option<String> {
+    let start = live.find(PR_BODY_START)?;
+    let inner_start = start + PR_BODY_START.len();
+    let end = inner_start + live[inner_start..].find(PR_BODY_END)?;
+
+    // Web editors save line endings as CRLF
+    let current = live[inner_start..end].replace("\r\n", "\n");
+    if current.trim() == generated.trim() {
+        return None;
+    }
+
+    Some(format!(
+        "{}{}{}",
+        &live[..start],
+        wrap_generated_body(generated.trim()),
+        &live[end + PR_BODY_END.len()..]
+    ))
+}
+
 /// Create narrowed segments from resolved bookmarks and analysis
 ///
 /// This bridges CLI bookmark selection with submission planning.
@@ -208,6 +408,63 @@ pub fn create_narrowed_segments(
     Ok(segments)
 }
 
+/// Split each segment into one segment per change, for one PR per change
+///
+/// `bookmarks` maps change IDs to the bookmark each change is submitted
+/// from; changes without one are left out. Each change builds on the one
+/// below it in its segment, and a segment's bottom change on the top change
+/// of the segment it builds on.
+#[allow(clippy::implicit_hasher)]
+pub fn split_into_changes(
+    analysis: &SubmissionAnalysis,
+    bookmarks: &HashMap<String, Bookmark>,
+) -> SubmissionAnalysis {
+    let mut segments = Vec::new();
+    let mut parents = HashMap::new();
+    // Bookmark of the top submitted change in or below each segment
+    let mut tops: HashMap<&str, String> = HashMap::new();
+
+    for segment in &analysis.segments {
+        let mut below = parent_bookmark(
+            &segment.bookmark.name,
+            &analysis.segments,
+            &analysis.parents,
+        )
+        .ok()
+        .flatten()
+        .and_then(|parent| tops.get(parent).cloned());
+
+        // Changes are newest first
+        for change in segment.changes.iter().rev() {
+            let Some(bookmark) = bookmarks.get(&change.change_id) else {
+                continue;
+            };
+            if let Some(parent) = below.replace(bookmark.name.clone())
+                && !analysis.parents.is_empty()
+            {
+                parents.insert(bookmark.name.clone(), parent);
+            }
+            segments.push(NarrowedBookmarkSegment {
+                bookmark: bookmark.clone(),
+                changes: vec![change.clone()],
+            });
+        }
+
+        if let Some(top) = below {
+            tops.insert(&segment.bookmark.name, top);
+        }
+    }
+
+    SubmissionAnalysis {
+        target_bookmark: segments
+            .last()
+            .map(|s| s.bookmark.name.clone())
+            .unwrap_or_default(),
+        segments,
+        parents,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,6 +488,7 @@ mod tests {
             author_name: "Test".to_string(),
             author_email: "test@example.com".to_string(),
             description_first_line: desc.to_string(),
+            description: format!("{desc}\n"),
             parents: vec![],
             local_bookmarks: bookmarks.iter().map(ToString::to_string).collect(),
             remote_bookmarks: vec![],
@@ -245,24 +503,23 @@ mod tests {
         let bm1 = make_bookmark("feat-a");
         let bm2 = make_bookmark("feat-b");
 
-        let stack = BranchStack {
-            segments: vec![
-                BookmarkSegment {
-                    bookmarks: vec![bm1.clone()],
-                    changes: vec![make_log_entry("First change", &["feat-a"])],
-                },
-                BookmarkSegment {
-                    bookmarks: vec![bm2.clone()],
-                    changes: vec![make_log_entry("Second change", &["feat-b"])],
-                },
-            ],
-        };
+        let stack = BranchStack::linear(vec![
+            BookmarkSegment {
+                bookmarks: vec![bm1.clone()],
+                changes: vec![make_log_entry("First change", &["feat-a"])],
+            },
+            BookmarkSegment {
+                bookmarks: vec![bm2.clone()],
+                changes: vec![make_log_entry("Second change", &["feat-b"])],
+            },
+        ]);
 
         let graph = ChangeGraph {
             bookmarks: [("feat-a".to_string(), bm1), ("feat-b".to_string(), bm2)]
                 .into_iter()
                 .collect(),
-            stack: Some(stack),
+            stack: Some(stack.clone()),
+            stacks: vec![stack],
             excluded_bookmark_count: 0,
         };
 
@@ -278,24 +535,23 @@ mod tests {
         let bm1 = make_bookmark("feat-a");
         let bm2 = make_bookmark("feat-b");
 
-        let stack = BranchStack {
-            segments: vec![
-                BookmarkSegment {
-                    bookmarks: vec![bm1.clone()],
-                    changes: vec![make_log_entry("First change", &["feat-a"])],
-                },
-                BookmarkSegment {
-                    bookmarks: vec![bm2.clone()],
-                    changes: vec![make_log_entry("Second change", &["feat-b"])],
-                },
-            ],
-        };
+        let stack = BranchStack::linear(vec![
+            BookmarkSegment {
+                bookmarks: vec![bm1.clone()],
+                changes: vec![make_log_entry("First change", &["feat-a"])],
+            },
+            BookmarkSegment {
+                bookmarks: vec![bm2.clone()],
+                changes: vec![make_log_entry("Second change", &["feat-b"])],
+            },
+        ]);
 
         let graph = ChangeGraph {
             bookmarks: [("feat-a".to_string(), bm1), ("feat-b".to_string(), bm2)]
                 .into_iter()
                 .collect(),
-            stack: Some(stack),
+            stack: Some(stack.clone()),
+            stacks: vec![stack],
             excluded_bookmark_count: 0,
         };
 
@@ -316,16 +572,15 @@ mod tests {
     fn test_analyze_submission_bookmark_not_found() {
         let bm1 = make_bookmark("feat-a");
 
-        let stack = BranchStack {
-            segments: vec![BookmarkSegment {
-                bookmarks: vec![bm1.clone()],
-                changes: vec![make_log_entry("First change", &["feat-a"])],
-            }],
-        };
+        let stack = BranchStack::linear(vec![BookmarkSegment {
+            bookmarks: vec![bm1.clone()],
+            changes: vec![make_log_entry("First change", &["feat-a"])],
+        }]);
 
         let graph = ChangeGraph {
             bookmarks: std::iter::once(("feat-a".to_string(), bm1)).collect(),
-            stack: Some(stack),
+            stack: Some(stack.clone()),
+            stacks: vec![stack],
             excluded_bookmark_count: 0,
         };
 
@@ -340,7 +595,7 @@ mod tests {
             changes: vec![],
         }];
 
-        let base = get_base_branch("feat-a", &segments, "main").unwrap();
+        let base = get_base_branch("feat-a", &segments, &HashMap::new(), "main").unwrap();
         assert_eq!(base, "main");
     }
 
@@ -357,7 +612,142 @@ mod tests {
             },
         ];
 
-        let base = get_base_branch("feat-b", &segments, "main").unwrap();
+        let base = get_base_branch("feat-b", &segments, &HashMap::new(), "main").unwrap();
+        assert_eq!(base, "feat-a");
+    }
+
+    /// feat-a with feat-b and feat-c both built on it
+    fn make_branching_graph() -> ChangeGraph {
+        let names = ["feat-a", "feat-b", "feat-c"];
+        let mut stack = BranchStack::linear(
+            names
+                .iter()
+                .map(|name| BookmarkSegment {
+                    bookmarks: vec![make_bookmark(name)],
+                    changes: vec![make_log_entry(&format!("Add {name}"), &[name])],
+                })
+                .collect(),
+        );
+        stack
+            .parents
+            .insert("feat-c".to_string(), "feat-a".to_string());
+
+        ChangeGraph {
+            bookmarks: names
+                .iter()
+                .map(|name| ((*name).to_string(), make_bookmark(name)))
+                .collect(),
+            stack: Some(stack.clone()),
+            stacks: vec![stack],
+            excluded_bookmark_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_analyze_branching_stack_follows_ancestors() {
+        let graph = make_branching_graph();
+
+        let whole = analyze_submission(&graph, None).unwrap();
+        assert_eq!(whole.segments.len(), 3);
+        assert_eq!(
+            whole.parents.get("feat-c").map(String::as_str),
+            Some("feat-a")
+        );
+
+        // Submitting feat-c leaves its sibling out
+        let branch = analyze_submission(&graph, Some("feat-c")).unwrap();
+        let names: Vec<_> = branch
+            .segments
+            .iter()
+            .map(|s| s.bookmark.name.as_str())
+            .collect();
+        assert_eq!(names, ["feat-a", "feat-c"]);
+        assert_eq!(
+            get_base_branch("feat-c", &branch.segments, &branch.parents, "main").unwrap(),
+            "feat-a"
+        );
+
+        let upstack = analyze_upstack(&graph, "feat-a").unwrap();
+        assert_eq!(upstack.segments.len(), 3);
+    }
+
+    #[test]
+    fn test_truncate_to_keeps_ancestors() {
+        let graph = make_branching_graph();
+        let mut analysis = analyze_submission(&graph, None).unwrap();
+
+        assert!(analysis.truncate_to("feat-c"));
+        let names: Vec<_> = analysis
+            .segments
+            .iter()
+            .map(|s| s.bookmark.name.as_str())
+            .collect();
+        assert_eq!(names, ["feat-a", "feat-c"]);
+        assert_eq!(analysis.target_bookmark, "feat-c");
+        assert!(!analysis.truncate_to("feat-b"));
+    }
+
+    #[test]
+    fn test_split_into_changes() {
+        let graph = make_branching_graph();
+        let mut analysis = analyze_submission(&graph, None).unwrap();
+        // feat-a has a second, older change, and one of feat-b's isn't mapped
+        analysis.segments[0]
+            .changes
+            .push(make_log_entry("Prepare feat-a", &[]));
+        analysis.segments[1]
+            .changes
+            .insert(0, make_log_entry("Tweak feat-b", &[]));
+        let change_ids = ["Prepare feat-a", "Add feat-a", "Add feat-b", "Add feat-c"];
+        let bookmarks: HashMap<String, Bookmark> = change_ids
+            .iter()
+            .enumerate()
+            .map(|(i, desc)| (format!("{desc}_change"), make_bookmark(&format!("c{i}"))))
+            .collect();
+
+        let split = split_into_changes(&analysis, &bookmarks);
+        let names: Vec<_> = split
+            .segments
+            .iter()
+            .map(|s| s.bookmark.name.as_str())
+            .collect();
+        assert_eq!(names, ["c0", "c1", "c2", "c3"]);
+        assert_eq!(split.target_bookmark, "c3");
+        assert!(split.segments.iter().all(|s| s.changes.len() == 1));
+        assert_eq!(
+            get_base_branch("c1", &split.segments, &split.parents, "main").unwrap(),
+            "c0"
+        );
+        assert_eq!(
+            get_base_branch("c2", &split.segments, &split.parents, "main").unwrap(),
+            "c1"
+        );
+        // feat-c's change builds on feat-a's top change, not feat-b's
+        assert_eq!(
+            get_base_branch("c3", &split.segments, &split.parents, "main").unwrap(),
+            "c1"
+        );
+    }
+
+    #[test]
+    fn test_get_base_branch_skips_missing_parent() {
+        // feat-b was left out; feat-c builds on what's below it
+        let segments = vec![
+            NarrowedBookmarkSegment {
+                bookmark: make_bookmark("feat-a"),
+                changes: vec![],
+            },
+            NarrowedBookmarkSegment {
+                bookmark: make_bookmark("feat-c"),
+                changes: vec![],
+            },
+        ];
+        let parents = HashMap::from([
+            ("feat-b".to_string(), "feat-a".to_string()),
+            ("feat-c".to_string(), "feat-b".to_string()),
+        ]);
+
+        let base = get_base_branch("feat-c", &segments, &parents, "main").unwrap();
         assert_eq!(base, "feat-a");
     }
 
@@ -400,6 +790,80 @@ mod tests {
         assert_eq!(title, "Implement cool feature");
     }
 
+    #[test]
+    fn test_generate_pr_body_single_commit() {
+        let mut root = make_log_entry("Add cool feature", &["feat-a"]);
+        root.description =
+            "Add cool feature\n\nIt is cool because\nof reasons.\n\nSecond paragraph.\n".into();
+        let segments = vec![NarrowedBookmarkSegment {
+            bookmark: make_bookmark("feat-a"),
+            changes: vec![root],
+        }];
+
+        let body = generate_pr_body("feat-a", &segments).unwrap();
+        assert_eq!(body, "It is cool because\nof reasons.\n\nSecond paragraph.");
+
+        // A one-line description leaves the body empty
+        let segments = vec![NarrowedBookmarkSegment {
+            bookmark: make_bookmark("feat-a"),
+            changes: vec![make_log_entry("Add cool feature", &["feat-a"])],
+        }];
+        assert_eq!(generate_pr_body("feat-a", &segments).unwrap(), "");
+    }
+
+    #[test]
+    fn test_generate_pr_body_lists_other_commits() {
+        let mut root = make_log_entry("Implement cool feature", &[]);
+        root.description = "Implement cool feature\n\nDetails.\n".into();
+        let segments = vec![NarrowedBookmarkSegment {
+            bookmark: make_bookmark("feat-a"),
+            changes: vec![
+                make_log_entry("Fix typo in feature", &["feat-a"]),
+                make_log_entry("", &[]),
+                root,
+            ],
+        }];
+
+        let body = generate_pr_body("feat-a", &segments).unwrap();
+        assert_eq!(
+            body,
+            "Details.\n\nAlso in this PR:\n- (no description)\n- Fix typo in feature"
+        );
+    }
+
+    #[test]
+    fn test_updated_pr_body_keeps_hand_edits() {
+        let live = format!(
+            "Reviewers: look at the parser first.\r\n\r\n{}\r\n\r\nTested on staging.",
+            wrap_generated_body("Old details.").replace('\n', "\r\n")
+        );
+
+        // Unchanged generated section: nothing to do
+        assert_eq!(updated_pr_body(&live, "Old details."), None);
+
+        let updated = updated_pr_body(&live, "New details.").unwrap();
+        assert_eq!(
+            updated,
+            format!(
+                "Reviewers: look at the parser first.\r\n\r\n{}\r\n\r\nTested on staging.",
+                wrap_generated_body("New details.")
+            )
+        );
+    }
+
+    #[test]
+    fn test_updated_pr_body_ignores_bodies_without_markers() {
+        assert_eq!(updated_pr_body("Written by hand.", "Generated."), None);
+        assert_eq!(
+            updated_pr_body(&format!("{PR_BODY_START} unterminated"), "Generated."),
+            None
+        );
+        assert_eq!(
+            updated_pr_body(&wrap_generated_body(""), "Generated."),
+            Some(wrap_generated_body("Generated."))
+        );
+    }
+
     #[test]
     fn test_select_bookmark_single() {
         let segment = BookmarkSegment {
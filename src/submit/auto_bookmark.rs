@@ -0,0 +1,360 @@
+//! Bookmarks for unbookmarked changes
+//!
+//! `ryu submit --auto-bookmark` gives every change in the stack without a
+//! bookmark one of its own, much like `jj git push -c`, so each change
+//! becomes a PR. Names come from the `ryu.bookmark-template` template.
+//! `ryu submit --per-change` does the same for every change, keeping each
+//! change's bookmark in the tracking state.
+
+use crate::error::{Error, Result};
+use crate::tracking::TrackingState;
+use crate::types::{Bookmark, LogEntry};
+use jj_lib::hex_util::{decode_hex, encode_reverse_hex};
+use std::collections::{HashMap, HashSet};
+
+/// jj config key for the template new bookmark names are rendered from
+pub const BOOKMARK_TEMPLATE_CONFIG_KEY: &str = "ryu.bookmark-template";
+
+/// Template for new bookmark names, unless `ryu.bookmark-template` is set
+///
+/// Placeholders:
+/// - `{user}`: the author's email address up to the `@`
+/// - `{change_id_short}`: the first 12 characters of the change ID, as jj
+///   shows it
+/// - `{change_id}`: the full change ID
+/// - `{slug}`: the first line of the description, lowercased, with runs of
+///   other characters than letters and digits replaced by `-`
+pub const DEFAULT_BOOKMARK_TEMPLATE: &str = "{user}/{change_id_short}";
+
+/// Longest `{slug}`, in characters
+const MAX_SLUG_LEN: usize = 50;
+
+/// A bookmark to create on an unbookmarked change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoBookmark {
+    /// Bookmark name
+    pub name: String,
+    /// Commit it points to
+    pub commit_id: String,
+    /// Change of that commit
+    pub change_id: String,
+}
+
+impl AutoBookmark {
+    /// The bookmark, as it is once created
+    pub fn bookmark(&self) -> Bookmark {
+        Bookmark {
+            name: self.name.clone(),
+            commit_id: self.commit_id.clone(),
+            change_id: self.change_id.clone(),
+            has_remote: false,
+            is_synced: false,
+        }
+    }
+}
+
+/// Name a bookmark for each unbookmarked change in `changes`
+///
+/// Changes without a description are left out, as `jj git push -c` leaves
+/// them: there's nothing to title their PR with, and the working copy is
+/// usually one. A name already in `existing` (or given to an earlier
+/// change) gets `-2`, `-3`, ... appended.
+#[allow(clippy::implicit_hasher)]
+pub fn plan_auto_bookmarks(
+    changes: &[LogEntry],
+    existing: &HashMap<String, Bookmark>,
+    template: &str,
+) -> Result<Vec<AutoBookmark>> {
+    let mut taken: HashSet<String> = existing.keys().cloned().collect();
+    let mut planned = Vec::new();
+
+    for change in changes {
+        if !change.local_bookmarks.is_empty() || change.description.trim().is_empty() {
+            continue;
+        }
+
+        let name = unique_name(&render_bookmark_name(template, change)?, &mut taken);
+
+        planned.push(AutoBookmark {
+            name,
+            commit_id: change.commit_id.clone(),
+            change_id: change.change_id.clone(),
+        });
+    }
+
+    Ok(planned)
+}
+
+/// The bookmark a change is submitted from, one PR per change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeBookmark {
+    /// Bookmark name
+    pub name: String,
+    /// Commit it points to (or will)
+    pub commit_id: String,
+    /// Change of that commit
+    pub change_id: String,
+    /// Whether the bookmark has to be created first
+    pub create: bool,
+}
+
+impl ChangeBookmark {
+    /// The bookmark, as it is once created
+    pub fn bookmark(&self) -> Bookmark {
+        Bookmark {
+            name: self.name.clone(),
+            commit_id: self.commit_id.clone(),
+            change_id: self.change_id.clone(),
+            has_remote: false,
+            is_synced: false,
+        }
+    }
+}
+
+/// Pick the bookmark each described change in `changes` is submitted from
+///
+/// A change keeps the bookmark `tracking` maps it to, recreated if it was
+/// deleted, so it keeps its PR however often it's rewritten. Otherwise a
+/// bookmark already on the change (and not another change's) is used, or a new one is named from
+/// `template` as [`plan_auto_bookmarks`] does.
+#[allow(clippy::implicit_hasher)]
+pub fn plan_change_bookmarks(
+    changes: &[LogEntry],
+    tracking: &TrackingState,
+    existing: &HashMap<String, Bookmark>,
+    template: &str,
+) -> Result<Vec<ChangeBookmark>> {
+    let mut taken: HashSet<String> = existing.keys().cloned().collect();
+    taken.extend(tracking.changes.iter().map(|c| c.bookmark.clone()));
+    let mut planned = Vec::new();
+
+    for change in changes {
+        if change.description.trim().is_empty() {
+            continue;
+        }
+
+        // A mapped bookmark that's since been moved to another change is
+        // no longer this change's
+        let mapped = tracking
+            .change(&change.change_id)
+            .map(|c| c.bookmark.clone())
+            .filter(|name| {
+                existing
+                    .get(name)
+                    .is_none_or(|b| b.change_id == change.change_id)
+            });
+        let (name, create) = if let Some(name) = mapped {
+            let create = !existing.contains_key(&name);
+            (name, create)
+        } else if let Some(name) = change.local_bookmarks.iter().find(|name| {
+            // One left behind by a change squashed into this one isn't it
+            !tracking.changes.iter().any(|c| &c.bookmark == *name)
+        }) {
+            (name.clone(), false)
+        } else {
+            let name = unique_name(&render_bookmark_name(template, change)?, &mut taken);
+            (name, true)
+        };
+
+        planned.push(ChangeBookmark {
+            name,
+            commit_id: change.commit_id.clone(),
+            change_id: change.change_id.clone(),
+            create,
+        });
+    }
+
+    Ok(planned)
+}
+
+/// `base`, or `base` with the first free `-2`, `-3`, ... suffix, now taken
+fn unique_name(base: &str, taken: &mut HashSet<String>) -> String {
+    let mut name = base.to_string();
+    let mut suffix = 2;
+    while taken.contains(&name) {
+        name = format!("{base}-{suffix}");
+        suffix += 1;
+    }
+    taken.insert(name.clone());
+    name
+}
+
+/// Render a bookmark name for `change` from `template`
+///
+/// See [`DEFAULT_BOOKMARK_TEMPLATE`] for the placeholders.
+pub fn render_bookmark_name(template: &str, change: &LogEntry) -> Result<String> {
+    let change_id = display_change_id(&change.change_id);
+    let mut name = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        let end = rest[start..].find('}').ok_or_else(|| {
+            Error::Config(format!(
+                "unclosed '{{' in {BOOKMARK_TEMPLATE_CONFIG_KEY}: {template}"
+            ))
+        })?;
+        let value = match &rest[start + 1..start + end] {
+            "user" => {
+                let user = change.author_email.split('@').next().unwrap_or_default();
+                slugify(if user.is_empty() {
+                    &change.author_name
+                } else {
+                    user
+                })
+            }
+            "change_id_short" => change_id.chars().take(12).collect(),
+            "change_id" => change_id.clone(),
+            "slug" => {
+                let slug = slugify(&change.description_first_line);
+                if slug.is_empty() {
+                    change_id.chars().take(12).collect()
+                } else {
+                    slug
+                }
+            }
+            other => {
+                return Err(Error::Config(format!(
+                    "unknown placeholder {{{other}}} in {BOOKMARK_TEMPLATE_CONFIG_KEY}"
+                )));
+            }
+        };
+        name.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    name.push_str(rest);
+
+    if name.is_empty() {
+        return Err(Error::Config(format!(
+            "{BOOKMARK_TEMPLATE_CONFIG_KEY} renders an empty bookmark name"
+        )));
+    }
+    Ok(name)
+}
+
+/// Lowercase letters and digits, with `-` for each run of anything else
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.chars().take(MAX_SLUG_LEN).collect();
+    slug.trim_end_matches('-').to_string()
+}
+
+/// A change ID as jj shows it (reverse hex, `k`-`z`)
+fn display_change_id(hex: &str) -> String {
+    decode_hex(hex).map_or_else(|| hex.to_string(), |bytes| encode_reverse_hex(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn make_change(change_id: &str, description: &str, bookmarks: &[&str]) -> LogEntry {
+        LogEntry {
+            commit_id: format!("{change_id}0"),
+            change_id: change_id.to_string(),
+            author_name: "Alice Smith".to_string(),
+            author_email: "alice@example.com".to_string(),
+            description_first_line: description.lines().next().unwrap_or("").to_string(),
+            description: description.to_string(),
+            parents: vec![],
+            local_bookmarks: bookmarks.iter().map(ToString::to_string).collect(),
+            remote_bookmarks: vec![],
+            is_working_copy: false,
+            authored_at: Utc::now(),
+            committed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_render_default_template() {
+        let change = make_change("0123456789abcdef0123456789abcdef", "Add login", &[]);
+        let name = render_bookmark_name(DEFAULT_BOOKMARK_TEMPLATE, &change).unwrap();
+        // jj shows change IDs in reverse hex
+        assert_eq!(name, "alice/zyxwvutsrqpo");
+    }
+
+    #[test]
+    fn test_render_slug() {
+        let change = make_change("00", "Fix: the *login* page (again)\n\nDetails", &[]);
+        let name = render_bookmark_name("fix/{slug}", &change).unwrap();
+        assert_eq!(name, "fix/fix-the-login-page-again");
+    }
+
+    #[test]
+    fn test_render_unknown_placeholder() {
+        let change = make_change("00", "Add login", &[]);
+        assert!(matches!(
+            render_bookmark_name("{branch}", &change),
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_plan_skips_bookmarked_and_undescribed_changes() {
+        let changes = vec![
+            make_change("03", "", &[]),
+            make_change("02", "Add logout", &[]),
+            make_change("01", "Add login", &["feat-login"]),
+            make_change("00", "Add logout", &[]),
+        ];
+        let existing = HashMap::from([(
+            "add-logout".to_string(),
+            Bookmark {
+                name: "add-logout".to_string(),
+                commit_id: "x".to_string(),
+                change_id: "x".to_string(),
+                has_remote: false,
+                is_synced: false,
+            },
+        )]);
+
+        let planned = plan_auto_bookmarks(&changes, &existing, "{slug}").unwrap();
+        let names: Vec<&str> = planned.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["add-logout-2", "add-logout-3"]);
+        assert_eq!(planned[0].change_id, "02");
+    }
+
+    #[test]
+    fn test_plan_change_bookmarks_keeps_mapping() {
+        let changes = vec![
+            make_change("02", "Add logout", &[]),
+            make_change("01", "Add login", &["feat-login"]),
+            make_change("00", "Add signup", &[]),
+        ];
+        let mut tracking = TrackingState::new();
+        // The mapped bookmark was deleted; it's recreated
+        tracking.track_change("00", "alice/signup");
+        let existing = HashMap::from([(
+            "feat-login".to_string(),
+            Bookmark {
+                name: "feat-login".to_string(),
+                commit_id: "010".to_string(),
+                change_id: "01".to_string(),
+                has_remote: true,
+                is_synced: true,
+            },
+        )]);
+
+        let planned = plan_change_bookmarks(&changes, &tracking, &existing, "{slug}").unwrap();
+        let names: Vec<(&str, bool)> = planned
+            .iter()
+            .map(|b| (b.name.as_str(), b.create))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("add-logout", true),
+                ("feat-login", false),
+                ("alice/signup", true)
+            ]
+        );
+    }
+}
@@ -0,0 +1,396 @@
+//! CODEOWNERS
+//!
+//! Reads the repository's `CODEOWNERS` from trunk (GitHub syntax, or GitLab
+//! syntax with sections) and works out which owners to request as reviewers
+//! on each PR of a stack, from the files that PR changes.
+
+use crate::error::Result;
+use crate::repo::JjWorkspace;
+use crate::submit::{ExecutionStep, SubmissionPlan};
+use crate::types::Platform;
+use std::collections::HashMap;
+
+const GITHUB_LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+const GITLAB_LOCATIONS: &[&str] = &["CODEOWNERS", "docs/CODEOWNERS", ".gitlab/CODEOWNERS"];
+
+/// Where a platform looks for `CODEOWNERS`, in order of precedence
+///
+/// Gitea's `CODEOWNERS` uses regular expressions rather than globs, and the
+/// other platforms have no such file.
+const fn codeowners_locations(platform: Platform) -> &'static [&'static str] {
+    match platform {
+        // The local forge stands in for GitHub in demos
+        Platform::GitHub | Platform::Local => GITHUB_LOCATIONS,
+        Platform::GitLab => GITLAB_LOCATIONS,
+        Platform::Gitea | Platform::Bitbucket | Platform::AzureDevOps | Platform::Gerrit => &[],
+    }
+}
+
+/// Parsed `CODEOWNERS` rules
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    /// Lowercased GitLab section name; `None` before any section
+    section: Option<String>,
+    pattern: Pattern,
+    owners: Vec<String>,
+}
+
+/// A gitignore-style path pattern, split into components
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern {
+    parts: Vec<String>,
+    /// Ends with `/`: only matches what's inside a directory
+    dir_only: bool,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Self {
+        let dir_only = pattern.ends_with('/');
+        let trimmed = pattern.trim_end_matches('/');
+        // Like .gitignore, a pattern with no inner slash matches at any depth
+        let anchored = trimmed.contains('/');
+        let mut parts: Vec<String> = trimmed
+            .trim_start_matches('/')
+            .split('/')
+            .filter(|p| !p.is_empty())
+            .map(ToString::to_string)
+            .collect();
+        if !anchored {
+            parts.insert(0, "**".to_string());
+        }
+        Self { parts, dir_only }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        let path: Vec<&str> = path.split('/').collect();
+        if !self.dir_only && match_parts(&self.parts, &path) {
+            return true;
+        }
+        // A pattern naming a directory owns everything in it, except that
+        // `dir/*` only covers the files directly inside
+        if self.parts.last().is_some_and(|p| p == "*") {
+            return false;
+        }
+        (1..path.len()).any(|len| match_parts(&self.parts, &path[..len]))
+    }
+}
+
+fn match_parts(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| match_parts(rest, &path[skip..]))
+        }
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(name, tail)| match_component(first, name) && match_parts(rest, tail)),
+    }
+}
+
+/// Match one path component against `*` and `?` wildcards
+fn match_component(pattern: &str, name: &str) -> bool {
+    fn go(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| go(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && go(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && go(rest, &name[1..]),
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    go(&pattern, &name)
+}
+
+/// Split a line at unescaped whitespace, unescaping `\ ` and `\#`
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => field.extend(chars.next()),
+            c if c.is_whitespace() => {
+                if !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() {
+        fields.push(field);
+    }
+    fields
+}
+
+/// Reviewer name of an owner entry: `@user` → `user`, `@org/team` →
+/// `org/team`
+///
+/// Emails and GitLab roles (`@@developer`) can't be requested by name.
+fn reviewer_name(owner: &str) -> Option<&str> {
+    owner
+        .strip_prefix('@')
+        .filter(|name| !name.is_empty() && !name.starts_with('@'))
+}
+
+/// Parse a GitLab section header: `[Name]`, `^[Name]`, `[Name][2]`, each
+/// optionally followed by default owners
+///
+/// Returns the name, whether the section is optional, and the owners.
+fn parse_section(line: &str) -> Option<(String, bool, Vec<String>)> {
+    let (optional, rest) = line
+        .strip_prefix('^')
+        .map_or((false, line), |rest| (true, rest));
+    let rest = rest.strip_prefix('[')?;
+    let (name, mut rest) = rest.split_once(']')?;
+    // Required approval count
+    if let Some(after) = rest.strip_prefix('[') {
+        rest = after.split_once(']').map_or("", |(_, after)| after);
+    }
+    Some((name.trim().to_lowercase(), optional, split_fields(rest)))
+}
+
+/// Parse `CODEOWNERS` content
+///
+/// Both GitHub and GitLab syntax are understood. Rules of optional GitLab
+/// sections (`^[Section]`) are skipped: their approval isn't required.
+pub fn parse_codeowners(content: &str) -> CodeOwners {
+    let mut rules = Vec::new();
+    let mut section: Option<String> = None;
+    let mut optional = false;
+    let mut default_owners: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') || line.starts_with("^[") {
+            if let Some((name, is_optional, owners)) = parse_section(line) {
+                section = Some(name);
+                optional = is_optional;
+                default_owners = owners;
+            }
+            continue;
+        }
+        if optional {
+            continue;
+        }
+
+        // Drop trailing comments
+        let line = line.find(" #").map_or(line, |idx| &line[..idx]);
+        let mut fields = split_fields(line).into_iter();
+        let Some(pattern) = fields.next() else {
+            continue;
+        };
+        let owners: Vec<String> = fields.collect();
+        // A path without owners falls back to the section's defaults (none,
+        // outside a section), clearing owners matched by earlier rules
+        let owners = if owners.is_empty() {
+            default_owners.clone()
+        } else {
+            owners
+        };
+        rules.push(Rule {
+            section: section.clone(),
+            pattern: Pattern::parse(&pattern),
+            owners,
+        });
+    }
+
+    CodeOwners { rules }
+}
+
+impl CodeOwners {
+    /// Whether there are no rules
+    pub const fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Reviewers owning a file (repo-relative, `/`-separated)
+    ///
+    /// Within each section the last matching rule wins; the owners of all
+    /// sections are combined.
+    pub fn owners_of(&self, path: &str) -> Vec<String> {
+        let mut by_section: Vec<(Option<&str>, &[String])> = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.pattern.matches(path)) {
+            let section = rule.section.as_deref();
+            match by_section.iter_mut().find(|(s, _)| *s == section) {
+                Some(entry) => entry.1 = &rule.owners,
+                None => by_section.push((section, &rule.owners)),
+            }
+        }
+
+        let mut owners: Vec<String> = Vec::new();
+        for name in by_section
+            .into_iter()
+            .flat_map(|(_, owners)| owners)
+            .filter_map(|o| reviewer_name(o))
+        {
+            if !owners.iter().any(|o| o.eq_ignore_ascii_case(name)) {
+                owners.push(name.to_string());
+            }
+        }
+        owners
+    }
+
+    /// Reviewers owning any of `files`, in order of first appearance
+    pub fn owners_of_files(&self, files: &[String]) -> Vec<String> {
+        let mut owners: Vec<String> = Vec::new();
+        for owner in files.iter().flat_map(|f| self.owners_of(f)) {
+            if !owners.iter().any(|o| o.eq_ignore_ascii_case(&owner)) {
+                owners.push(owner);
+            }
+        }
+        owners
+    }
+}
+
+/// Find and parse the platform's `CODEOWNERS` in the `trunk()` tree
+pub fn find_codeowners(workspace: &JjWorkspace, platform: Platform) -> Result<Option<CodeOwners>> {
+    for path in codeowners_locations(platform) {
+        if let Some(content) = workspace.read_trunk_file(path)? {
+            return Ok(Some(parse_codeowners(&content)));
+        }
+    }
+    Ok(None)
+}
+
+/// Owners to request on each PR the plan creates, by bookmark name
+///
+/// Each PR's files are those changed between its base and its bookmark.
+/// Bookmarks without owners are absent.
+pub fn codeowner_reviewers(
+    workspace: &JjWorkspace,
+    codeowners: &CodeOwners,
+    plan: &SubmissionPlan,
+) -> Result<HashMap<String, Vec<String>>> {
+    let mut reviewers = HashMap::new();
+    for step in &plan.execution_steps {
+        let ExecutionStep::CreatePr(create) = step else {
+            continue;
+        };
+        let Some(segment) = plan
+            .segments
+            .iter()
+            .find(|s| s.bookmark.name == create.bookmark.name)
+        else {
+            continue;
+        };
+        // Changes are newest first
+        let (Some(last), Some(first)) = (segment.changes.first(), segment.changes.last()) else {
+            continue;
+        };
+
+        let files = workspace.changed_files(&first.commit_id, &last.commit_id)?;
+        let owners = codeowners.owners_of_files(&files);
+        if !owners.is_empty() {
+            reviewers.insert(create.bookmark.name.clone(), owners);
+        }
+    }
+    Ok(reviewers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners(codeowners: &CodeOwners, path: &str) -> Vec<String> {
+        codeowners.owners_of(path)
+    }
+
+    #[test]
+    fn test_github_last_match_wins() {
+        let codeowners = parse_codeowners(
+            "# Default owners\n\
+             *       @global-owner\n\
+             *.js    @js-owner  # trailing comment\n\
+             /docs/  @acme/docs\n\
+             apps/   @app-owner\n\
+             /build/logs/\n",
+        );
+
+        assert_eq!(owners(&codeowners, "README.md"), ["global-owner"]);
+        assert_eq!(owners(&codeowners, "src/app.js"), ["js-owner"]);
+        assert_eq!(owners(&codeowners, "docs/guide/intro.md"), ["acme/docs"]);
+        assert_eq!(owners(&codeowners, "src/apps/main.rs"), ["app-owner"]);
+        // No owners listed: nobody owns it
+        assert!(owners(&codeowners, "build/logs/out.txt").is_empty());
+    }
+
+    #[test]
+    fn test_patterns() {
+        let codeowners = parse_codeowners(
+            "docs/*      @docs\n\
+             **/logs     @logs\n\
+             /src/**/*.rs @rust\n\
+             Make?ile    @make\n\
+             user@example.com-file user@example.com @@developer\n",
+        );
+
+        assert_eq!(owners(&codeowners, "docs/readme.md"), ["docs"]);
+        // `dir/*` doesn't reach into subdirectories
+        assert!(owners(&codeowners, "docs/api/index.md").is_empty());
+        assert_eq!(owners(&codeowners, "var/logs/today/a.log"), ["logs"]);
+        assert_eq!(owners(&codeowners, "src/a/b/lib.rs"), ["rust"]);
+        assert_eq!(owners(&codeowners, "src/lib.rs"), ["rust"]);
+        assert_eq!(owners(&codeowners, "sub/Makefile"), ["make"]);
+        // Emails and roles can't be requested by name
+        assert!(owners(&codeowners, "user@example.com-file").is_empty());
+    }
+
+    #[test]
+    fn test_gitlab_sections_combine() {
+        let codeowners = parse_codeowners(
+            "* @fallback\n\
+             \n\
+             [Frontend] @frontend-lead\n\
+             *.ts\n\
+             /web/legacy/ @legacy-team\n\
+             \n\
+             [Docs][2] @docs-team\n\
+             *.md\n\
+             \n\
+             ^[Optional]\n\
+             * @nobody\n\
+             \n\
+             [frontend]\n\
+             *.css @designer\n",
+        );
+
+        assert_eq!(
+            owners(&codeowners, "web/app.ts"),
+            ["fallback", "frontend-lead"]
+        );
+        assert_eq!(
+            owners(&codeowners, "web/legacy/old.ts"),
+            ["fallback", "legacy-team"]
+        );
+        assert_eq!(owners(&codeowners, "README.md"), ["fallback", "docs-team"]);
+        // Section names are case-insensitive, so `[frontend]` continues it
+        assert_eq!(
+            owners(&codeowners, "web/site.css"),
+            ["fallback", "designer"]
+        );
+    }
+
+    #[test]
+    fn test_owners_of_files_dedupes() {
+        let codeowners = parse_codeowners("*.rs @alice @bob\n*.md @Alice\n");
+        let files = ["src/lib.rs".to_string(), "README.md".to_string()];
+        assert_eq!(codeowners.owners_of_files(&files), ["alice", "bob"]);
+    }
+
+    #[test]
+    fn test_escaped_spaces() {
+        let codeowners = parse_codeowners("/my\\ docs/ @writer\n");
+        assert_eq!(owners(&codeowners, "my docs/a.md"), ["writer"]);
+    }
+}
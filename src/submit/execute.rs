@@ -5,16 +5,22 @@
 use crate::error::{Error, Result};
 use crate::platform::PlatformService;
 use crate::repo::JjWorkspace;
-use crate::submit::plan::{PrBaseUpdate, PrToCreate};
-use crate::submit::{ExecutionStep, Phase, ProgressCallback, PushStatus, SubmissionPlan};
-use crate::types::{Bookmark, PullRequest};
+use crate::submit::journal::{SubmissionJournal, clear_journal, save_journal};
+use crate::submit::plan::{
+    AutoMergeUpdate, PrBaseUpdate, PrMetadataUpdate, PrToCreate, PrTriageUpdate,
+};
+use crate::submit::{
+    ExecutionStep, Phase, ProgressCallback, PushStatus, SubmissionPlan, parent_bookmark,
+};
+use crate::types::{Bookmark, MergeMethod, PullRequest};
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::path::Path;
 
 /// Result of submission execution
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct SubmissionResult {
     /// Whether execution succeeded
     pub success: bool,
@@ -24,10 +30,21 @@ pub struct SubmissionResult {
     pub updated_prs: Vec<PullRequest>,
     /// Bookmarks that were pushed
     pub pushed_bookmarks: Vec<String>,
+    /// Bookmarks whose PRs had auto-merge enabled
+    pub auto_merged: Vec<AutoMerged>,
     /// Errors encountered (non-fatal)
     pub errors: Vec<String>,
 }
 
+/// A PR that had auto-merge enabled during a submission
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AutoMerged {
+    /// Bookmark of the PR
+    pub bookmark: String,
+    /// Method the PR will be merged with
+    pub method: MergeMethod,
+}
+
 impl SubmissionResult {
     /// Create a new successful result
     pub fn new() -> Self {
@@ -82,6 +99,10 @@ pub struct StackItem {
     pub pr_number: u64,
     /// PR title
     pub pr_title: String,
+    /// Bookmark this PR builds on, when the stack branches (`None` for
+    /// the bottom PR; linear stacks leave it out)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
 }
 
 /// Prefix for stack comment data
@@ -104,6 +125,21 @@ pub fn execute_push(workspace: &mut JjWorkspace, bookmark: &Bookmark, remote: &s
     }
 }
 
+/// Execute a push step for a change-based platform (e.g. Gerrit `refs/for/<base>`)
+pub fn execute_review_push(
+    workspace: &mut JjWorkspace,
+    bookmark: &Bookmark,
+    remote: &str,
+    target_ref: &str,
+) -> StepOutcome {
+    match workspace.git_push_for_review(&bookmark.name, remote, target_ref) {
+        Ok(()) => StepOutcome::Success(None),
+        Err(e) => {
+            StepOutcome::FatalError(format!("Failed to push {} for review: {e}", bookmark.name))
+        }
+    }
+}
+
 /// Execute an update base step
 pub async fn execute_update_base(
     platform: &dyn PlatformService,
@@ -125,9 +161,10 @@ pub async fn execute_update_base(
 pub async fn execute_create_pr(platform: &dyn PlatformService, create: &PrToCreate) -> StepOutcome {
     match platform
         .create_pr_with_options(
-            &create.bookmark.name,
+            &platform.review_head(&create.bookmark),
             &create.base_branch,
             &create.title,
+            &create.body,
             create.draft,
         )
         .await
@@ -140,6 +177,82 @@ pub async fn execute_create_pr(platform: &dyn PlatformService, create: &PrToCrea
     }
 }
 
+/// Execute a title/body update step (soft fail on error)
+pub async fn execute_update_metadata(
+    platform: &dyn PlatformService,
+    update: &PrMetadataUpdate,
+) -> StepOutcome {
+    match platform
+        .update_pr_metadata(update.pr.number, &update.title, &update.body)
+        .await
+    {
+        Ok(updated_pr) => StepOutcome::Success(Some((update.bookmark.name.clone(), updated_pr))),
+        Err(e) => StepOutcome::SoftError(format!(
+            "Failed to update title and description of PR #{}: {e}",
+            update.pr.number
+        )),
+    }
+}
+
+/// Execute a reviewer/assignee/label step (soft fail on error)
+///
+/// `pr_number` is the PR's number, known only after creation for new PRs.
+pub async fn execute_add_triage(
+    platform: &dyn PlatformService,
+    update: &PrTriageUpdate,
+    pr_number: Option<u64>,
+) -> StepOutcome {
+    let Some(number) = update.pr_number.or(pr_number) else {
+        return StepOutcome::SoftError(format!(
+            "No PR for {} to add {} to",
+            update.bookmark.name, update.add
+        ));
+    };
+
+    let add = &update.add;
+    let result = async {
+        if !add.reviewers.is_empty() {
+            platform.request_reviewers(number, &add.reviewers).await?;
+        }
+        if !add.assignees.is_empty() {
+            platform.add_assignees(number, &add.assignees).await?;
+        }
+        if !add.labels.is_empty() {
+            platform.add_labels(number, &add.labels).await?;
+        }
+        Ok::<_, Error>(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => StepOutcome::Success(None),
+        Err(e) => StepOutcome::SoftError(format!("Failed to add {add} to PR #{number}: {e}")),
+    }
+}
+
+/// Execute an enable auto-merge step (soft fail on error)
+///
+/// `pr_number` is the PR's number, known only after creation for new PRs.
+pub async fn execute_enable_auto_merge(
+    platform: &dyn PlatformService,
+    update: &AutoMergeUpdate,
+    pr_number: Option<u64>,
+) -> StepOutcome {
+    let Some(number) = update.pr_number.or(pr_number) else {
+        return StepOutcome::SoftError(format!(
+            "No PR for {} to enable auto-merge on",
+            update.bookmark.name
+        ));
+    };
+
+    match platform.enable_auto_merge(number, update.method).await {
+        Ok(()) => StepOutcome::Success(None),
+        Err(e) => {
+            StepOutcome::SoftError(format!("Failed to enable auto-merge on PR #{number}: {e}"))
+        }
+    }
+}
+
 /// Execute a publish PR step (soft fail on error)
 pub async fn execute_publish_pr(platform: &dyn PlatformService, pr: &PullRequest) -> StepOutcome {
     match platform.publish_pr(pr.number).await {
@@ -159,7 +272,13 @@ pub async fn execute_publish_pr(platform: &dyn PlatformService, pr: &PullRequest
 /// 2. Update PR bases
 /// 3. Create new PRs
 /// 4. Publish draft PRs
-/// 5. Add/update stack comments
+/// 5. Update out-of-date PR titles and bodies
+/// 6. Request reviewers, add assignees and labels
+/// 7. Enable auto-merge on the bottom PR
+/// 8. Add/update stack comments
+///
+/// Progress is journaled to `.jj/repo/ryu/` step by step, and the journal is
+/// removed once the submission succeeds; see [`crate::submit::load_journal`].
 pub async fn execute_submission(
     plan: &SubmissionPlan,
     workspace: &mut JjWorkspace,
@@ -180,28 +299,51 @@ pub async fn execute_submission(
     // Track all PRs (existing + created) for comment generation
     let mut bookmark_to_pr: HashMap<String, PullRequest> = plan.existing_prs.clone();
 
+    let workspace_root = workspace.workspace_root().to_path_buf();
+    let mut journal = SubmissionJournal::new(plan);
+    if !plan.execution_steps.is_empty() {
+        record_journal(&workspace_root, &journal, progress).await;
+    }
+
     // Phase: Executing all steps
     progress.on_phase(Phase::Executing).await;
 
-    for step in &plan.execution_steps {
-        let outcome = execute_step(step, workspace, platform, &plan.remote, progress).await;
+    for (idx, step) in plan.execution_steps.iter().enumerate() {
+        let outcome =
+            execute_step(step, workspace, platform, plan, &bookmark_to_pr, progress).await;
+
+        if matches!(outcome, StepOutcome::Success(_)) {
+            journal.mark_done(idx);
+            record_journal(&workspace_root, &journal, progress).await;
+        }
 
         match outcome {
             StepOutcome::Success(Some((bookmark, pr))) => {
                 // Track the PR for comment generation
                 match step {
                     ExecutionStep::CreatePr(_) => result.created_prs.push(pr.clone()),
-                    ExecutionStep::UpdateBase(_) | ExecutionStep::PublishPr(_) => {
+                    ExecutionStep::UpdateBase(_)
+                    | ExecutionStep::PublishPr(_)
+                    | ExecutionStep::UpdatePrMetadata(_) => {
                         result.updated_prs.push(pr.clone());
                     }
-                    ExecutionStep::Push(_) => {}
+                    ExecutionStep::Push(_)
+                    | ExecutionStep::AddTriage(_)
+                    | ExecutionStep::EnableAutoMerge(_) => {}
                 }
                 bookmark_to_pr.insert(bookmark, pr);
             }
             StepOutcome::Success(None) => {
                 // Push succeeded - track it
-                if let ExecutionStep::Push(bm) = step {
-                    result.pushed_bookmarks.push(bm.name.clone());
+                match step {
+                    ExecutionStep::Push(bm) => result.pushed_bookmarks.push(bm.name.clone()),
+                    ExecutionStep::EnableAutoMerge(update) => {
+                        result.auto_merged.push(AutoMerged {
+                            bookmark: update.bookmark.name.clone(),
+                            method: update.method,
+                        });
+                    }
+                    _ => {}
                 }
             }
             StepOutcome::FatalError(msg) => {
@@ -219,7 +361,11 @@ pub async fn execute_submission(
     // Phase: Adding stack comments
     progress.on_phase(Phase::AddingComments).await;
 
-    if !bookmark_to_pr.is_empty() {
+    // Change-based platforms show the stack as a relation chain; their
+    // messages can't be edited, so a stack comment would pile up
+    let change_based = platform.review_push_ref(&plan.default_branch).is_some();
+
+    if !bookmark_to_pr.is_empty() && !change_based {
         let stack_data = build_stack_comment_data(plan, &bookmark_to_pr);
 
         for (idx, item) in stack_data.stack.iter().enumerate() {
@@ -236,17 +382,35 @@ pub async fn execute_submission(
         }
     }
 
+    if result.success
+        && let Err(e) = clear_journal(&workspace_root)
+    {
+        progress.on_error(&e).await;
+    }
+
     progress.on_phase(Phase::Complete).await;
 
     Ok(result)
 }
 
+/// Persist the journal; failing to write it doesn't stop the submission
+async fn record_journal(
+    workspace_root: &Path,
+    journal: &SubmissionJournal,
+    progress: &dyn ProgressCallback,
+) {
+    if let Err(e) = save_journal(workspace_root, journal) {
+        progress.on_error(&e).await;
+    }
+}
+
 /// Execute a single step with progress reporting
 async fn execute_step(
     step: &ExecutionStep,
     workspace: &mut JjWorkspace,
     platform: &dyn PlatformService,
-    remote: &str,
+    plan: &SubmissionPlan,
+    bookmark_to_pr: &HashMap<String, PullRequest>,
     progress: &dyn ProgressCallback,
 ) -> StepOutcome {
     match step {
@@ -255,7 +419,12 @@ async fn execute_step(
                 .on_bookmark_push(&bookmark.name, PushStatus::Started)
                 .await;
 
-            let outcome = execute_push(workspace, bookmark, remote);
+            let outcome = match platform.review_push_ref(&plan.default_branch) {
+                Some(target_ref) => {
+                    execute_review_push(workspace, bookmark, &plan.remote, &target_ref)
+                }
+                None => execute_push(workspace, bookmark, &plan.remote),
+            };
 
             match &outcome {
                 StepOutcome::Success(_) => {
@@ -315,6 +484,51 @@ async fn execute_step(
 
             execute_publish_pr(platform, pr).await
         }
+
+        ExecutionStep::UpdatePrMetadata(update) => {
+            progress
+                .on_message(&format!(
+                    "Updating PR #{} ({}) title and description",
+                    update.pr.number, update.bookmark.name
+                ))
+                .await;
+
+            let outcome = execute_update_metadata(platform, update).await;
+
+            if let StepOutcome::Success(Some((bookmark, pr))) = &outcome {
+                progress.on_pr_updated(bookmark, pr).await;
+            }
+
+            outcome
+        }
+
+        ExecutionStep::AddTriage(update) => {
+            progress
+                .on_message(&format!(
+                    "Adding {} to {}",
+                    update.add, update.bookmark.name
+                ))
+                .await;
+
+            let created = bookmark_to_pr
+                .get(&update.bookmark.name)
+                .map(|pr| pr.number);
+            execute_add_triage(platform, update, created).await
+        }
+
+        ExecutionStep::EnableAutoMerge(update) => {
+            progress
+                .on_message(&format!(
+                    "Enabling auto-merge ({}) on {}",
+                    update.method, update.bookmark.name
+                ))
+                .await;
+
+            let created = bookmark_to_pr
+                .get(&update.bookmark.name)
+                .map(|pr| pr.number);
+            execute_enable_auto_merge(platform, update, created).await
+        }
     }
 }
 
@@ -329,6 +543,15 @@ async fn report_dry_run(plan: &SubmissionPlan, progress: &dyn ProgressCallback)
         return;
     }
 
+    if let Some(pr_remote) = &plan.pr_remote {
+        progress
+            .on_message(&format!(
+                "Pushing to {}, opening PRs against {pr_remote}",
+                plan.remote
+            ))
+            .await;
+    }
+
     progress.on_message("Would execute:").await;
     for step in &plan.execution_steps {
         let msg = format_step_for_dry_run(step, &plan.remote);
@@ -356,7 +579,7 @@ pub fn build_stack_comment_data(
     plan: &SubmissionPlan,
     bookmark_to_pr: &HashMap<String, PullRequest>,
 ) -> StackCommentData {
-    let stack: Vec<StackItem> = plan
+    let mut stack: Vec<StackItem> = plan
         .segments
         .iter()
         .filter_map(|seg| {
@@ -365,10 +588,24 @@ pub fn build_stack_comment_data(
                 pr_url: pr.html_url.clone(),
                 pr_number: pr.number,
                 pr_title: pr.title.clone(),
+                parent: parent_bookmark(&seg.bookmark.name, &plan.segments, &plan.parents)
+                    .ok()
+                    .flatten()
+                    .map(String::from),
             })
         })
         .collect();
 
+    // A linear stack's order says it all
+    let linear = stack.iter().enumerate().all(|(i, item)| {
+        item.parent.as_deref() == i.checked_sub(1).map(|p| stack[p].bookmark_name.as_str())
+    });
+    if linear {
+        for item in &mut stack {
+            item.parent = None;
+        }
+    }
+
     StackCommentData {
         version: 1,
         stack,
@@ -385,6 +622,22 @@ pub fn format_stack_comment(data: &StackCommentData, current_idx: usize) -> Resu
 
     let mut body = format!("{COMMENT_DATA_PREFIX}{encoded_data}{COMMENT_DATA_POSTFIX}\n");
 
+    if data.stack.iter().any(|item| item.parent.is_some()) {
+        write_stack_tree(&mut body, data, current_idx);
+    } else {
+        write_stack_list(&mut body, data, current_idx);
+    }
+
+    let _ = write!(
+        body,
+        "\n---\nThis stack of pull requests is managed by [jj-ryu](https://github.com/dmmulroy/jj-ryu)."
+    );
+
+    Ok(body)
+}
+
+/// Write a linear stack, leaf first, with the base branch at the bottom
+fn write_stack_list(body: &mut String, data: &StackCommentData, current_idx: usize) {
     // Reverse order: newest/leaf at top, oldest at bottom
     // Format: "* PR title #N" with current PR marked with 👈 and bold
     let reversed_idx = data.stack.len() - 1 - current_idx;
@@ -402,13 +655,48 @@ pub fn format_stack_comment(data: &StackCommentData, current_idx: usize) -> Resu
 
     // Add base branch at bottom
     let _ = writeln!(body, "* `{}`", data.base_branch);
+}
 
-    let _ = write!(
-        body,
-        "\n---\nThis stack of pull requests is managed by [jj-ryu](https://github.com/dmmulroy/jj-ryu)."
-    );
+/// Write a branching stack as a nested list under the base branch
+///
+/// Each PR is nested under the one it builds on; PRs whose parent isn't in
+/// the stack sit directly under the base branch.
+fn write_stack_tree(body: &mut String, data: &StackCommentData, current_idx: usize) {
+    let _ = writeln!(body, "* `{}`", data.base_branch);
+    write_stack_children(body, data, current_idx, None, 1);
+}
 
-    Ok(body)
+/// Write the PRs built on `parent` (`None` for the base branch), then theirs
+fn write_stack_children(
+    body: &mut String,
+    data: &StackCommentData,
+    current_idx: usize,
+    parent: Option<&str>,
+    depth: usize,
+) {
+    let in_stack = |name: &&str| data.stack.iter().any(|item| item.bookmark_name == *name);
+    let indent = "  ".repeat(depth);
+    for (i, item) in data.stack.iter().enumerate() {
+        if item.parent.as_deref().filter(in_stack) != parent {
+            continue;
+        }
+        if i == current_idx {
+            let _ = writeln!(
+                body,
+                "{indent}* **{} #{} {STACK_COMMENT_THIS_PR}**",
+                item.pr_title, item.pr_number
+            );
+        } else {
+            let _ = writeln!(body, "{indent}* {} #{}", item.pr_title, item.pr_number);
+        }
+        write_stack_children(
+            body,
+            data,
+            current_idx,
+            Some(&item.bookmark_name),
+            depth + 1,
+        );
+    }
 }
 
 /// Create or update the stack comment on a PR
@@ -453,6 +741,7 @@ mod tests {
             base_ref: "main".to_string(),
             head_ref: bookmark.to_string(),
             title: format!("PR for {bookmark}"),
+            body: String::new(),
             node_id: Some(format!("PR_node_{number}")),
             is_draft: false,
         }
@@ -497,6 +786,21 @@ mod tests {
         assert_eq!(result.errors.len(), 1);
     }
 
+    #[test]
+    fn test_submission_result_auto_merged_json() {
+        let mut result = SubmissionResult::new();
+        result.auto_merged.push(AutoMerged {
+            bookmark: "feat-a".to_string(),
+            method: MergeMethod::Squash,
+        });
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json["auto_merged"],
+            serde_json::json!([{ "bookmark": "feat-a", "method": "squash" }])
+        );
+    }
+
     // === StepOutcome tests ===
 
     #[test]
@@ -541,6 +845,7 @@ mod tests {
             bookmark: bm,
             base_branch: "main".to_string(),
             title: "Add feature".to_string(),
+            body: String::new(),
             draft: false,
         };
         let step = ExecutionStep::CreatePr(create);
@@ -555,6 +860,7 @@ mod tests {
             bookmark: bm,
             base_branch: "main".to_string(),
             title: "Add feature".to_string(),
+            body: String::new(),
             draft: true,
         };
         let step = ExecutionStep::CreatePr(create);
@@ -599,10 +905,12 @@ mod tests {
                     changes: vec![],
                 },
             ],
+            parents: HashMap::new(),
             constraints: vec![],
             execution_steps: vec![],
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
@@ -635,10 +943,12 @@ mod tests {
                     changes: vec![],
                 },
             ],
+            parents: HashMap::new(),
             constraints: vec![],
             execution_steps: vec![],
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
@@ -662,12 +972,14 @@ mod tests {
                     pr_url: "https://example.com/1".to_string(),
                     pr_number: 1,
                     pr_title: "feat: add auth".to_string(),
+                    parent: None,
                 },
                 StackItem {
                     bookmark_name: "feat-b".to_string(),
                     pr_url: "https://example.com/2".to_string(),
                     pr_number: 2,
                     pr_title: "feat: add sessions".to_string(),
+                    parent: None,
                 },
             ],
             base_branch: "main".to_string(),
@@ -688,6 +1000,7 @@ mod tests {
                 pr_url: "https://example.com/1".to_string(),
                 pr_number: 1,
                 pr_title: "feat: add auth".to_string(),
+                parent: None,
             }],
             base_branch: "main".to_string(),
         };
@@ -697,16 +1010,82 @@ mod tests {
         assert!(body.contains(COMMENT_DATA_POSTFIX));
     }
 
+    #[test]
+    fn test_stack_comment_renders_branching_stack() {
+        let segment = |name: &str| NarrowedBookmarkSegment {
+            bookmark: make_bookmark(name),
+            changes: vec![],
+        };
+        let plan = SubmissionPlan {
+            segments: vec![segment("feat-a"), segment("feat-b"), segment("feat-c")],
+            parents: HashMap::from([
+                ("feat-b".to_string(), "feat-a".to_string()),
+                ("feat-c".to_string(), "feat-a".to_string()),
+            ]),
+            constraints: vec![],
+            execution_steps: vec![],
+            existing_prs: HashMap::new(),
+            remote: "origin".to_string(),
+            pr_remote: None,
+            default_branch: "main".to_string(),
+        };
+        let bookmark_to_pr: HashMap<_, _> = ["feat-a", "feat-b", "feat-c"]
+            .into_iter()
+            .zip(1..)
+            .map(|(name, number)| (name.to_string(), make_pr(number, name)))
+            .collect();
+
+        let data = build_stack_comment_data(&plan, &bookmark_to_pr);
+        assert_eq!(data.stack[0].parent, None);
+        assert_eq!(data.stack[2].parent.as_deref(), Some("feat-a"));
+
+        let body = format_stack_comment(&data, 2).unwrap();
+        assert!(body.contains(
+            "* `main`\n  * PR for feat-a #1\n    * PR for feat-b #2\n    * **PR for feat-c #3 👈**\n"
+        ));
+    }
+
+    #[test]
+    fn test_build_stack_comment_data_linear_has_no_parents() {
+        let plan = SubmissionPlan {
+            segments: vec![
+                NarrowedBookmarkSegment {
+                    bookmark: make_bookmark("feat-a"),
+                    changes: vec![],
+                },
+                NarrowedBookmarkSegment {
+                    bookmark: make_bookmark("feat-b"),
+                    changes: vec![],
+                },
+            ],
+            parents: HashMap::from([("feat-b".to_string(), "feat-a".to_string())]),
+            constraints: vec![],
+            execution_steps: vec![],
+            existing_prs: HashMap::new(),
+            remote: "origin".to_string(),
+            pr_remote: None,
+            default_branch: "main".to_string(),
+        };
+        let mut bookmark_to_pr = HashMap::new();
+        bookmark_to_pr.insert("feat-a".to_string(), make_pr(1, "feat-a"));
+        bookmark_to_pr.insert("feat-b".to_string(), make_pr(2, "feat-b"));
+
+        let data = build_stack_comment_data(&plan, &bookmark_to_pr);
+        assert!(data.stack.iter().all(|item| item.parent.is_none()));
+    }
+
     // === Plan helper tests ===
 
     #[test]
     fn test_plan_is_empty() {
         let plan = SubmissionPlan {
             segments: vec![],
+            parents: HashMap::new(),
             constraints: vec![],
             execution_steps: vec![],
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
@@ -728,11 +1107,14 @@ mod tests {
                     bookmark: bm,
                     base_branch: "main".to_string(),
                     title: "Add feat-a".to_string(),
+                    body: String::new(),
                     draft: false,
                 }),
             ],
+            parents: HashMap::new(),
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
@@ -0,0 +1,297 @@
+//! Detection of merged and closed PRs for sync
+//!
+//! After the bottom of a stack lands, its bookmarks still sit in
+//! `trunk()..@` (squash and rebase merges put different commits in trunk),
+//! and the next PR still targets the landed branch. Sync asks the platform
+//! which tracked bookmarks' PRs have finished and works out which changes can
+//! be dropped from the stack.
+
+use crate::error::Result;
+use crate::platform::PlatformService;
+use crate::tracking::TrackedBookmark;
+use crate::types::{Bookmark, ChangeGraph, LogEntry, PullRequest};
+use std::collections::{BTreeSet, HashSet};
+use tracing::debug;
+
+/// A tracked bookmark whose PR is no longer open
+#[derive(Debug, Clone)]
+pub struct FinishedBookmark {
+    /// Bookmark name
+    pub bookmark: String,
+    /// The PR as it was when it was merged or closed
+    pub pr: PullRequest,
+    /// Whether the PR was merged (as opposed to closed without merging)
+    pub merged: bool,
+    /// Commit the bookmark (or its tracked change) is at in the stack, if any
+    pub commit_id: Option<String>,
+}
+
+/// Finished PRs found for a stack, and what sync does about them
+#[derive(Debug, Clone, Default)]
+pub struct FinishedPrs {
+    /// PRs merged into trunk at the bottom of the stack, bottom first
+    ///
+    /// Their changes are dropped from the stack, which is rebased onto trunk.
+    pub landed: Vec<FinishedBookmark>,
+    /// PRs whose bookmarks are only untracked: closed without merging, or
+    /// merged from a bookmark that is no longer in the stack
+    pub untracked: Vec<FinishedBookmark>,
+    /// Merged PRs left alone, because they merged into a branch other than
+    /// trunk or sit above a bookmark whose PR is still open
+    pub skipped: Vec<FinishedBookmark>,
+    /// Commits dropped from the stack, oldest first
+    pub landed_commits: Vec<String>,
+    /// Local bookmarks on the dropped commits, which are deleted with them
+    pub deleted_bookmarks: Vec<String>,
+}
+
+impl FinishedPrs {
+    /// Whether there is nothing to clean up
+    pub const fn is_empty(&self) -> bool {
+        self.landed.is_empty() && self.untracked.is_empty() && self.skipped.is_empty()
+    }
+
+    /// Bookmarks to stop tracking (landed and untracked)
+    pub fn untrack_names(&self) -> Vec<&str> {
+        self.landed
+            .iter()
+            .chain(&self.untracked)
+            .map(|f| f.bookmark.as_str())
+            .collect()
+    }
+}
+
+/// Find tracked bookmarks whose PRs were merged or closed
+///
+/// Bookmarks in the stack are checked once they have been pushed; tracked
+/// bookmarks missing from the stack (deleted by a fetch after the remote
+/// branch was removed) are found through their tracked change. Bookmarks with
+/// an open PR are skipped.
+///
+/// A PR has landed when it merged into `default_branch` and so did every
+/// bookmark below it on its branch of the stack, tracked or not. Commits are
+/// never compared with trunk, so squash and rebase merges are detected the
+/// same way as merge commits.
+#[allow(clippy::too_many_lines)]
+pub async fn find_finished_prs(
+    graph: &ChangeGraph,
+    tracked: &[TrackedBookmark],
+    platform: &dyn PlatformService,
+    default_branch: &str,
+) -> Result<FinishedPrs> {
+    let (changes, below) = stack_changes(graph);
+
+    // Stack positions of bookmarks that still have an open PR (or never had one)
+    let mut blocking: Vec<usize> = Vec::new();
+
+    // Each candidate with its position in the stack, if it's still there
+    let mut candidates: Vec<(Bookmark, Option<usize>, &TrackedBookmark)> = Vec::new();
+    for tracked_bookmark in tracked {
+        if let Some(bookmark) = graph.bookmarks.get(&tracked_bookmark.name) {
+            let position = changes
+                .iter()
+                .position(|c| c.commit_id == bookmark.commit_id);
+            // Never pushed, so there can't be a PR
+            if !bookmark.has_remote {
+                blocking.extend(position);
+                continue;
+            }
+            candidates.push((bookmark.clone(), position, tracked_bookmark));
+        } else {
+            let position = changes
+                .iter()
+                .position(|c| c.change_id == tracked_bookmark.change_id);
+            candidates.push((
+                Bookmark {
+                    name: tracked_bookmark.name.clone(),
+                    commit_id: position
+                        .map(|i| changes[i].commit_id.clone())
+                        .unwrap_or_default(),
+                    change_id: tracked_bookmark.change_id.clone(),
+                    has_remote: false,
+                    is_synced: false,
+                },
+                position,
+                tracked_bookmark,
+            ));
+        }
+    }
+
+    // Untracked bookmarks in the stack hold work of their own, unless their
+    // PR merged too
+    let mut untracked: Vec<(&Bookmark, usize)> = Vec::new();
+    for bookmark in graph.bookmarks.values() {
+        if tracked.iter().any(|t| t.name == bookmark.name) {
+            continue;
+        }
+        let Some(position) = changes
+            .iter()
+            .position(|c| c.commit_id == bookmark.commit_id)
+        else {
+            continue;
+        };
+        if bookmark.has_remote {
+            untracked.push((bookmark, position));
+        } else {
+            blocking.push(position);
+        }
+    }
+
+    if candidates.is_empty() {
+        return Ok(FinishedPrs::default());
+    }
+
+    let heads: Vec<String> = candidates
+        .iter()
+        .map(|(b, _, _)| platform.review_head(b))
+        .collect();
+    let untracked_heads: Vec<String> = untracked
+        .iter()
+        .map(|(b, _)| platform.review_head(b))
+        .collect();
+    let head_refs: Vec<&str> = heads
+        .iter()
+        .chain(&untracked_heads)
+        .map(String::as_str)
+        .collect();
+    let open = platform.find_existing_prs(&head_refs).await?;
+
+    for ((_, position), head) in untracked.iter().zip(&untracked_heads) {
+        let merged = if open.contains_key(head) {
+            false
+        } else {
+            platform
+                .find_closed_pr(head)
+                .await?
+                .is_some_and(|c| c.merged && c.pr.base_ref == default_branch)
+        };
+        if !merged {
+            blocking.push(*position);
+        }
+    }
+
+    let mut finished: Vec<(FinishedBookmark, Option<usize>)> = Vec::new();
+    for ((bookmark, position, tracked_bookmark), head) in candidates.into_iter().zip(&heads) {
+        let closed = if open.contains_key(head) {
+            None
+        } else {
+            platform.find_closed_pr(head).await?
+        };
+        // A PR that finished before the bookmark was tracked belongs to an
+        // earlier branch of the same name
+        let closed = closed.filter(|c| {
+            c.closed_at
+                .is_none_or(|closed_at| closed_at >= tracked_bookmark.tracked_at)
+        });
+
+        match closed {
+            Some(closed) => {
+                debug!(bookmark = %bookmark.name, pr = closed.pr.number, merged = closed.merged, "found finished PR");
+                finished.push((
+                    FinishedBookmark {
+                        commit_id: position.map(|_| bookmark.commit_id.clone()),
+                        bookmark: bookmark.name,
+                        pr: closed.pr,
+                        merged: closed.merged,
+                    },
+                    position,
+                ));
+            }
+            None => blocking.extend(position),
+        }
+    }
+
+    Ok(classify(
+        finished,
+        &blocking,
+        &changes,
+        &below,
+        graph,
+        default_branch,
+    ))
+}
+
+/// Stack changes from trunk upwards, with the position of the change below
+/// each one on its branch of the stack
+fn stack_changes(graph: &ChangeGraph) -> (Vec<&LogEntry>, Vec<Option<usize>>) {
+    let mut changes: Vec<&LogEntry> = Vec::new();
+    let mut below: Vec<Option<usize>> = Vec::new();
+    if let Some(stack) = &graph.stack {
+        let mut tops: Vec<Option<usize>> = Vec::new();
+        for (index, segment) in stack.segments.iter().enumerate() {
+            let mut previous = stack.parent_index(index).and_then(|parent| tops[parent]);
+            for change in segment.changes.iter().rev() {
+                below.push(previous);
+                previous = Some(changes.len());
+                changes.push(change);
+            }
+            tops.push(previous);
+        }
+    }
+    (changes, below)
+}
+
+/// Split finished bookmarks into landed, untracked and skipped
+///
+/// `blocking` holds the stack positions of bookmarks that haven't finished,
+/// and `below` the position of the change below each one on its branch of
+/// the stack. A merged bookmark lands only if nothing below it, on its own
+/// branch, is blocking; a sibling branch never holds it back, and is never
+/// dropped with it.
+fn classify(
+    mut finished: Vec<(FinishedBookmark, Option<usize>)>,
+    blocking: &[usize],
+    changes: &[&LogEntry],
+    below: &[Option<usize>],
+    graph: &ChangeGraph,
+    default_branch: &str,
+) -> FinishedPrs {
+    let mut blocking: HashSet<usize> = blocking.iter().copied().collect();
+    for (f, position) in &finished {
+        if let Some(position) = *position
+            && !(f.merged && f.pr.base_ref == default_branch)
+        {
+            blocking.insert(position);
+        }
+    }
+
+    // A change and every change below it on its branch
+    let path = |from: usize| {
+        let mut next = Some(from);
+        std::iter::from_fn(move || {
+            let position = next?;
+            next = below[position];
+            Some(position)
+        })
+    };
+
+    let mut result = FinishedPrs::default();
+    let mut landed: BTreeSet<usize> = BTreeSet::new();
+    finished.sort_by_key(|(_, position)| *position);
+    for (f, position) in finished {
+        match position {
+            Some(position) if f.merged && path(position).all(|p| !blocking.contains(&p)) => {
+                landed.extend(path(position));
+                result.landed.push(f);
+            }
+            Some(_) if f.merged => result.skipped.push(f),
+            _ => result.untracked.push(f),
+        }
+    }
+
+    result.landed_commits = landed
+        .iter()
+        .map(|&p| changes[p].commit_id.clone())
+        .collect();
+    let landed: HashSet<&str> = result.landed_commits.iter().map(String::as_str).collect();
+    let mut deleted: Vec<String> = graph
+        .bookmarks
+        .values()
+        .filter(|b| landed.contains(b.commit_id.as_str()))
+        .map(|b| b.name.clone())
+        .collect();
+    deleted.sort();
+    result.deleted_bookmarks = deleted;
+
+    result
+}
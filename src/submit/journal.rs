@@ -0,0 +1,379 @@
+//! Execution journal for resumable submissions
+//!
+//! Before executing a plan, the steps are written to
+//! `.jj/repo/ryu/submit_journal.json` and each one is marked done as it
+//! completes. A successful run deletes the journal, so a journal left on disk
+//! means the previous run died or failed partway through; `ryu submit
+//! --resume` picks it up from there.
+
+use crate::error::{Error, Result};
+use crate::submit::{ExecutionStep, SubmissionPlan};
+use crate::types::{MergeMethod, PrTriage};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Current version of the journal file format.
+pub const JOURNAL_VERSION: u32 = 1;
+
+/// Filename for the execution journal.
+const JOURNAL_FILE: &str = "submit_journal.json";
+
+/// Kind of a journaled execution step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStepKind {
+    /// Push bookmark to remote
+    Push,
+    /// Update PR base branch
+    UpdateBase,
+    /// Create a new PR
+    CreatePr,
+    /// Publish a draft PR
+    PublishPr,
+    /// Update a PR's title and body
+    UpdatePrMetadata,
+    /// Request reviewers and add assignees and labels
+    AddTriage,
+    /// Enable auto-merge on the bottom PR
+    EnableAutoMerge,
+}
+
+impl JournalStepKind {
+    /// Kind of an execution step
+    pub const fn of(step: &ExecutionStep) -> Self {
+        match step {
+            ExecutionStep::Push(_) => Self::Push,
+            ExecutionStep::UpdateBase(_) => Self::UpdateBase,
+            ExecutionStep::CreatePr(_) => Self::CreatePr,
+            ExecutionStep::PublishPr(_) => Self::PublishPr,
+            ExecutionStep::UpdatePrMetadata(_) => Self::UpdatePrMetadata,
+            ExecutionStep::AddTriage(_) => Self::AddTriage,
+            ExecutionStep::EnableAutoMerge(_) => Self::EnableAutoMerge,
+        }
+    }
+}
+
+/// A step of the journaled plan.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JournalStep {
+    /// What the step does.
+    pub kind: JournalStepKind,
+    /// Bookmark the step acts on.
+    pub bookmark: String,
+    /// Human-readable description (the step's `Display`).
+    pub description: String,
+    /// Whether the step completed.
+    pub done: bool,
+}
+
+impl JournalStep {
+    /// Whether this entry records `step`
+    pub fn matches(&self, step: &ExecutionStep) -> bool {
+        self.kind == JournalStepKind::of(step) && self.bookmark == step.bookmark_name()
+    }
+}
+
+/// A submission in progress.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionJournal {
+    /// File format version.
+    pub version: u32,
+    /// When execution started.
+    pub started_at: DateTime<Utc>,
+    /// Remote bookmarks are pushed to.
+    pub remote: String,
+    /// Remote PRs are opened against, when different from `remote`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_remote: Option<String>,
+    /// Bookmarks in the submission, root to leaf.
+    pub bookmarks: Vec<String>,
+    /// Whether new PRs were being created as drafts.
+    #[serde(default)]
+    pub draft: bool,
+    /// Whether draft PRs were being published.
+    #[serde(default)]
+    pub publish: bool,
+    /// Reviewers, assignees and labels being added, by bookmark.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub triage: BTreeMap<String, PrTriage>,
+    /// Auto-merge being enabled, by bookmark.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub auto_merge: BTreeMap<String, MergeMethod>,
+    /// Planned steps, in execution order.
+    pub steps: Vec<JournalStep>,
+}
+
+impl SubmissionJournal {
+    /// Journal for a plan about to be executed
+    pub fn new(plan: &SubmissionPlan) -> Self {
+        let steps = plan
+            .execution_steps
+            .iter()
+            .map(|step| JournalStep {
+                kind: JournalStepKind::of(step),
+                bookmark: step.bookmark_name().to_string(),
+                description: step.to_string(),
+                done: false,
+            })
+            .collect();
+
+        Self {
+            version: JOURNAL_VERSION,
+            started_at: Utc::now(),
+            remote: plan.remote.clone(),
+            pr_remote: plan.pr_remote.clone(),
+            bookmarks: plan
+                .segments
+                .iter()
+                .map(|s| s.bookmark.name.clone())
+                .collect(),
+            draft: plan
+                .execution_steps
+                .iter()
+                .any(|step| matches!(step, ExecutionStep::CreatePr(c) if c.draft)),
+            publish: plan
+                .execution_steps
+                .iter()
+                .any(|step| matches!(step, ExecutionStep::PublishPr(_))),
+            triage: plan
+                .execution_steps
+                .iter()
+                .filter_map(|step| match step {
+                    ExecutionStep::AddTriage(update) => {
+                        Some((update.bookmark.name.clone(), update.add.clone()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            auto_merge: plan
+                .execution_steps
+                .iter()
+                .filter_map(|step| match step {
+                    ExecutionStep::EnableAutoMerge(update) => {
+                        Some((update.bookmark.name.clone(), update.method))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            steps,
+        }
+    }
+
+    /// Number of completed steps
+    pub fn completed(&self) -> usize {
+        self.steps.iter().filter(|s| s.done).count()
+    }
+
+    /// First step that didn't complete
+    pub fn first_unfinished(&self) -> Option<&JournalStep> {
+        self.steps.iter().find(|s| !s.done)
+    }
+
+    /// Mark the step at `index` (in plan order) as done
+    pub fn mark_done(&mut self, index: usize) {
+        if let Some(step) = self.steps.get_mut(index) {
+            step.done = true;
+        }
+    }
+
+    /// Whether the journal records `step` as completed
+    pub fn is_done(&self, step: &ExecutionStep) -> bool {
+        self.steps.iter().any(|s| s.done && s.matches(step))
+    }
+}
+
+/// Get path to the execution journal.
+pub fn journal_path(workspace_root: &Path) -> PathBuf {
+    workspace_root
+        .join(".jj")
+        .join("repo")
+        .join("ryu")
+        .join(JOURNAL_FILE)
+}
+
+/// Load the journal of an unfinished submission.
+///
+/// Returns `None` if the previous submission finished (or none ran).
+pub fn load_journal(workspace_root: &Path) -> Result<Option<SubmissionJournal>> {
+    let path = journal_path(workspace_root);
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| Error::Tracking(format!("failed to read {}: {e}", path.display())))?;
+
+    let journal: SubmissionJournal = serde_json::from_str(&content)
+        .map_err(|e| Error::Tracking(format!("failed to parse {}: {e}", path.display())))?;
+
+    if journal.version > JOURNAL_VERSION {
+        return Err(Error::Tracking(format!(
+            "{} was written by a newer ryu (version {}); delete it to continue",
+            path.display(),
+            journal.version
+        )));
+    }
+
+    Ok(Some(journal))
+}
+
+/// Save the journal to disk.
+///
+/// Creates the `.jj/repo/ryu/` directory if it doesn't exist.
+pub fn save_journal(workspace_root: &Path, journal: &SubmissionJournal) -> Result<()> {
+    let path = journal_path(workspace_root);
+    let dir = path.parent().expect("path has parent");
+
+    if !dir.exists() {
+        fs::create_dir_all(dir)
+            .map_err(|e| Error::Tracking(format!("failed to create {}: {e}", dir.display())))?;
+    }
+
+    let content = serde_json::to_string_pretty(journal)
+        .map_err(|e| Error::Tracking(format!("failed to serialize journal: {e}")))?;
+
+    // Write-then-rename so a crash mid-write never leaves a torn journal
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content)
+        .map_err(|e| Error::Tracking(format!("failed to write {}: {e}", tmp.display())))?;
+    fs::rename(&tmp, &path)
+        .map_err(|e| Error::Tracking(format!("failed to write {}: {e}", path.display())))?;
+
+    Ok(())
+}
+
+/// Delete the journal once a submission has finished.
+pub fn clear_journal(workspace_root: &Path) -> Result<()> {
+    let path = journal_path(workspace_root);
+
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(Error::Tracking(format!(
+            "failed to remove {}: {e}",
+            path.display()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::submit::PrToCreate;
+    use crate::types::{Bookmark, NarrowedBookmarkSegment};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn setup_fake_jj_workspace() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".jj").join("repo")).unwrap();
+        temp
+    }
+
+    fn make_bookmark(name: &str) -> Bookmark {
+        Bookmark {
+            name: name.to_string(),
+            commit_id: format!("{name}_commit"),
+            change_id: format!("{name}_change"),
+            has_remote: false,
+            is_synced: false,
+        }
+    }
+
+    fn make_plan() -> SubmissionPlan {
+        let a = make_bookmark("feat-a");
+        let b = make_bookmark("feat-b");
+        SubmissionPlan {
+            segments: vec![
+                NarrowedBookmarkSegment {
+                    bookmark: a.clone(),
+                    changes: vec![],
+                },
+                NarrowedBookmarkSegment {
+                    bookmark: b.clone(),
+                    changes: vec![],
+                },
+            ],
+            parents: HashMap::new(),
+            constraints: vec![],
+            execution_steps: vec![
+                ExecutionStep::Push(a.clone()),
+                ExecutionStep::Push(b.clone()),
+                ExecutionStep::CreatePr(PrToCreate {
+                    bookmark: a,
+                    base_branch: "main".to_string(),
+                    title: "Add a".to_string(),
+                    body: String::new(),
+                    draft: true,
+                }),
+                ExecutionStep::CreatePr(PrToCreate {
+                    bookmark: b,
+                    base_branch: "feat-a".to_string(),
+                    title: "Add b".to_string(),
+                    body: String::new(),
+                    draft: true,
+                }),
+            ],
+            existing_prs: HashMap::new(),
+            remote: "origin".to_string(),
+            pr_remote: None,
+            default_branch: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_journal_path() {
+        let temp = setup_fake_jj_workspace();
+        let path = journal_path(temp.path());
+        assert!(path.ends_with(".jj/repo/ryu/submit_journal.json"));
+    }
+
+    #[test]
+    fn test_journal_records_plan() {
+        let plan = make_plan();
+        let mut journal = SubmissionJournal::new(&plan);
+
+        assert_eq!(journal.bookmarks, vec!["feat-a", "feat-b"]);
+        assert!(journal.draft);
+        assert!(!journal.publish);
+        assert_eq!(journal.steps.len(), 4);
+        assert_eq!(journal.completed(), 0);
+
+        journal.mark_done(0);
+        journal.mark_done(1);
+        assert_eq!(journal.completed(), 2);
+        assert!(journal.is_done(&plan.execution_steps[1]));
+        assert!(!journal.is_done(&plan.execution_steps[2]));
+
+        let next = journal.first_unfinished().unwrap();
+        assert_eq!(next.kind, JournalStepKind::CreatePr);
+        assert_eq!(next.bookmark, "feat-a");
+    }
+
+    #[test]
+    fn test_load_missing_journal_returns_none() {
+        let temp = setup_fake_jj_workspace();
+        assert!(load_journal(temp.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_roundtrip_and_clear() {
+        let temp = setup_fake_jj_workspace();
+        let mut journal = SubmissionJournal::new(&make_plan());
+        journal.mark_done(0);
+
+        save_journal(temp.path(), &journal).unwrap();
+        let loaded = load_journal(temp.path()).unwrap().unwrap();
+        assert_eq!(loaded.steps, journal.steps);
+        assert_eq!(loaded.remote, "origin");
+
+        clear_journal(temp.path()).unwrap();
+        assert!(load_journal(temp.path()).unwrap().is_none());
+        // Clearing twice is fine
+        clear_journal(temp.path()).unwrap();
+    }
+}
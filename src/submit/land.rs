@@ -0,0 +1,235 @@
+//! Landing planner: merging the bottom of a stack
+//!
+//! `ryu land` merges PRs from the bottom of the stack, one at a time. Only the
+//! lowest PR targets trunk, so after each merge the next PR is retargeted and
+//! the rest of the stack is rebased onto trunk and pushed before it can merge
+//! in turn. The planner works out which PRs to land and which stay open.
+//!
+//! With `ryu land --queue` the bottom PR goes into the platform's merge queue
+//! instead, and `ryu sync` later checks on the PRs ryu queued.
+
+use crate::error::{Error, Result};
+use crate::platform::PlatformService;
+use crate::submit::{analyze_submission, parent_bookmark};
+use crate::types::{ChangeGraph, MergeQueueEntry, MergeQueueState, Mergeability, PullRequest};
+use serde::Serialize;
+
+/// Config key for the merge method: `merge`, `squash` (the default) or `rebase`
+pub const MERGE_METHOD_CONFIG_KEY: &str = "ryu.merge-method";
+
+/// A bookmark in the stack and its open PR
+#[derive(Debug, Clone, Serialize)]
+pub struct LandTarget {
+    /// Bookmark the PR is opened from
+    pub bookmark: String,
+    /// The open PR
+    pub pr: PullRequest,
+    /// Commits of the bookmark's segment, newest first
+    pub commit_ids: Vec<String>,
+    /// Local bookmarks on those commits, which are deleted once they land
+    pub bookmarks: Vec<String>,
+    /// Bookmark the PR builds on, `None` for the bottom of the stack
+    pub parent: Option<String>,
+}
+
+/// PRs to land and PRs left open above them
+#[derive(Debug, Clone, Serialize)]
+pub struct LandPlan {
+    /// PRs to merge, bottom of the stack first
+    pub targets: Vec<LandTarget>,
+    /// Open PRs above the targets, bottom first
+    ///
+    /// They are rebased onto trunk and pushed once the targets land.
+    pub rest: Vec<LandTarget>,
+}
+
+impl LandPlan {
+    /// PR that targets trunk once the first target merges
+    pub fn next(&self) -> Option<&LandTarget> {
+        self.targets.get(1).or_else(|| self.rest.first())
+    }
+
+    /// PRs to retarget to trunk once the first target merges
+    ///
+    /// That's [`Self::next`], plus in a branching stack every other PR
+    /// built on the first target.
+    pub fn retargeted(&self) -> Vec<&LandTarget> {
+        let Some(next) = self.next() else {
+            return Vec::new();
+        };
+        let first = self.targets.first().map(|t| &t.bookmark);
+        let siblings = self.targets[1..]
+            .iter()
+            .chain(&self.rest)
+            .filter(|t| t.bookmark != next.bookmark && t.parent.as_ref() == first);
+        std::iter::once(next).chain(siblings).collect()
+    }
+}
+
+/// Plan which PRs at the bottom of the stack to land
+///
+/// Without `all` only the lowest PR lands, and `upto` (if given) must be its
+/// bookmark. With `all` every PR up to `upto` lands, or the whole stack when
+/// `upto` is `None` (which a branching stack doesn't allow: there's no single
+/// top to land up to). Every bookmark to land needs an open PR, and the
+/// lowest must target `default_branch`.
+pub async fn plan_landing(
+    graph: &ChangeGraph,
+    platform: &dyn PlatformService,
+    default_branch: &str,
+    upto: Option<&str>,
+    all: bool,
+) -> Result<LandPlan> {
+    let analysis = analyze_submission(graph, None)?;
+
+    // Bookmarks to land: the bottom one up to `upto` and those in between
+    let landing: Vec<String> = match upto {
+        Some(name) => {
+            let mut path = analysis.clone();
+            if !path.truncate_to(name) {
+                return Err(Error::BookmarkNotFound(name.to_string()));
+            }
+            if path.segments.len() > 1 && !all {
+                return Err(Error::InvalidArgument(format!(
+                    "'{name}' is not at the bottom of the stack; use --all to land everything up to it"
+                )));
+            }
+            path.segments.into_iter().map(|s| s.bookmark.name).collect()
+        }
+        None if all => {
+            if graph.stack.as_ref().is_some_and(|s| !s.is_linear()) {
+                return Err(Error::InvalidArgument(
+                    "the stack branches; name the bookmark to land everything up to".to_string(),
+                ));
+            }
+            analysis
+                .segments
+                .iter()
+                .map(|s| s.bookmark.name.clone())
+                .collect()
+        }
+        None => vec![analysis.segments[0].bookmark.name.clone()],
+    };
+
+    let parents: Vec<Option<String>> = analysis
+        .segments
+        .iter()
+        .map(|s| {
+            parent_bookmark(&s.bookmark.name, &analysis.segments, &analysis.parents)
+                .ok()
+                .flatten()
+                .map(String::from)
+        })
+        .collect();
+    let segments = analysis.segments;
+
+    let heads: Vec<String> = segments
+        .iter()
+        .map(|s| platform.review_head(&s.bookmark))
+        .collect();
+    let head_refs: Vec<&str> = heads.iter().map(String::as_str).collect();
+    let mut open = platform.find_existing_prs(&head_refs).await?;
+
+    let mut plan = LandPlan {
+        targets: Vec::new(),
+        rest: Vec::new(),
+    };
+    for ((segment, head), parent) in segments.into_iter().zip(&heads).zip(parents) {
+        let lands = landing.contains(&segment.bookmark.name);
+        let Some(pr) = open.remove(head) else {
+            if lands {
+                return Err(Error::InvalidArgument(format!(
+                    "bookmark '{}' has no open PR; run 'ryu submit' first",
+                    segment.bookmark.name
+                )));
+            }
+            continue;
+        };
+        let mut bookmarks: Vec<String> = segment
+            .changes
+            .iter()
+            .flat_map(|c| c.local_bookmarks.iter().cloned())
+            .collect();
+        bookmarks.sort();
+        bookmarks.dedup();
+        let target = LandTarget {
+            bookmark: segment.bookmark.name,
+            pr,
+            commit_ids: segment.changes.into_iter().map(|c| c.commit_id).collect(),
+            bookmarks,
+            parent,
+        };
+        if lands {
+            plan.targets.push(target);
+        } else {
+            plan.rest.push(target);
+        }
+    }
+
+    let bottom = &plan.targets[0].pr;
+    if bottom.base_ref != default_branch {
+        return Err(Error::NotReadyToLand(
+            bottom.number,
+            format!(
+                "targets '{}' instead of '{default_branch}'; run 'ryu sync' first",
+                bottom.base_ref
+            ),
+        ));
+    }
+
+    Ok(plan)
+}
+
+/// Fail unless `pr` can be merged now
+pub fn ensure_ready(pr: &PullRequest, mergeability: &Mergeability) -> Result<()> {
+    let mut blockers = mergeability.blockers();
+    if pr.is_draft {
+        blockers.insert(0, "is a draft");
+    }
+    if blockers.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::NotReadyToLand(pr.number, blockers.join(", ")))
+    }
+}
+
+/// A PR ryu added to the merge queue that won't merge from it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueProblem {
+    /// Bookmark the PR is opened from
+    pub bookmark: String,
+    /// PR number
+    pub pr_number: u64,
+    /// Its failing queue entry, or `None` if it left the queue unmerged
+    pub entry: Option<MergeQueueEntry>,
+}
+
+impl QueueProblem {
+    /// Whether the PR was removed from the queue (rather than failing in it)
+    pub const fn was_removed(&self) -> bool {
+        self.entry.is_none()
+    }
+}
+
+/// Check on PRs ryu added to the merge queue
+///
+/// `queued` pairs bookmark names with their PR numbers, leaving out PRs
+/// already known to have merged or closed. Returns the PRs that left the
+/// queue without merging, or whose merge group is failing.
+pub async fn check_merge_queue(
+    platform: &dyn PlatformService,
+    queued: &[(String, u64)],
+) -> Result<Vec<QueueProblem>> {
+    let mut problems = Vec::new();
+    for (bookmark, pr_number) in queued {
+        let entry = platform.get_merge_queue_entry(*pr_number).await?;
+        if entry.is_none_or(|e| e.state == MergeQueueState::Unmergeable) {
+            problems.push(QueueProblem {
+                bookmark: bookmark.clone(),
+                pr_number: *pr_number,
+                entry,
+            });
+        }
+    }
+    Ok(problems)
+}
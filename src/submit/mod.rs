@@ -6,16 +6,31 @@
 //! 3. Execution - perform the actual operations
 
 mod analysis;
+mod auto_bookmark;
+mod codeowners;
 mod execute;
+mod finished;
+mod journal;
+mod land;
+mod orphaned;
 mod plan;
 mod progress;
+mod status;
+mod template;
 
 pub use analysis::{
-    SubmissionAnalysis, analyze_submission, create_narrowed_segments, generate_pr_title,
-    get_base_branch, select_bookmark_for_segment,
+    PR_BODY_END, PR_BODY_START, SubmissionAnalysis, analyze_submission, analyze_upstack,
+    create_narrowed_segments, generate_pr_body, generate_pr_title, get_base_branch,
+    parent_bookmark, select_bookmark_for_segment, split_into_changes, updated_pr_body,
+    wrap_generated_body,
 };
+pub use auto_bookmark::{
+    AutoBookmark, BOOKMARK_TEMPLATE_CONFIG_KEY, ChangeBookmark, DEFAULT_BOOKMARK_TEMPLATE,
+    plan_auto_bookmarks, plan_change_bookmarks, render_bookmark_name,
+};
+pub use codeowners::{CodeOwners, codeowner_reviewers, find_codeowners, parse_codeowners};
 pub use execute::{
-    STACK_COMMENT_THIS_PR, SubmissionResult, execute_submission, format_stack_comment,
+    AutoMerged, STACK_COMMENT_THIS_PR, SubmissionResult, execute_submission, format_stack_comment,
 };
 
 // Exports for testing stack comment formatting (used by integration tests)
@@ -23,8 +38,24 @@ pub use execute::{
     COMMENT_DATA_POSTFIX, COMMENT_DATA_PREFIX, StackCommentData, StackItem,
     build_stack_comment_data,
 };
+pub use finished::{FinishedBookmark, FinishedPrs, find_finished_prs};
+pub use journal::{
+    JOURNAL_VERSION, JournalStep, JournalStepKind, SubmissionJournal, clear_journal, journal_path,
+    load_journal, save_journal,
+};
+pub use land::{
+    LandPlan, LandTarget, MERGE_METHOD_CONFIG_KEY, QueueProblem, check_merge_queue, ensure_ready,
+    plan_landing,
+};
+pub use orphaned::{OrphanedChange, close_orphaned_prs};
 pub use plan::{
-    ExecutionConstraint, ExecutionStep, PrBaseUpdate, PrToCreate, SubmissionPlan,
-    create_submission_plan,
+    AutoMergeUpdate, ExecutionConstraint, ExecutionStep, PrBaseUpdate, PrMetadataUpdate,
+    PrToCreate, PrTriageUpdate, SubmissionPlan, create_submission_plan, plan_auto_merge,
+    plan_triage,
 };
 pub use progress::{NoopProgress, Phase, ProgressCallback, PushStatus};
+pub use status::{BookmarkStatus, collect_stack_status};
+pub use template::{
+    DESCRIPTION_PLACEHOLDER, PR_TEMPLATE_CONFIG_KEY, PrTemplate, apply_pr_template,
+    find_pr_templates, render_pr_template, select_pr_template,
+};
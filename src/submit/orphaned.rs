@@ -0,0 +1,66 @@
+//! PRs of changes that are gone, for one PR per change
+//!
+//! Squashing a change into another or abandoning it leaves its PR open with
+//! nothing of its own to merge. The tracking state maps each change
+//! submitted with `--per-change` to its bookmark by change ID, so the changes
+//! no longer in the repo can be found and their PRs closed.
+
+use crate::error::Result;
+use crate::platform::PlatformService;
+use crate::tracking::TrackedChange;
+use crate::types::{Bookmark, PullRequest};
+use std::collections::HashSet;
+
+/// A tracked change that is no longer in the repo
+#[derive(Debug, Clone)]
+pub struct OrphanedChange {
+    /// The change as it was tracked
+    pub change: TrackedChange,
+    /// Its PR, if one was still open
+    pub pr: Option<PullRequest>,
+}
+
+/// Close the open PRs of tracked changes missing from `live_change_ids`
+///
+/// `live_change_ids` should hold every change in `trunk()..`, so a change
+/// that landed counts as gone too; its PR is no longer open and is left
+/// alone. Returns every gone change, whether or not it had a PR to close;
+/// on a dry run nothing is closed.
+#[allow(clippy::implicit_hasher)]
+pub async fn close_orphaned_prs(
+    platform: &dyn PlatformService,
+    changes: &[TrackedChange],
+    live_change_ids: &HashSet<String>,
+    dry_run: bool,
+) -> Result<Vec<OrphanedChange>> {
+    let mut orphaned = Vec::new();
+
+    for change in changes {
+        if live_change_ids.contains(&change.change_id) {
+            continue;
+        }
+
+        let bookmark = Bookmark {
+            name: change.bookmark.clone(),
+            commit_id: String::new(),
+            change_id: change.change_id.clone(),
+            has_remote: true,
+            is_synced: false,
+        };
+        let pr = platform
+            .find_existing_pr(&platform.review_head(&bookmark))
+            .await?;
+        if let Some(pr) = &pr
+            && !dry_run
+        {
+            platform.close_pr(pr.number).await?;
+        }
+
+        orphaned.push(OrphanedChange {
+            change: change.clone(),
+            pr,
+        });
+    }
+
+    Ok(orphaned)
+}
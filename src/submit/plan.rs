@@ -5,13 +5,17 @@
 use crate::error::{Error, Result};
 use crate::platform::PlatformService;
 use crate::submit::SubmissionAnalysis;
-use crate::submit::analysis::{generate_pr_title, get_base_branch};
-use crate::types::{Bookmark, NarrowedBookmarkSegment, PullRequest};
+use crate::submit::analysis::{
+    generate_pr_body, generate_pr_title, get_base_branch, parent_bookmark, updated_pr_body,
+    wrap_generated_body,
+};
+use crate::types::{Bookmark, MergeMethod, NarrowedBookmarkSegment, PrTriage, PullRequest};
+use serde::Serialize;
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
 /// Information about a PR that needs to be created
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PrToCreate {
     /// Bookmark for this PR
     pub bookmark: Bookmark,
@@ -19,12 +23,14 @@ pub struct PrToCreate {
     pub base_branch: String,
     /// Generated PR title
     pub title: String,
+    /// Generated PR body
+    pub body: String,
     /// Whether to create as draft
     pub draft: bool,
 }
 
 /// Information about a PR that needs its base updated
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PrBaseUpdate {
     /// Bookmark for this PR
     pub bookmark: Bookmark,
@@ -36,8 +42,56 @@ pub struct PrBaseUpdate {
     pub pr: PullRequest,
 }
 
+/// Information about a PR whose title or body is out of date
+#[derive(Debug, Clone, Serialize)]
+pub struct PrMetadataUpdate {
+    /// Bookmark for this PR
+    pub bookmark: Bookmark,
+    /// Existing PR (with the live title and body)
+    pub pr: PullRequest,
+    /// Generated PR title
+    pub title: String,
+    /// Live body with its generated section brought up to date
+    pub body: String,
+}
+
+impl PrMetadataUpdate {
+    /// Whether the title changes
+    pub fn title_changed(&self) -> bool {
+        self.title != self.pr.title
+    }
+
+    /// Whether the body changes
+    pub fn body_changed(&self) -> bool {
+        self.body != self.pr.body
+    }
+}
+
+/// Reviewers, assignees and labels a PR is missing
+#[derive(Debug, Clone, Serialize)]
+pub struct PrTriageUpdate {
+    /// Bookmark for this PR
+    pub bookmark: Bookmark,
+    /// Existing PR number, or `None` for a PR created earlier in the plan
+    pub pr_number: Option<u64>,
+    /// What to add
+    pub add: PrTriage,
+}
+
+/// Auto-merge to enable on the PR at the bottom of the stack
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoMergeUpdate {
+    /// Bookmark for this PR
+    pub bookmark: Bookmark,
+    /// Existing PR number, or `None` for a PR created earlier in the plan
+    pub pr_number: Option<u64>,
+    /// How the PR is merged once it's ready
+    pub method: MergeMethod,
+}
+
 /// Ordered execution step for a submission plan
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum ExecutionStep {
     /// Push bookmark to remote
     Push(Bookmark),
@@ -47,6 +101,12 @@ pub enum ExecutionStep {
     CreatePr(PrToCreate),
     /// Publish a draft PR
     PublishPr(PullRequest),
+    /// Update the title and body of an existing PR
+    UpdatePrMetadata(PrMetadataUpdate),
+    /// Request reviewers and add assignees and labels
+    AddTriage(PrTriageUpdate),
+    /// Merge the bottom PR once its checks pass
+    EnableAutoMerge(AutoMergeUpdate),
 }
 
 impl ExecutionStep {
@@ -57,6 +117,9 @@ impl ExecutionStep {
             Self::UpdateBase(update) => &update.bookmark.name,
             Self::CreatePr(create) => &create.bookmark.name,
             Self::PublishPr(pr) => &pr.head_ref,
+            Self::UpdatePrMetadata(update) => &update.bookmark.name,
+            Self::AddTriage(update) => &update.bookmark.name,
+            Self::EnableAutoMerge(update) => &update.bookmark.name,
         }
     }
 }
@@ -82,6 +145,32 @@ impl std::fmt::Display for ExecutionStep {
                 Ok(())
             }
             Self::PublishPr(pr) => write!(f, "publish PR #{} ({})", pr.number, pr.head_ref),
+            Self::UpdatePrMetadata(update) => {
+                let what = match (update.title_changed(), update.body_changed()) {
+                    (true, true) => "title and description",
+                    (true, false) => "title",
+                    _ => "description",
+                };
+                write!(
+                    f,
+                    "update {what} of PR #{} ({})",
+                    update.pr.number, update.bookmark.name
+                )
+            }
+            Self::AddTriage(update) => {
+                write!(f, "add {} to ", update.add)?;
+                match update.pr_number {
+                    Some(number) => write!(f, "PR #{number} ({})", update.bookmark.name),
+                    None => write!(f, "new PR ({})", update.bookmark.name),
+                }
+            }
+            Self::EnableAutoMerge(update) => {
+                write!(f, "enable auto-merge ({}) on ", update.method)?;
+                match update.pr_number {
+                    Some(number) => write!(f, "PR #{number} ({})", update.bookmark.name),
+                    None => write!(f, "new PR ({})", update.bookmark.name),
+                }
+            }
         }
     }
 }
@@ -92,17 +181,21 @@ impl std::fmt::Display for ExecutionStep {
 
 /// Typed reference to a Push operation by bookmark name.
 /// Distinct from [`UpdateRef`]/[`CreateRef`] to prevent mixing constraint endpoints.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct PushRef(pub String);
 
 /// Typed reference to an `UpdateBase` operation by bookmark name.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct UpdateRef(pub String);
 
 /// Typed reference to a `CreatePr` operation by bookmark name.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct CreateRef(pub String);
 
+/// Typed reference to an `UpdatePrMetadata` operation by bookmark name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct MetadataRef(pub String);
+
 /// Dependency constraint between execution operations.
 ///
 /// Each variant encodes a semantic relationship between operations.
@@ -111,7 +204,8 @@ pub struct CreateRef(pub String);
 /// Constraints may reference operations that don't exist in the current plan
 /// (e.g., a bookmark that's already synced has no `Push` node). Resolution
 /// returns `None` for such constraints, which is expected behavior.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum ExecutionConstraint {
     /// Push parent branch before child branch.
     /// Ensures commits are pushed in stack order (ancestors before descendants).
@@ -158,6 +252,24 @@ pub enum ExecutionConstraint {
         /// Child PR (created second)
         child: CreateRef,
     },
+
+    /// Push branch before rewriting its PR's title and body.
+    /// The new description describes the new commits, so they go up first.
+    PushBeforeMetadata {
+        /// Branch to push
+        push: PushRef,
+        /// PR to update
+        metadata: MetadataRef,
+    },
+
+    /// Retarget PR before rewriting its title and body.
+    /// The description is written against the diff the PR shows once retargeted.
+    RetargetBeforeMetadata {
+        /// PR to retarget
+        pr: UpdateRef,
+        /// PR to update
+        metadata: MetadataRef,
+    },
 }
 
 impl std::fmt::Display for ExecutionConstraint {
@@ -178,6 +290,12 @@ impl std::fmt::Display for ExecutionConstraint {
             Self::CreateOrder { parent, child } => {
                 write!(f, "CreatePr({}) → CreatePr({})", parent.0, child.0)
             }
+            Self::PushBeforeMetadata { push, metadata } => {
+                write!(f, "Push({}) → UpdatePrMetadata({})", push.0, metadata.0)
+            }
+            Self::RetargetBeforeMetadata { pr, metadata } => {
+                write!(f, "UpdateBase({}) → UpdatePrMetadata({})", pr.0, metadata.0)
+            }
         }
     }
 }
@@ -194,6 +312,7 @@ struct NodeRegistry {
     update: HashMap<String, NodeIdx>,
     create: HashMap<String, NodeIdx>,
     publish: HashMap<String, NodeIdx>,
+    metadata: HashMap<String, NodeIdx>,
 }
 
 impl NodeRegistry {
@@ -213,8 +332,16 @@ impl NodeRegistry {
         self.publish.insert(name.to_string(), NodeIdx(idx));
     }
 
+    fn register_metadata(&mut self, name: &str, idx: usize) {
+        self.metadata.insert(name.to_string(), NodeIdx(idx));
+    }
+
     fn len(&self) -> usize {
-        self.push.len() + self.update.len() + self.create.len() + self.publish.len()
+        self.push.len()
+            + self.update.len()
+            + self.create.len()
+            + self.publish.len()
+            + self.metadata.len()
     }
 }
 
@@ -250,6 +377,16 @@ impl ExecutionConstraint {
                 let to = registry.create.get(&child.0)?;
                 Some((from.0, to.0))
             }
+            Self::PushBeforeMetadata { push, metadata } => {
+                let from = registry.push.get(&push.0)?;
+                let to = registry.metadata.get(&metadata.0)?;
+                Some((from.0, to.0))
+            }
+            Self::RetargetBeforeMetadata { pr, metadata } => {
+                let from = registry.update.get(&pr.0)?;
+                let to = registry.metadata.get(&metadata.0)?;
+                Some((from.0, to.0))
+            }
         }
     }
 }
@@ -262,10 +399,14 @@ struct ExecutionNode {
 }
 
 /// Submission plan
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SubmissionPlan {
     /// Segments to submit (used for stack comment generation)
     pub segments: Vec<NarrowedBookmarkSegment>,
+    /// Bookmark each segment's bookmark builds on, in a branching stack
+    /// (see [`parent_bookmark`])
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub parents: HashMap<String, String>,
     /// Dependency constraints between operations (for debugging/dry-run display)
     pub constraints: Vec<ExecutionConstraint>,
     /// Ordered execution steps
@@ -274,6 +415,8 @@ pub struct SubmissionPlan {
     pub existing_prs: HashMap<String, PullRequest>,
     /// Remote name to push to
     pub remote: String,
+    /// Remote PRs are opened against, when it differs from `remote` (fork workflow)
+    pub pr_remote: Option<String>,
     /// Default branch name (main/master)
     pub default_branch: String,
 }
@@ -315,6 +458,30 @@ impl SubmissionPlan {
             .filter(|s| matches!(s, ExecutionStep::PublishPr(_)))
             .count()
     }
+
+    /// Count title/body update steps
+    pub fn count_metadata_updates(&self) -> usize {
+        self.execution_steps
+            .iter()
+            .filter(|s| matches!(s, ExecutionStep::UpdatePrMetadata(_)))
+            .count()
+    }
+
+    /// Count reviewer/assignee/label steps
+    pub fn count_triage_updates(&self) -> usize {
+        self.execution_steps
+            .iter()
+            .filter(|s| matches!(s, ExecutionStep::AddTriage(_)))
+            .count()
+    }
+
+    /// Count auto-merge steps
+    pub fn count_auto_merges(&self) -> usize {
+        self.execution_steps
+            .iter()
+            .filter(|s| matches!(s, ExecutionStep::EnableAutoMerge(_)))
+            .count()
+    }
 }
 
 /// Create a submission plan
@@ -323,6 +490,7 @@ impl SubmissionPlan {
 /// - Which bookmarks need pushing
 /// - Which PRs need to be created
 /// - Which PR bases need updating
+/// - Which PR titles and bodies no longer match their commits
 pub async fn create_submission_plan(
     analysis: &SubmissionAnalysis,
     platform: &dyn PlatformService,
@@ -332,18 +500,34 @@ pub async fn create_submission_plan(
     let segments = &analysis.segments;
     let bookmarks: Vec<&Bookmark> = segments.iter().map(|s| &s.bookmark).collect();
 
-    // Check for existing PRs
-    let mut existing_prs = HashMap::new();
-    for bookmark in &bookmarks {
-        if let Some(pr) = platform.find_existing_pr(&bookmark.name).await? {
-            existing_prs.insert(bookmark.name.clone(), pr);
+    // Without stacked bases (Gerrit, fork PRs) every PR targets trunk
+    let stacked_bases = platform.supports_stacked_bases();
+    let base_for = |name: &str| {
+        if stacked_bases {
+            get_base_branch(name, segments, &analysis.parents, default_branch)
+        } else {
+            Ok(default_branch.to_string())
         }
-    }
+    };
+
+    // Check for existing PRs in one batch, keyed back to bookmark names
+    let heads: Vec<String> = bookmarks.iter().map(|b| platform.review_head(b)).collect();
+    let head_refs: Vec<&str> = heads.iter().map(String::as_str).collect();
+    let mut found = platform.find_existing_prs(&head_refs).await?;
+    let existing_prs: HashMap<String, PullRequest> = bookmarks
+        .iter()
+        .zip(&heads)
+        .filter_map(|(bookmark, head)| found.remove(head).map(|pr| (bookmark.name.clone(), pr)))
+        .collect();
 
     // Collect raw operations (unordered)
     let mut bookmarks_needing_push = Vec::new();
     let mut prs_to_create = Vec::new();
     let mut prs_to_update_base = Vec::new();
+    let mut prs_to_update_metadata = Vec::new();
+
+    // Change-based platforms take titles and bodies from commit messages
+    let change_based = platform.review_push_ref(default_branch).is_some();
 
     for bookmark in &bookmarks {
         // Check if needs push
@@ -354,7 +538,7 @@ pub async fn create_submission_plan(
         // Check if needs PR creation
         if let Some(pr) = existing_prs.get(&bookmark.name) {
             // PR exists - check if base needs updating
-            let expected_base = get_base_branch(&bookmark.name, segments, default_branch)?;
+            let expected_base = base_for(&bookmark.name)?;
 
             if pr.base_ref != expected_base {
                 prs_to_update_base.push(PrBaseUpdate {
@@ -364,15 +548,21 @@ pub async fn create_submission_plan(
                     pr: pr.clone(),
                 });
             }
+
+            if !change_based && let Some(update) = plan_metadata_update(bookmark, pr, segments)? {
+                prs_to_update_metadata.push(update);
+            }
         } else {
             // PR doesn't exist - needs creation
-            let base_branch = get_base_branch(&bookmark.name, segments, default_branch)?;
+            let base_branch = base_for(&bookmark.name)?;
             let title = generate_pr_title(&bookmark.name, segments)?;
+            let body = wrap_generated_body(&generate_pr_body(&bookmark.name, segments)?);
 
             prs_to_create.push(PrToCreate {
                 bookmark: (*bookmark).clone(),
                 base_branch,
                 title,
+                body,
                 draft: false,
             });
         }
@@ -381,37 +571,216 @@ pub async fn create_submission_plan(
     // Build ordered execution steps
     let (constraints, execution_steps) = build_execution_steps(
         segments,
+        &analysis.parents,
         &bookmarks_needing_push,
         &prs_to_update_base,
         &prs_to_create,
         &[], // prs_to_publish populated by CLI layer via apply_plan_options
+        &prs_to_update_metadata,
     )?;
 
     Ok(SubmissionPlan {
         segments: segments.clone(),
+        parents: analysis.parents.clone(),
         constraints,
         execution_steps,
         existing_prs,
         remote: remote.to_string(),
+        pr_remote: None,
         default_branch: default_branch.to_string(),
     })
 }
 
+/// Compare an existing PR's title and body with the generated ones
+///
+/// The title always follows the commits. The body does only where it has a
+/// generated section (see [`wrap_generated_body`]); anything else was
+/// written by hand, or before ryu generated bodies, and is left alone.
+fn plan_metadata_update(
+    bookmark: &Bookmark,
+    pr: &PullRequest,
+    segments: &[NarrowedBookmarkSegment],
+) -> Result<Option<PrMetadataUpdate>> {
+    let title = generate_pr_title(&bookmark.name, segments)?;
+    let body = updated_pr_body(&pr.body, &generate_pr_body(&bookmark.name, segments)?);
+    if title == pr.title && body.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(PrMetadataUpdate {
+        bookmark: bookmark.clone(),
+        pr: pr.clone(),
+        title,
+        body: body.unwrap_or_else(|| pr.body.clone()),
+    }))
+}
+
+/// Add steps giving each PR the reviewers, assignees and labels it's missing
+///
+/// `wanted` maps bookmark names to what their PR should have. Existing PRs
+/// are compared with what they already have, so repeated submits only add
+/// what's missing; PRs the plan creates get everything. `@me` among
+/// assignees resolves to the authenticated user. Among reviewers it's
+/// dropped: the user authors the PRs, and can't review their own. The steps
+/// run last, in stack order.
+#[allow(clippy::implicit_hasher)]
+pub async fn plan_triage(
+    plan: &mut SubmissionPlan,
+    platform: &dyn PlatformService,
+    wanted: &HashMap<String, PrTriage>,
+) -> Result<()> {
+    if wanted.values().all(PrTriage::is_empty) {
+        return Ok(());
+    }
+
+    let mentions_me = |t: &PrTriage| t.reviewers.iter().chain(&t.assignees).any(|n| n == "@me");
+    let me = if wanted.values().any(mentions_me) {
+        Some(platform.current_user().await?)
+    } else {
+        None
+    };
+
+    let mut steps = Vec::new();
+    for segment in &plan.segments {
+        let bookmark = &segment.bookmark;
+        let Some(triage) = wanted.get(&bookmark.name).filter(|t| !t.is_empty()) else {
+            continue;
+        };
+        let triage = me
+            .as_deref()
+            .map_or_else(|| triage.clone(), |me| resolve_me(triage, me));
+
+        let (pr_number, current) =
+            if let Some(pr) = plan.existing_prs.get(&bookmark.name) {
+                (Some(pr.number), platform.get_pr_triage(pr.number).await?)
+            } else if plan.execution_steps.iter().any(
+                |s| matches!(s, ExecutionStep::CreatePr(c) if c.bookmark.name == bookmark.name),
+            ) {
+                (None, PrTriage::default())
+            } else {
+                continue;
+            };
+
+        let add = triage.missing_from(&current);
+        if !add.is_empty() {
+            steps.push(ExecutionStep::AddTriage(PrTriageUpdate {
+                bookmark: bookmark.clone(),
+                pr_number,
+                add,
+            }));
+        }
+    }
+
+    plan.execution_steps.extend(steps);
+    Ok(())
+}
+
+/// Add a step enabling auto-merge on the bottom PR of the stack
+///
+/// `wanted` maps bookmarks to the method their PR should auto-merge with.
+/// Only the PR targeting the default branch is armed: PRs higher up would
+/// merge into the bookmark below them. Once it lands and the stack is
+/// synced, the next PR becomes the bottom one and is armed in turn. PRs
+/// with auto-merge already enabled are left alone.
+#[allow(clippy::implicit_hasher)]
+pub async fn plan_auto_merge(
+    plan: &mut SubmissionPlan,
+    platform: &dyn PlatformService,
+    wanted: &HashMap<String, MergeMethod>,
+) -> Result<()> {
+    let Some(bookmark) = plan.segments.first().map(|s| &s.bookmark) else {
+        return Ok(());
+    };
+    let Some(&method) = wanted.get(&bookmark.name) else {
+        return Ok(());
+    };
+
+    // Base the PR has once the plan's other steps ran
+    let base = plan
+        .execution_steps
+        .iter()
+        .find_map(|step| match step {
+            ExecutionStep::CreatePr(c) if c.bookmark.name == bookmark.name => {
+                Some(c.base_branch.as_str())
+            }
+            ExecutionStep::UpdateBase(u) if u.bookmark.name == bookmark.name => {
+                Some(u.expected_base.as_str())
+            }
+            _ => None,
+        })
+        .or_else(|| {
+            plan.existing_prs
+                .get(&bookmark.name)
+                .map(|pr| pr.base_ref.as_str())
+        });
+    if base != Some(plan.default_branch.as_str()) {
+        return Ok(());
+    }
+
+    let pr_number = match plan.existing_prs.get(&bookmark.name) {
+        Some(pr) if platform.get_auto_merge(pr.number).await?.is_some() => return Ok(()),
+        Some(pr) => Some(pr.number),
+        None => None,
+    };
+    let update = AutoMergeUpdate {
+        bookmark: bookmark.clone(),
+        pr_number,
+        method,
+    };
+    plan.execution_steps
+        .push(ExecutionStep::EnableAutoMerge(update));
+    Ok(())
+}
+
+/// Replace `@me` among assignees with the user's name, and leave the user
+/// out of the reviewers
+fn resolve_me(triage: &PrTriage, me: &str) -> PrTriage {
+    let is_me = |n: &str| n == "@me" || n.eq_ignore_ascii_case(me);
+    PrTriage {
+        reviewers: triage
+            .reviewers
+            .iter()
+            .filter(|n| !is_me(n))
+            .cloned()
+            .collect(),
+        assignees: triage
+            .assignees
+            .iter()
+            .map(|n| {
+                if n == "@me" {
+                    me.to_string()
+                } else {
+                    n.clone()
+                }
+            })
+            .collect(),
+        labels: triage.labels.clone(),
+    }
+}
+
 /// Build dependency-ordered execution steps.
 ///
 /// Returns both the constraints (for debugging/display) and the sorted execution steps.
 fn build_execution_steps(
     segments: &[NarrowedBookmarkSegment],
+    parents: &HashMap<String, String>,
     bookmarks_needing_push: &[Bookmark],
     prs_to_update_base: &[PrBaseUpdate],
     prs_to_create: &[PrToCreate],
     prs_to_publish: &[PullRequest],
+    prs_to_update_metadata: &[PrMetadataUpdate],
 ) -> Result<(Vec<ExecutionConstraint>, Vec<ExecutionStep>)> {
     let stack_index = build_stack_index(segments);
 
     // Phase 1: Collect semantic constraints (declarative, no indices)
-    let constraints =
-        collect_constraints(segments, prs_to_update_base, prs_to_create, &stack_index);
+    let constraints = collect_constraints(
+        segments,
+        parents,
+        prs_to_update_base,
+        prs_to_create,
+        prs_to_update_metadata,
+        &stack_index,
+    );
 
     tracing::debug!(
         constraint_count = constraints.len(),
@@ -425,6 +794,7 @@ fn build_execution_steps(
         prs_to_update_base,
         prs_to_create,
         prs_to_publish,
+        prs_to_update_metadata,
     );
 
     // Phase 3: Resolve constraints to edges
@@ -452,17 +822,32 @@ fn build_stack_index(segments: &[NarrowedBookmarkSegment]) -> HashMap<String, us
 /// (e.g., already-synced bookmarks have no Push node); resolution handles this.
 fn collect_constraints(
     segments: &[NarrowedBookmarkSegment],
+    parents: &HashMap<String, String>,
     prs_to_update_base: &[PrBaseUpdate],
     prs_to_create: &[PrToCreate],
+    prs_to_update_metadata: &[PrMetadataUpdate],
     stack_index: &HashMap<String, usize>,
 ) -> Vec<ExecutionConstraint> {
     let mut constraints = Vec::new();
 
+    // Each bookmark with the one it builds on (siblings in a branching
+    // stack share a parent)
+    let edges: Vec<(&str, &str)> = segments
+        .iter()
+        .filter_map(|seg| {
+            let child = seg.bookmark.name.as_str();
+            parent_bookmark(child, segments, parents)
+                .ok()
+                .flatten()
+                .map(|parent| (parent, child))
+        })
+        .collect();
+
     // Constraint: Push(parent) → Push(child) for stack order
-    for window in segments.windows(2) {
+    for &(parent, child) in &edges {
         constraints.push(ExecutionConstraint::PushOrder {
-            parent: PushRef(window[0].bookmark.name.clone()),
-            child: PushRef(window[1].bookmark.name.clone()),
+            parent: PushRef(parent.to_string()),
+            child: PushRef(child.to_string()),
         });
     }
 
@@ -500,10 +885,24 @@ fn collect_constraints(
     }
 
     // Constraint: CreatePr(parent) → CreatePr(child)
-    for window in segments.windows(2) {
+    for &(parent, child) in &edges {
         constraints.push(ExecutionConstraint::CreateOrder {
-            parent: CreateRef(window[0].bookmark.name.clone()),
-            child: CreateRef(window[1].bookmark.name.clone()),
+            parent: CreateRef(parent.to_string()),
+            child: CreateRef(child.to_string()),
+        });
+    }
+
+    // Constraint: Push(bookmark) → UpdatePrMetadata(bookmark)
+    // Constraint: UpdateBase(bookmark) → UpdatePrMetadata(bookmark)
+    for update in prs_to_update_metadata {
+        let name = &update.bookmark.name;
+        constraints.push(ExecutionConstraint::PushBeforeMetadata {
+            push: PushRef(name.clone()),
+            metadata: MetadataRef(name.clone()),
+        });
+        constraints.push(ExecutionConstraint::RetargetBeforeMetadata {
+            pr: UpdateRef(name.clone()),
+            metadata: MetadataRef(name.clone()),
         });
     }
 
@@ -517,6 +916,7 @@ fn build_execution_nodes(
     prs_to_update_base: &[PrBaseUpdate],
     prs_to_create: &[PrToCreate],
     prs_to_publish: &[PullRequest],
+    prs_to_update_metadata: &[PrMetadataUpdate],
 ) -> (Vec<ExecutionNode>, NodeRegistry) {
     let mut nodes = Vec::new();
     let mut order = 0usize;
@@ -592,6 +992,16 @@ fn build_execution_nodes(
         order += 1;
     }
 
+    // Add title/body update nodes
+    for update in prs_to_update_metadata {
+        registry.register_metadata(&update.bookmark.name, nodes.len());
+        nodes.push(ExecutionNode {
+            step: ExecutionStep::UpdatePrMetadata(update.clone()),
+            order,
+        });
+        order += 1;
+    }
+
     (nodes, registry)
 }
 
@@ -704,6 +1114,7 @@ mod tests {
             base_ref: base.to_string(),
             head_ref: bookmark.to_string(),
             title: format!("PR for {bookmark}"),
+            body: String::new(),
             node_id: Some(format!("PR_node_{number}")),
             is_draft: false,
         }
@@ -728,6 +1139,7 @@ mod tests {
             bookmark: bookmark.clone(),
             base_branch: base_branch.to_string(),
             title: format!("Add {}", bookmark.name),
+            body: String::new(),
             draft: false,
         }
     }
@@ -757,6 +1169,7 @@ mod tests {
             bookmark: make_bookmark("feat-a", false, false),
             base_branch: "main".to_string(),
             title: "Add feature A".to_string(),
+            body: String::new(),
             draft: false,
         };
 
@@ -766,6 +1179,35 @@ mod tests {
         assert!(!pr_create.draft);
     }
 
+    #[test]
+    fn test_execution_steps_branching_stack_orders_by_parent() {
+        // b and c both build on a
+        let segments = vec![make_segment("a"), make_segment("b"), make_segment("c")];
+        let parents = HashMap::from([
+            ("b".to_string(), "a".to_string()),
+            ("c".to_string(), "a".to_string()),
+        ]);
+        let pushes = vec![
+            make_bookmark("a", false, false),
+            make_bookmark("b", false, false),
+            make_bookmark("c", false, false),
+        ];
+
+        let (constraints, _steps) =
+            build_execution_steps(&segments, &parents, &pushes, &[], &[], &[], &[]).unwrap();
+
+        let push_orders: Vec<(&str, &str)> = constraints
+            .iter()
+            .filter_map(|c| match c {
+                ExecutionConstraint::PushOrder { parent, child } => {
+                    Some((parent.0.as_str(), child.0.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(push_orders, [("a", "b"), ("a", "c")]);
+    }
+
     #[test]
     fn test_execution_steps_simple_push_order() {
         let segments = vec![make_segment("a"), make_segment("b")];
@@ -775,7 +1217,7 @@ mod tests {
         ];
 
         let (_constraints, steps) =
-            build_execution_steps(&segments, &pushes, &[], &[], &[]).unwrap();
+            build_execution_steps(&segments, &HashMap::new(), &pushes, &[], &[], &[], &[]).unwrap();
 
         let push_a = find_step_index(
             &steps,
@@ -800,7 +1242,8 @@ mod tests {
         let creates = vec![make_create(&bm_a, "main")];
 
         let (_constraints, steps) =
-            build_execution_steps(&segments, &pushes, &[], &creates, &[]).unwrap();
+            build_execution_steps(&segments, &HashMap::new(), &pushes, &[], &creates, &[], &[])
+                .unwrap();
 
         let push_a = find_step_index(
             &steps,
@@ -825,7 +1268,8 @@ mod tests {
         let creates = vec![make_create(&bm_a, "main"), make_create(&bm_b, "a")];
 
         let (_constraints, steps) =
-            build_execution_steps(&segments, &pushes, &[], &creates, &[]).unwrap();
+            build_execution_steps(&segments, &HashMap::new(), &pushes, &[], &creates, &[], &[])
+                .unwrap();
 
         let create_a = find_step_index(
             &steps,
@@ -856,7 +1300,8 @@ mod tests {
         ];
 
         let (_constraints, steps) =
-            build_execution_steps(&segments, &pushes, &updates, &[], &[]).unwrap();
+            build_execution_steps(&segments, &HashMap::new(), &pushes, &updates, &[], &[], &[])
+                .unwrap();
 
         let retarget_b = find_step_index(
             &steps,
@@ -885,10 +1330,12 @@ mod tests {
     fn test_plan_is_empty() {
         let plan = SubmissionPlan {
             segments: vec![],
+            parents: HashMap::new(),
             constraints: vec![],
             execution_steps: vec![],
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
@@ -902,6 +1349,7 @@ mod tests {
         let bm = make_bookmark("a", false, false);
         let plan = SubmissionPlan {
             segments: vec![make_segment("a")],
+            parents: HashMap::new(),
             constraints: vec![],
             execution_steps: vec![
                 ExecutionStep::Push(bm.clone()),
@@ -909,6 +1357,7 @@ mod tests {
             ],
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
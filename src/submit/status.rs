@@ -0,0 +1,99 @@
+//! Live status of the PRs in a stack
+//!
+//! `ryu status` shows, for every bookmark in the stack, whether its PR is
+//! open, merged or closed, how its reviews stand and whether CI passes.
+
+use crate::error::Result;
+use crate::platform::PlatformService;
+use crate::types::{ChangeGraph, PrState, PrStatus, PullRequest};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A bookmark in the stack and the live status of its PR
+#[derive(Debug, Clone, Serialize)]
+pub struct BookmarkStatus {
+    /// Bookmark name
+    pub bookmark: String,
+    /// Its PR, if it has one
+    pub pr: Option<PrStatus>,
+}
+
+/// Fetch the status of every bookmark's PR in the stack, bottom first
+///
+/// A bookmark's PR is its open PR if it has one, else the PR `known` maps it
+/// to (e.g. from the PR cache), else its most recently merged or closed PR.
+/// Bookmarks that were never pushed are only looked up in `known`. On
+/// platforms without [`PlatformService::get_pr_status`], only the state of
+/// the open or most recently closed PR is reported.
+#[allow(clippy::implicit_hasher)]
+pub async fn collect_stack_status(
+    graph: &ChangeGraph,
+    platform: &dyn PlatformService,
+    known: &HashMap<String, u64>,
+) -> Result<Vec<BookmarkStatus>> {
+    let Some(stack) = &graph.stack else {
+        return Ok(Vec::new());
+    };
+    let bookmarks: Vec<_> = stack
+        .segments
+        .iter()
+        .flat_map(|s| &s.bookmarks)
+        .map(|b| graph.bookmarks.get(&b.name).unwrap_or(b))
+        .collect();
+
+    let heads: Vec<String> = bookmarks
+        .iter()
+        .filter(|b| b.has_remote)
+        .map(|b| platform.review_head(b))
+        .collect();
+    let head_refs: Vec<&str> = heads.iter().map(String::as_str).collect();
+    let open = platform.find_existing_prs(&head_refs).await?;
+
+    let mut statuses = Vec::with_capacity(bookmarks.len());
+    for bookmark in bookmarks {
+        let head = platform.review_head(bookmark);
+        let pr = if platform.supports_pr_status() {
+            let number = match (open.get(&head), known.get(&bookmark.name)) {
+                (Some(pr), _) => Some(pr.number),
+                (None, Some(&number)) => Some(number),
+                (None, None) if bookmark.has_remote => platform
+                    .find_closed_pr(&head)
+                    .await?
+                    .map(|closed| closed.pr.number),
+                (None, None) => None,
+            };
+            match number {
+                Some(number) => Some(platform.get_pr_status(number).await?),
+                None => None,
+            }
+        } else if bookmark.has_remote || known.contains_key(&bookmark.name) {
+            state_only_status(platform, open.get(&head), &head).await?
+        } else {
+            None
+        };
+        statuses.push(BookmarkStatus {
+            bookmark: bookmark.name.clone(),
+            pr,
+        });
+    }
+    Ok(statuses)
+}
+
+/// Status of a bookmark's PR from whether it's open, merged or closed alone
+async fn state_only_status(
+    platform: &dyn PlatformService,
+    open: Option<&PullRequest>,
+    head: &str,
+) -> Result<Option<PrStatus>> {
+    if let Some(pr) = open {
+        return Ok(Some(PrStatus::state_only(pr.clone(), PrState::Open)));
+    }
+    Ok(platform.find_closed_pr(head).await?.map(|closed| {
+        let state = if closed.merged {
+            PrState::Merged
+        } else {
+            PrState::Closed
+        };
+        PrStatus::state_only(closed.pr, state)
+    }))
+}
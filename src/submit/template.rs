@@ -0,0 +1,392 @@
+//! PR templates
+//!
+//! Discovers the platform's PR/MR description templates in the trunk tree and
+//! renders them into the bodies of newly created PRs.
+
+use crate::error::{Error, Result};
+use crate::repo::JjWorkspace;
+use crate::submit::{ExecutionStep, PrToCreate, SubmissionPlan};
+use crate::types::{NarrowedBookmarkSegment, Platform};
+use std::fmt::Write;
+
+/// jj config key choosing among several templates (e.g. `ryu.pr-template = "bugfix"`)
+pub const PR_TEMPLATE_CONFIG_KEY: &str = "ryu.pr-template";
+
+/// Placeholder the generated description is rendered into
+///
+/// Templates without it get the generated description above their text.
+pub const DESCRIPTION_PLACEHOLDER: &str = "{{description}}";
+
+/// A PR template found in the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrTemplate {
+    /// Name used to choose it: the file stem in a template directory,
+    /// `default` for a single template file
+    pub name: String,
+    /// Repo-relative path
+    pub path: String,
+    /// Template text
+    pub content: String,
+}
+
+/// Where a platform looks for templates
+struct TemplateLocations {
+    /// Single template files, in order of precedence
+    files: &'static [&'static str],
+    /// Directories holding one template per file
+    dirs: &'static [&'static str],
+}
+
+const GITHUB_TEMPLATES: TemplateLocations = TemplateLocations {
+    files: &[
+        ".github/pull_request_template.md",
+        ".github/PULL_REQUEST_TEMPLATE.md",
+        "pull_request_template.md",
+        "PULL_REQUEST_TEMPLATE.md",
+        "docs/pull_request_template.md",
+        "docs/PULL_REQUEST_TEMPLATE.md",
+    ],
+    dirs: &[
+        ".github/PULL_REQUEST_TEMPLATE",
+        "PULL_REQUEST_TEMPLATE",
+        "docs/PULL_REQUEST_TEMPLATE",
+    ],
+};
+
+const GITLAB_TEMPLATES: TemplateLocations = TemplateLocations {
+    files: &[],
+    dirs: &[".gitlab/merge_request_templates"],
+};
+
+const GITEA_TEMPLATES: TemplateLocations = TemplateLocations {
+    files: &[
+        ".gitea/pull_request_template.md",
+        ".gitea/PULL_REQUEST_TEMPLATE.md",
+        ".github/pull_request_template.md",
+        ".github/PULL_REQUEST_TEMPLATE.md",
+        "pull_request_template.md",
+        "PULL_REQUEST_TEMPLATE.md",
+        "docs/pull_request_template.md",
+        "docs/PULL_REQUEST_TEMPLATE.md",
+    ],
+    dirs: &[],
+};
+
+const AZURE_DEVOPS_TEMPLATES: TemplateLocations = TemplateLocations {
+    files: &[
+        ".azuredevops/pull_request_template.md",
+        ".vsts/pull_request_template.md",
+        "docs/pull_request_template.md",
+        "pull_request_template.md",
+    ],
+    dirs: &[
+        ".azuredevops/pull_request_template",
+        ".vsts/pull_request_template",
+        "docs/pull_request_template",
+        "pull_request_template",
+    ],
+};
+
+/// Bitbucket Server keeps its template in repository settings, and Gerrit
+/// descriptions are commit messages
+const NO_TEMPLATES: TemplateLocations = TemplateLocations {
+    files: &[],
+    dirs: &[],
+};
+
+const fn template_locations(platform: Platform) -> &'static TemplateLocations {
+    match platform {
+        // The local forge stands in for GitHub in demos
+        Platform::GitHub | Platform::Local => &GITHUB_TEMPLATES,
+        Platform::GitLab => &GITLAB_TEMPLATES,
+        Platform::Gitea => &GITEA_TEMPLATES,
+        Platform::AzureDevOps => &AZURE_DEVOPS_TEMPLATES,
+        Platform::Bitbucket | Platform::Gerrit => &NO_TEMPLATES,
+    }
+}
+
+/// Find the platform's PR templates in the `trunk()` tree
+///
+/// The first single template file found comes first, followed by every
+/// Markdown file in the template directories.
+pub fn find_pr_templates(workspace: &JjWorkspace, platform: Platform) -> Result<Vec<PrTemplate>> {
+    let locations = template_locations(platform);
+    let mut templates = Vec::new();
+
+    for path in locations.files {
+        if let Some(content) = workspace.read_trunk_file(path)? {
+            templates.push(PrTemplate {
+                name: "default".to_string(),
+                path: (*path).to_string(),
+                content,
+            });
+            break;
+        }
+    }
+
+    for dir in locations.dirs {
+        for file in workspace.list_trunk_dir(dir)? {
+            let Some(stem) = markdown_stem(&file) else {
+                continue;
+            };
+            let path = format!("{dir}/{file}");
+            if let Some(content) = workspace.read_trunk_file(&path)? {
+                templates.push(PrTemplate {
+                    name: stem.to_string(),
+                    path,
+                    content,
+                });
+            }
+        }
+    }
+
+    Ok(templates)
+}
+
+/// File name without a (case-insensitive) `.md` extension, if it has one
+fn markdown_stem(file: &str) -> Option<&str> {
+    let (stem, ext) = file.rsplit_once('.')?;
+    ext.eq_ignore_ascii_case("md").then_some(stem)
+}
+
+/// Choose the template for new PRs
+///
+/// `configured` (the [`PR_TEMPLATE_CONFIG_KEY`] setting) picks a template by
+/// name or path, and `none` turns templates off. Otherwise the single template
+/// file wins, then one named `default` (GitLab's convention), then a lone
+/// template; with several and no choice, none is used.
+pub fn select_pr_template<'a>(
+    templates: &'a [PrTemplate],
+    configured: Option<&str>,
+) -> Result<Option<&'a PrTemplate>> {
+    let named = |name: &str| {
+        templates
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name) || t.path == name)
+    };
+
+    match configured {
+        Some("none") => Ok(None),
+        Some(name) => named(name).map(Some).ok_or_else(|| {
+            let available: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+            Error::Config(format!(
+                "{PR_TEMPLATE_CONFIG_KEY} '{name}' not found (available: {})",
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            ))
+        }),
+        None => Ok(named("default").or(match templates {
+            [only] => Some(only),
+            _ => None,
+        })),
+    }
+}
+
+/// Render a template into the body of a new PR
+///
+/// Fills in `{{title}}`, `{{bookmark}}`, `{{commits}}` (the bookmark's
+/// changes, oldest first) and `{{stack}}` (the submitted bookmarks, root
+/// first). `create.body` holds the generated description, which goes at
+/// [`DESCRIPTION_PLACEHOLDER`] or above the template.
+pub fn render_pr_template(
+    template: &str,
+    create: &PrToCreate,
+    segments: &[NarrowedBookmarkSegment],
+) -> String {
+    let bookmark = &create.bookmark.name;
+
+    let mut commits = String::new();
+    if let Some(segment) = segments.iter().find(|s| &s.bookmark.name == bookmark) {
+        for change in segment.changes.iter().rev() {
+            let line = if change.description_first_line.is_empty() {
+                "(no description)"
+            } else {
+                &change.description_first_line
+            };
+            let _ = writeln!(commits, "- {line}");
+        }
+    }
+
+    let mut stack = String::new();
+    for segment in segments {
+        if &segment.bookmark.name == bookmark {
+            let _ = writeln!(stack, "- **{}** (this PR)", segment.bookmark.name);
+        } else {
+            let _ = writeln!(stack, "- {}", segment.bookmark.name);
+        }
+    }
+
+    let rendered = template
+        .replace("{{title}}", &create.title)
+        .replace("{{bookmark}}", bookmark)
+        .replace("{{commits}}", commits.trim_end())
+        .replace("{{stack}}", stack.trim_end());
+
+    if rendered.contains(DESCRIPTION_PLACEHOLDER) {
+        rendered.replace(DESCRIPTION_PLACEHOLDER, &create.body)
+    } else {
+        format!("{}\n\n{}", create.body, rendered.trim_start())
+    }
+}
+
+/// Render `template` into the body of every PR the plan creates
+pub fn apply_pr_template(plan: &mut SubmissionPlan, template: &PrTemplate) {
+    let segments = &plan.segments;
+    for step in &mut plan.execution_steps {
+        if let ExecutionStep::CreatePr(create) = step {
+            create.body = render_pr_template(&template.content, create, segments);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::submit::wrap_generated_body;
+    use crate::types::{Bookmark, LogEntry};
+    use chrono::Utc;
+
+    fn make_bookmark(name: &str) -> Bookmark {
+        Bookmark {
+            name: name.to_string(),
+            commit_id: format!("{name}_commit"),
+            change_id: format!("{name}_change"),
+            has_remote: false,
+            is_synced: false,
+        }
+    }
+
+    fn make_log_entry(desc: &str) -> LogEntry {
+        LogEntry {
+            commit_id: "abc123".to_string(),
+            change_id: "xyz789".to_string(),
+            author_name: "Test".to_string(),
+            author_email: "test@test.com".to_string(),
+            description_first_line: desc.to_string(),
+            description: format!("{desc}\n"),
+            parents: vec![],
+            local_bookmarks: vec![],
+            remote_bookmarks: vec![],
+            is_working_copy: false,
+            authored_at: Utc::now(),
+            committed_at: Utc::now(),
+        }
+    }
+
+    fn make_template(name: &str, path: &str) -> PrTemplate {
+        PrTemplate {
+            name: name.to_string(),
+            path: path.to_string(),
+            content: String::new(),
+        }
+    }
+
+    fn make_create(name: &str) -> PrToCreate {
+        PrToCreate {
+            bookmark: make_bookmark(name),
+            base_branch: "main".to_string(),
+            title: "Add parser".to_string(),
+            body: wrap_generated_body("Details."),
+            draft: false,
+        }
+    }
+
+    fn make_segments() -> Vec<NarrowedBookmarkSegment> {
+        vec![
+            NarrowedBookmarkSegment {
+                bookmark: make_bookmark("feat-a"),
+                changes: vec![make_log_entry("Add lexer")],
+            },
+            NarrowedBookmarkSegment {
+                bookmark: make_bookmark("feat-b"),
+                changes: vec![
+                    make_log_entry("Fix parser typo"),
+                    make_log_entry("Add parser"),
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_fills_placeholders() {
+        let template = "## {{title}}\n\nBranch: {{bookmark}}\n\n{{description}}\n\n\
+                        ### Commits\n{{commits}}\n\n### Stack\n{{stack}}\n";
+        let body = render_pr_template(template, &make_create("feat-b"), &make_segments());
+
+        assert_eq!(
+            body,
+            format!(
+                "## Add parser\n\nBranch: feat-b\n\n{}\n\n### Commits\n- Add parser\n\
+                 - Fix parser typo\n\n### Stack\n- feat-a\n- **feat-b** (this PR)\n",
+                wrap_generated_body("Details.")
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_puts_description_above_template() {
+        let body = render_pr_template(
+            "## Checklist\n- [ ] Tests\n",
+            &make_create("feat-a"),
+            &make_segments(),
+        );
+        assert_eq!(
+            body,
+            format!(
+                "{}\n\n## Checklist\n- [ ] Tests\n",
+                wrap_generated_body("Details.")
+            )
+        );
+    }
+
+    #[test]
+    fn test_select_prefers_single_file_then_default() {
+        let templates = vec![
+            make_template("bugfix", ".gitlab/merge_request_templates/bugfix.md"),
+            make_template("Default", ".gitlab/merge_request_templates/Default.md"),
+        ];
+        let selected = select_pr_template(&templates, None).unwrap().unwrap();
+        assert_eq!(selected.name, "Default");
+
+        // Several templates and none of them the default: no choice is made
+        let ambiguous = vec![
+            make_template("bugfix", ".github/PULL_REQUEST_TEMPLATE/bugfix.md"),
+            make_template("feature", ".github/PULL_REQUEST_TEMPLATE/feature.md"),
+        ];
+        assert_eq!(select_pr_template(&ambiguous, None).unwrap(), None);
+        assert_eq!(
+            select_pr_template(&ambiguous[..1], None).unwrap(),
+            Some(&ambiguous[0])
+        );
+    }
+
+    #[test]
+    fn test_select_configured_template() {
+        let templates = vec![
+            make_template("default", ".github/pull_request_template.md"),
+            make_template("feature", ".github/PULL_REQUEST_TEMPLATE/feature.md"),
+        ];
+
+        let by_name = select_pr_template(&templates, Some("Feature")).unwrap();
+        assert_eq!(by_name, Some(&templates[1]));
+        let by_path =
+            select_pr_template(&templates, Some(".github/PULL_REQUEST_TEMPLATE/feature.md"))
+                .unwrap();
+        assert_eq!(by_path, Some(&templates[1]));
+        assert_eq!(select_pr_template(&templates, Some("none")).unwrap(), None);
+
+        let err = select_pr_template(&templates, Some("bugfix")).unwrap_err();
+        assert!(err.to_string().contains("default, feature"));
+    }
+
+    #[test]
+    fn test_markdown_stem() {
+        assert_eq!(markdown_stem("Default.md"), Some("Default"));
+        assert_eq!(markdown_stem("bug.fix.MD"), Some("bug.fix"));
+        assert_eq!(markdown_stem("config.yml"), None);
+        assert_eq!(markdown_stem("README"), None);
+    }
+}
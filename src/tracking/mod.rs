@@ -11,6 +11,7 @@ pub use pr_cache::{
 };
 pub use storage::{load_tracking, save_tracking, tracking_path};
 
+use crate::types::{MergeMethod, PrTriage};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -29,6 +30,17 @@ pub struct TrackedBookmark {
     pub remote: Option<String>,
     /// When this bookmark was tracked.
     pub tracked_at: DateTime<Utc>,
+    /// Reviewers, assignees and labels for this bookmark's PR.
+    ///
+    /// Each non-empty list replaces the one given to `ryu submit`.
+    #[serde(default, flatten)]
+    pub triage: PrTriage,
+    /// Auto-merge method, set by `ryu submit --auto-merge`.
+    ///
+    /// Sync enables it on this bookmark's PR once it reaches the bottom of
+    /// the stack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_merge: Option<MergeMethod>,
 }
 
 impl TrackedBookmark {
@@ -39,6 +51,8 @@ impl TrackedBookmark {
             change_id,
             remote: None,
             tracked_at: Utc::now(),
+            triage: PrTriage::default(),
+            auto_merge: None,
         }
     }
 
@@ -49,10 +63,27 @@ impl TrackedBookmark {
             change_id,
             remote: Some(remote),
             tracked_at: Utc::now(),
+            triage: PrTriage::default(),
+            auto_merge: None,
         }
     }
 }
 
+/// A change submitted as its own PR (`ryu submit --per-change`).
+///
+/// Keyed by change ID, so the change keeps its bookmark and PR when it is
+/// rewritten.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TrackedChange {
+    /// jj change ID.
+    pub change_id: String,
+    /// Bookmark the change is submitted from.
+    pub bookmark: String,
+    /// Number of the change's PR, once it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_number: Option<u64>,
+}
+
 /// Persistent state of tracked bookmarks.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TrackingState {
@@ -61,6 +92,9 @@ pub struct TrackingState {
     /// List of tracked bookmarks.
     #[serde(default)]
     pub bookmarks: Vec<TrackedBookmark>,
+    /// Changes submitted one PR per change.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<TrackedChange>,
 }
 
 impl TrackingState {
@@ -69,6 +103,7 @@ impl TrackingState {
         Self {
             version: TRACKING_VERSION,
             bookmarks: Vec::new(),
+            changes: Vec::new(),
         }
     }
 
@@ -100,6 +135,33 @@ impl TrackingState {
     pub fn tracked_names(&self) -> Vec<&str> {
         self.bookmarks.iter().map(|b| b.name.as_str()).collect()
     }
+
+    /// Get a change submitted one PR per change.
+    pub fn change(&self, change_id: &str) -> Option<&TrackedChange> {
+        self.changes.iter().find(|c| c.change_id == change_id)
+    }
+
+    /// Record a change's bookmark, keeping its PR number if the bookmark is unchanged.
+    pub fn track_change(&mut self, change_id: &str, bookmark: &str) {
+        match self.changes.iter_mut().find(|c| c.change_id == change_id) {
+            Some(change) if change.bookmark == bookmark => {}
+            Some(change) => {
+                change.bookmark = bookmark.to_string();
+                change.pr_number = None;
+            }
+            None => self.changes.push(TrackedChange {
+                change_id: change_id.to_string(),
+                bookmark: bookmark.to_string(),
+                pr_number: None,
+            }),
+        }
+    }
+
+    /// Forget a change. Returns it if it was tracked.
+    pub fn untrack_change(&mut self, change_id: &str) -> Option<TrackedChange> {
+        let index = self.changes.iter().position(|c| c.change_id == change_id)?;
+        Some(self.changes.remove(index))
+    }
 }
 
 #[cfg(test)]
@@ -148,6 +210,25 @@ mod tests {
         assert!(!state.untrack("feat-auth")); // Already removed
     }
 
+    #[test]
+    fn test_tracking_state_track_change() {
+        let mut state = TrackingState::new();
+        state.track_change("abc123", "alice/kpqvunts");
+        state.changes[0].pr_number = Some(7);
+
+        // Same bookmark keeps the PR
+        state.track_change("abc123", "alice/kpqvunts");
+        assert_eq!(state.change("abc123").unwrap().pr_number, Some(7));
+
+        // A new bookmark means a new PR
+        state.track_change("abc123", "alice/renamed");
+        assert_eq!(state.change("abc123").unwrap().pr_number, None);
+
+        assert!(state.untrack_change("abc123").is_some());
+        assert!(state.change("abc123").is_none());
+        assert!(state.untrack_change("abc123").is_none());
+    }
+
     #[test]
     fn test_tracking_state_serialization() {
         let mut state = TrackingState::new();
@@ -164,4 +245,19 @@ mod tests {
         assert_eq!(deserialized.bookmarks.len(), 1);
         assert_eq!(deserialized.bookmarks[0].name, "feat-auth");
     }
+
+    #[test]
+    fn test_tracking_state_serializes_triage_overrides() {
+        let mut bookmark = TrackedBookmark::new("feat-auth".to_string(), "abc123".to_string());
+        bookmark.triage.reviewers = vec!["alice".to_string(), "acme/backend".to_string()];
+        let mut state = TrackingState::new();
+        state.track(bookmark.clone());
+
+        let toml_str = toml::to_string_pretty(&state).unwrap();
+        assert!(toml_str.contains("reviewers = ["));
+        assert!(!toml_str.contains("labels"));
+
+        let deserialized: TrackingState = toml::from_str(&toml_str).unwrap();
+        assert_eq!(deserialized.bookmarks[0], bookmark);
+    }
 }
@@ -4,7 +4,7 @@
 //! deleted - it will be rebuilt on the next submit.
 
 use crate::error::{Error, Result};
-use crate::types::PullRequest;
+use crate::types::{MergeMethod, PullRequest};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -29,6 +29,12 @@ pub struct CachedPr {
     pub remote: String,
     /// When this cache entry was last updated.
     pub updated_at: DateTime<Utc>,
+    /// Method the PR auto-merges with, if ryu enabled auto-merge on it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_merge: Option<MergeMethod>,
+    /// Whether ryu added the PR to the merge queue, and it hasn't left it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub queued: bool,
 }
 
 /// PR cache state.
@@ -56,22 +62,50 @@ impl PrCache {
     }
 
     /// Update or insert a PR cache entry.
+    ///
+    /// The auto-merge and merge queue state is kept while the bookmark maps
+    /// to the same PR.
     pub fn upsert(&mut self, bookmark: &str, pr: &PullRequest, remote: &str) {
-        let entry = CachedPr {
+        let mut entry = CachedPr {
             bookmark: bookmark.to_string(),
             number: pr.number,
             url: pr.html_url.clone(),
             remote: remote.to_string(),
             updated_at: Utc::now(),
+            auto_merge: None,
+            queued: false,
         };
 
         if let Some(existing) = self.prs.iter_mut().find(|p| p.bookmark == bookmark) {
+            if existing.number == pr.number {
+                entry.auto_merge = existing.auto_merge;
+                entry.queued = existing.queued;
+            }
             *existing = entry;
         } else {
             self.prs.push(entry);
         }
     }
 
+    /// Record that auto-merge was enabled on a bookmark's cached PR.
+    pub fn set_auto_merge(&mut self, bookmark: &str, method: MergeMethod) {
+        if let Some(entry) = self.prs.iter_mut().find(|p| p.bookmark == bookmark) {
+            entry.auto_merge = Some(method);
+        }
+    }
+
+    /// Record whether a bookmark's cached PR is in the merge queue.
+    pub fn set_queued(&mut self, bookmark: &str, queued: bool) {
+        if let Some(entry) = self.prs.iter_mut().find(|p| p.bookmark == bookmark) {
+            entry.queued = queued;
+        }
+    }
+
+    /// Cached PRs ryu added to the merge queue.
+    pub fn queued(&self) -> impl Iterator<Item = &CachedPr> {
+        self.prs.iter().filter(|p| p.queued)
+    }
+
     /// Remove a bookmark's PR cache entry.
     pub fn remove(&mut self, bookmark: &str) -> bool {
         let len_before = self.prs.len();
@@ -164,6 +198,7 @@ mod tests {
             base_ref: "main".to_string(),
             head_ref: "feat".to_string(),
             title: "Test PR".to_string(),
+            body: String::new(),
             node_id: None,
             is_draft: false,
         }
@@ -205,6 +240,39 @@ mod tests {
         assert_eq!(cached.remote, "upstream");
     }
 
+    #[test]
+    fn test_upsert_keeps_auto_merge_for_same_pr() {
+        let mut cache = PrCache::new();
+        cache.upsert("feat-auth", &make_test_pr(123), "origin");
+        cache.set_auto_merge("feat-auth", MergeMethod::Squash);
+
+        cache.upsert("feat-auth", &make_test_pr(123), "origin");
+        assert_eq!(
+            cache.get("feat-auth").unwrap().auto_merge,
+            Some(MergeMethod::Squash)
+        );
+
+        // A new PR for the bookmark starts without auto-merge
+        cache.upsert("feat-auth", &make_test_pr(456), "origin");
+        assert_eq!(cache.get("feat-auth").unwrap().auto_merge, None);
+    }
+
+    #[test]
+    fn test_queued_entries() {
+        let mut cache = PrCache::new();
+        cache.upsert("feat-auth", &make_test_pr(123), "origin");
+        cache.upsert("feat-db", &make_test_pr(124), "origin");
+        cache.set_queued("feat-db", true);
+
+        // Refreshing the same PR keeps it queued
+        cache.upsert("feat-db", &make_test_pr(124), "origin");
+        let queued: Vec<_> = cache.queued().map(|p| p.bookmark.as_str()).collect();
+        assert_eq!(queued, vec!["feat-db"]);
+
+        cache.set_queued("feat-db", false);
+        assert_eq!(cache.queued().count(), 0);
+    }
+
     #[test]
     fn test_remove() {
         let mut cache = PrCache::new();
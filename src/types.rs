@@ -32,6 +32,8 @@ pub struct LogEntry {
     pub author_email: String,
     /// First line of commit description
     pub description_first_line: String,
+    /// Full commit description
+    pub description: String,
     /// Parent commit IDs
     pub parents: Vec<String>,
     /// Local bookmarks pointing to this commit
@@ -47,7 +49,7 @@ pub struct LogEntry {
 }
 
 /// A segment of changes belonging to one or more bookmarks
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BookmarkSegment {
     /// Bookmarks pointing to the tip of this segment
     pub bookmarks: Vec<Bookmark>,
@@ -56,7 +58,7 @@ pub struct BookmarkSegment {
 }
 
 /// A segment narrowed to a single bookmark (after user selection)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NarrowedBookmarkSegment {
     /// The selected bookmark for this segment
     pub bookmark: Bookmark,
@@ -64,27 +66,104 @@ pub struct NarrowedBookmarkSegment {
     pub changes: Vec<LogEntry>,
 }
 
-/// A stack of bookmarks from trunk to a leaf
-#[derive(Debug, Clone)]
+/// A stack of bookmarks from trunk to its leaves
+///
+/// Usually a line, but stacks can branch: two features built on one shared
+/// bookmark make a tree, with `parents` recording its shape.
+#[derive(Debug, Clone, Serialize)]
 pub struct BranchStack {
-    /// Segments from trunk (index 0) to leaf (last index)
+    /// Segments from trunk (index 0) to leaf (last index); in a tree, each
+    /// segment comes after its parent
     pub segments: Vec<BookmarkSegment>,
+    /// Parent bookmark (nearest bookmarked ancestor) of each bookmark not
+    /// based on trunk
+    pub parents: HashMap<String, String>,
+}
+
+impl BranchStack {
+    /// A stack whose segments each build on the one before
+    pub fn linear(segments: Vec<BookmarkSegment>) -> Self {
+        let parents = segments
+            .windows(2)
+            .flat_map(|pair| {
+                let parent = &pair[0].bookmarks[0].name;
+                pair[1]
+                    .bookmarks
+                    .iter()
+                    .map(move |b| (b.name.clone(), parent.clone()))
+            })
+            .collect();
+        Self { segments, parents }
+    }
+
+    /// Index of the segment a segment builds on, `None` for trunk
+    pub fn parent_index(&self, index: usize) -> Option<usize> {
+        let name = &self.segments[index].bookmarks.first()?.name;
+        let parent = self.parents.get(name)?;
+        self.segments
+            .iter()
+            .position(|s| s.bookmarks.iter().any(|b| &b.name == parent))
+    }
+
+    /// Whether no segment has more than one child
+    pub fn is_linear(&self) -> bool {
+        (1..self.segments.len()).all(|i| self.parent_index(i) == Some(i - 1))
+    }
+
+    /// Name of the bookmark at the top of the stack
+    pub fn leaf_bookmark(&self) -> Option<&str> {
+        self.segments
+            .last()
+            .and_then(|s| s.bookmarks.first())
+            .map(|b| b.name.as_str())
+    }
+
+    /// Whether any segment of the stack has `bookmark`
+    pub fn contains(&self, bookmark: &str) -> bool {
+        self.segments
+            .iter()
+            .any(|s| s.bookmarks.iter().any(|b| b.name == bookmark))
+    }
 }
 
 /// The complete change graph for a repository
 ///
-/// Represents the single linear stack from trunk to working copy.
-/// Only bookmarks between trunk and working copy are included.
-#[derive(Debug, Clone, Default)]
+/// `stack` is the stack from trunk to the working copy, which commands work
+/// on by default. `stacks` holds every stack found, so commands can work on
+/// the others too (see [`ChangeGraph::focus`]).
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ChangeGraph {
     /// All bookmarks in the stack by name
     pub bookmarks: HashMap<String, Bookmark>,
     /// The single stack from trunk to working copy (None if working copy is at trunk)
     pub stack: Option<BranchStack>,
+    /// Every stack with bookmarks: the working copy's first, then those of
+    /// the bookmarks matched by `ryu.stacks`
+    pub stacks: Vec<BranchStack>,
     /// Number of bookmarks excluded due to merge commits
     pub excluded_bookmark_count: usize,
 }
 
+impl ChangeGraph {
+    /// Make the stack containing `bookmark` the one commands work on
+    ///
+    /// Keeps the working copy's stack if it has `bookmark`; otherwise prefers
+    /// the stack `bookmark` tops, as stacks can share their lower segments.
+    /// Returns false, leaving no stack, if no stack contains it.
+    pub fn focus(&mut self, bookmark: &str) -> bool {
+        if self.stack.as_ref().is_some_and(|s| s.contains(bookmark)) {
+            return true;
+        }
+        self.stack = self
+            .stacks
+            .iter()
+            .find(|s| s.leaf_bookmark() == Some(bookmark))
+            .or_else(|| self.stacks.iter().find(|s| s.contains(bookmark)))
+            .cloned();
+        self.stack.is_some()
+    }
+}
+
 /// A pull request / merge request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequest {
@@ -98,12 +177,183 @@ pub struct PullRequest {
     pub head_ref: String,
     /// PR/MR title
     pub title: String,
+    /// PR/MR description (Markdown)
+    #[serde(default)]
+    pub body: String,
     /// GraphQL node ID (GitHub only, used for mutations)
     pub node_id: Option<String>,
     /// Whether PR is a draft
     pub is_draft: bool,
 }
 
+/// A pull request / merge request that is no longer open
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosedPullRequest {
+    /// The PR/MR as it was when it was closed
+    pub pr: PullRequest,
+    /// Whether it was merged (as opposed to closed without merging)
+    pub merged: bool,
+    /// When it was merged or closed, if the platform reports it
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// Whether a pull request is open, merged or closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrState {
+    /// Open (including drafts)
+    Open,
+    /// Merged
+    Merged,
+    /// Closed without merging
+    Closed,
+}
+
+impl std::fmt::Display for PrState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Merged => write!(f, "merged"),
+            Self::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+/// Overall verdict of a pull request's reviews
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    /// Approved as required
+    Approved,
+    /// A reviewer requested changes
+    ChangesRequested,
+    /// Still needs an approving review
+    ReviewRequired,
+    /// No review is required
+    #[default]
+    None,
+    /// The platform doesn't report reviews
+    Unknown,
+}
+
+impl std::fmt::Display for ReviewDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Approved => write!(f, "approved"),
+            Self::ChangesRequested => write!(f, "changes requested"),
+            Self::ReviewRequired => write!(f, "review required"),
+            Self::None => write!(f, "no review required"),
+            Self::Unknown => write!(f, "reviews unknown"),
+        }
+    }
+}
+
+/// A pull request with its live review and CI state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrStatus {
+    /// The PR/MR itself
+    pub pr: PullRequest,
+    /// Open, merged or closed
+    pub state: PrState,
+    /// Verdict of its reviews
+    pub review_decision: ReviewDecision,
+    /// Reviewers whose latest review approves it
+    pub approvals: u32,
+    /// Review threads not resolved yet
+    pub unresolved_threads: u32,
+    /// Combined CI status of its head commit
+    pub checks: CheckStatus,
+}
+
+impl PrStatus {
+    /// Status of a PR on a platform that only reports whether it's open,
+    /// merged or closed
+    pub const fn state_only(pr: PullRequest, state: PrState) -> Self {
+        Self {
+            pr,
+            state,
+            review_decision: ReviewDecision::Unknown,
+            approvals: 0,
+            unresolved_threads: 0,
+            checks: CheckStatus::Unknown,
+        }
+    }
+}
+
+/// Reviewers, assignees and labels of a pull request
+///
+/// GitHub team reviewers are written `org/team`. When requesting, the
+/// assignee `@me` stands for the authenticated user; as a reviewer it's
+/// dropped, since the user authors the PR.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrTriage {
+    /// Requested (or already reviewing) users and teams
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    /// Assigned users
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assignees: Vec<String>,
+    /// Label names
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+}
+
+impl PrTriage {
+    /// Whether nothing is set
+    pub const fn is_empty(&self) -> bool {
+        self.reviewers.is_empty() && self.assignees.is_empty() && self.labels.is_empty()
+    }
+
+    /// What `self` asks for that `current` doesn't have yet
+    ///
+    /// Names compare case-insensitively, as on every supported platform.
+    #[must_use]
+    pub fn missing_from(&self, current: &Self) -> Self {
+        let missing = |wanted: &[String], have: &[String]| -> Vec<String> {
+            let mut out: Vec<String> = Vec::new();
+            for name in wanted {
+                let present = |n: &String| n.eq_ignore_ascii_case(name);
+                if !have.iter().any(present) && !out.iter().any(present) {
+                    out.push(name.clone());
+                }
+            }
+            out
+        };
+        Self {
+            reviewers: missing(&self.reviewers, &current.reviewers),
+            assignees: missing(&self.assignees, &current.assignees),
+            labels: missing(&self.labels, &current.labels),
+        }
+    }
+
+    /// `self`, with each non-empty list of `overrides` replacing its own
+    #[must_use]
+    pub fn overridden_by(&self, overrides: &Self) -> Self {
+        let pick =
+            |own: &[String], other: &[String]| if other.is_empty() { own } else { other }.to_vec();
+        Self {
+            reviewers: pick(&self.reviewers, &overrides.reviewers),
+            assignees: pick(&self.assignees, &overrides.assignees),
+            labels: pick(&self.labels, &overrides.labels),
+        }
+    }
+}
+
+impl std::fmt::Display for PrTriage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [
+            ("reviewers", &self.reviewers),
+            ("assignees", &self.assignees),
+            ("labels", &self.labels),
+        ]
+        .into_iter()
+        .filter(|(_, names)| !names.is_empty())
+        .map(|(kind, names)| format!("{kind} {}", names.join(", ")))
+        .collect();
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
 /// A comment on a pull request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrComment {
@@ -113,6 +363,136 @@ pub struct PrComment {
     pub body: String,
 }
 
+/// How a PR is merged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    /// Merge commit
+    Merge,
+    /// Squash the PR into one commit
+    #[default]
+    Squash,
+    /// Rebase the PR's commits onto the base
+    Rebase,
+}
+
+impl std::fmt::Display for MergeMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Merge => write!(f, "merge"),
+            Self::Squash => write!(f, "squash"),
+            Self::Rebase => write!(f, "rebase"),
+        }
+    }
+}
+
+impl std::str::FromStr for MergeMethod {
+    type Err = crate::error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "merge" => Ok(Self::Merge),
+            "squash" => Ok(Self::Squash),
+            "rebase" => Ok(Self::Rebase),
+            _ => Err(crate::error::Error::Config(format!(
+                "unknown merge method '{s}' (expected merge, squash or rebase)"
+            ))),
+        }
+    }
+}
+
+/// Combined state of a PR's CI checks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    /// All checks passed
+    Success,
+    /// Checks are still running
+    Pending,
+    /// A check failed
+    Failure,
+    /// No checks are configured
+    #[default]
+    None,
+    /// The platform doesn't report CI status
+    Unknown,
+}
+
+/// Whether a PR can be merged
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mergeability {
+    /// Whether the PR has the approvals it needs
+    pub approved: bool,
+    /// State of CI on the PR's head
+    pub checks: CheckStatus,
+    /// Whether it merges without conflicts; `None` while the platform is
+    /// still working it out
+    pub mergeable: Option<bool>,
+}
+
+impl Mergeability {
+    /// Whether checks or conflict detection haven't finished yet
+    pub fn is_pending(&self) -> bool {
+        self.checks == CheckStatus::Pending || self.mergeable.is_none()
+    }
+
+    /// Why the PR can't be merged (yet); empty when it can
+    pub fn blockers(&self) -> Vec<&'static str> {
+        let mut blockers = Vec::new();
+        if !self.approved {
+            blockers.push("not approved");
+        }
+        match self.checks {
+            CheckStatus::Failure => blockers.push("checks failed"),
+            CheckStatus::Pending => blockers.push("checks still running"),
+            CheckStatus::Success | CheckStatus::None | CheckStatus::Unknown => {}
+        }
+        match self.mergeable {
+            Some(false) => blockers.push("has conflicts"),
+            None => blockers.push("mergeability not known yet"),
+            Some(true) => {}
+        }
+        blockers
+    }
+}
+
+/// State of a PR in a merge queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeQueueState {
+    /// Waiting for its turn
+    Queued,
+    /// Checks are running on the merge group
+    AwaitingChecks,
+    /// Checks passed; it merges when it reaches the front
+    Mergeable,
+    /// Checks failed or it conflicts; the queue is about to drop it
+    Unmergeable,
+    /// The queue is locked, e.g. while the base branch changes
+    Locked,
+}
+
+impl std::fmt::Display for MergeQueueState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Queued => write!(f, "queued"),
+            Self::AwaitingChecks => write!(f, "awaiting checks"),
+            Self::Mergeable => write!(f, "mergeable"),
+            Self::Unmergeable => write!(f, "unmergeable"),
+            Self::Locked => write!(f, "locked"),
+        }
+    }
+}
+
+/// A PR's place in a merge queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeQueueEntry {
+    /// Position in the queue, 1 being next to merge
+    pub position: Option<u64>,
+    /// Where it is in the queue's checks
+    pub state: MergeQueueState,
+}
+
 /// A git remote
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitRemote {
@@ -129,6 +509,16 @@ pub enum Platform {
     GitHub,
     /// GitLab or self-hosted GitLab
     GitLab,
+    /// Gitea or Forgejo (including Codeberg)
+    Gitea,
+    /// Bitbucket Data Center / Server (self-hosted only)
+    Bitbucket,
+    /// Azure DevOps Services (dev.azure.com)
+    AzureDevOps,
+    /// Gerrit Code Review (change-based, one review per commit)
+    Gerrit,
+    /// Local file-backed fake forge (offline work and demos)
+    Local,
 }
 
 impl std::fmt::Display for Platform {
@@ -136,6 +526,11 @@ impl std::fmt::Display for Platform {
         match self {
             Self::GitHub => write!(f, "GitHub"),
             Self::GitLab => write!(f, "GitLab"),
+            Self::Gitea => write!(f, "Gitea"),
+            Self::Bitbucket => write!(f, "Bitbucket"),
+            Self::AzureDevOps => write!(f, "Azure DevOps"),
+            Self::Gerrit => write!(f, "Gerrit"),
+            Self::Local => write!(f, "Local"),
         }
     }
 }
@@ -145,10 +540,10 @@ impl std::fmt::Display for Platform {
 pub struct PlatformConfig {
     /// Platform type
     pub platform: Platform,
-    /// Repository owner (user or organization)
+    /// Repository owner (user or organization; `<org>/<project>` on Azure DevOps)
     pub owner: String,
     /// Repository name
     pub repo: String,
-    /// Custom host (None for github.com/gitlab.com)
+    /// Custom host (None for github.com/gitlab.com/gitea.com)
     pub host: Option<String>,
 }
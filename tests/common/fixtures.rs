@@ -7,8 +7,8 @@
 
 use chrono::Utc;
 use jj_ryu::types::{
-    Bookmark, BookmarkSegment, BranchStack, ChangeGraph, LogEntry, Platform, PlatformConfig,
-    PrComment, PullRequest,
+    Bookmark, BookmarkSegment, BranchStack, ChangeGraph, ClosedPullRequest, LogEntry, Platform,
+    PlatformConfig, PrComment, PullRequest,
 };
 use std::collections::HashMap;
 
@@ -56,6 +56,7 @@ pub fn make_log_entry_with_ids(
         author_name: "Test Author".to_string(),
         author_email: "test@example.com".to_string(),
         description_first_line: desc.to_string(),
+        description: format!("{desc}\n"),
         parents: vec![],
         local_bookmarks: bookmarks.iter().map(ToString::to_string).collect(),
         remote_bookmarks: vec![],
@@ -73,6 +74,7 @@ pub fn make_pr(number: u64, head: &str, base: &str) -> PullRequest {
         base_ref: base.to_string(),
         head_ref: head.to_string(),
         title: format!("PR for {head}"),
+        body: String::new(),
         node_id: Some(format!("PR_node_{number}")),
         is_draft: false,
     }
@@ -86,11 +88,21 @@ pub fn make_pr_draft(number: u64, head: &str, base: &str) -> PullRequest {
         base_ref: base.to_string(),
         head_ref: head.to_string(),
         title: format!("PR for {head}"),
+        body: String::new(),
         node_id: Some(format!("PR_node_{number}")),
         is_draft: true,
     }
 }
 
+/// Create a PR that was merged (or, with `merged: false`, closed) just now
+pub fn make_closed_pr(number: u64, head: &str, base: &str, merged: bool) -> ClosedPullRequest {
+    ClosedPullRequest {
+        pr: make_pr(number, head, base),
+        merged,
+        closed_at: Some(Utc::now()),
+    }
+}
+
 /// Create a PR comment
 pub fn make_pr_comment(id: u64, body: &str) -> PrComment {
     PrComment {
@@ -146,9 +158,11 @@ pub fn make_linear_stack(names: &[&str]) -> ChangeGraph {
         });
     }
 
+    let stack = BranchStack::linear(segments);
     ChangeGraph {
         bookmarks,
-        stack: Some(BranchStack { segments }),
+        stack: Some(stack.clone()),
+        stacks: vec![stack],
         excluded_bookmark_count: 0,
     }
 }
@@ -178,11 +192,11 @@ pub fn make_multi_bookmark_segment(names: &[&str]) -> ChangeGraph {
         changes: vec![log_entry],
     };
 
+    let stack = BranchStack::linear(vec![segment]);
     ChangeGraph {
         bookmarks,
-        stack: Some(BranchStack {
-            segments: vec![segment],
-        }),
+        stack: Some(stack.clone()),
+        stacks: vec![stack],
         excluded_bookmark_count: 0,
     }
 }
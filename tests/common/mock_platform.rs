@@ -8,10 +8,13 @@
 use async_trait::async_trait;
 use jj_ryu::error::{Error, Result};
 use jj_ryu::platform::PlatformService;
-use jj_ryu::types::{PlatformConfig, PrComment, PullRequest};
+use jj_ryu::types::{
+    ClosedPullRequest, MergeMethod, MergeQueueEntry, MergeQueueState, Mergeability, PlatformConfig,
+    PrComment, PrStatus, PrTriage, PullRequest,
+};
 use std::collections::HashMap;
 use std::sync::Mutex;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 /// Call record for `create_pr`
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,6 +22,7 @@ pub struct CreatePrCall {
     pub head: String,
     pub base: String,
     pub title: String,
+    pub body: String,
 }
 
 /// Call record for `update_pr_base`
@@ -28,6 +32,14 @@ pub struct UpdateBaseCall {
     pub new_base: String,
 }
 
+/// Call record for `update_pr_metadata`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateMetadataCall {
+    pub pr_number: u64,
+    pub title: String,
+    pub body: String,
+}
+
 /// Call record for `create_pr_comment`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CreateCommentCall {
@@ -48,14 +60,28 @@ pub struct CreateCommentCall {
 pub struct MockPlatformService {
     config: PlatformConfig,
     next_pr_number: AtomicU64,
+    stacked_bases: AtomicBool,
+    pr_status: AtomicBool,
     find_pr_responses: Mutex<HashMap<String, Option<PullRequest>>>,
+    closed_pr_responses: Mutex<HashMap<String, ClosedPullRequest>>,
     list_comments_responses: Mutex<HashMap<u64, Vec<PrComment>>>,
+    triage_responses: Mutex<HashMap<u64, PrTriage>>,
+    mergeability_responses: Mutex<HashMap<u64, Mergeability>>,
+    auto_merge_responses: Mutex<HashMap<u64, MergeMethod>>,
+    merge_queue_responses: Mutex<HashMap<u64, MergeQueueEntry>>,
+    pr_status_responses: Mutex<HashMap<u64, PrStatus>>,
     // Call tracking
     find_pr_calls: Mutex<Vec<String>>,
     create_pr_calls: Mutex<Vec<CreatePrCall>>,
     update_base_calls: Mutex<Vec<UpdateBaseCall>>,
+    update_metadata_calls: Mutex<Vec<UpdateMetadataCall>>,
     create_comment_calls: Mutex<Vec<CreateCommentCall>>,
     list_comments_calls: Mutex<Vec<u64>>,
+    added_triage: Mutex<HashMap<u64, PrTriage>>,
+    merge_calls: Mutex<Vec<(u64, MergeMethod)>>,
+    auto_merge_calls: Mutex<Vec<(u64, MergeMethod)>>,
+    enqueue_calls: Mutex<Vec<u64>>,
+    close_calls: Mutex<Vec<u64>>,
     // Error injection
     error_on_find_pr: Mutex<Option<String>>,
     error_on_create_pr: Mutex<Option<String>>,
@@ -68,19 +94,43 @@ impl MockPlatformService {
         Self {
             config,
             next_pr_number: AtomicU64::new(1),
+            stacked_bases: AtomicBool::new(true),
+            pr_status: AtomicBool::new(true),
             find_pr_responses: Mutex::new(HashMap::new()),
+            closed_pr_responses: Mutex::new(HashMap::new()),
             list_comments_responses: Mutex::new(HashMap::new()),
+            triage_responses: Mutex::new(HashMap::new()),
+            mergeability_responses: Mutex::new(HashMap::new()),
+            auto_merge_responses: Mutex::new(HashMap::new()),
+            merge_queue_responses: Mutex::new(HashMap::new()),
+            pr_status_responses: Mutex::new(HashMap::new()),
             find_pr_calls: Mutex::new(Vec::new()),
             create_pr_calls: Mutex::new(Vec::new()),
             update_base_calls: Mutex::new(Vec::new()),
+            update_metadata_calls: Mutex::new(Vec::new()),
             create_comment_calls: Mutex::new(Vec::new()),
             list_comments_calls: Mutex::new(Vec::new()),
+            added_triage: Mutex::new(HashMap::new()),
+            merge_calls: Mutex::new(Vec::new()),
+            auto_merge_calls: Mutex::new(Vec::new()),
+            enqueue_calls: Mutex::new(Vec::new()),
+            close_calls: Mutex::new(Vec::new()),
             error_on_find_pr: Mutex::new(None),
             error_on_create_pr: Mutex::new(None),
             error_on_update_base: Mutex::new(None),
         }
     }
 
+    /// Simulate a platform without stacked bases (e.g. PRs from a fork)
+    pub fn set_stacked_bases(&self, supported: bool) {
+        self.stacked_bases.store(supported, Ordering::SeqCst);
+    }
+
+    /// Simulate a platform that only reports whether PRs are open or closed
+    pub fn set_pr_status_supported(&self, supported: bool) {
+        self.pr_status.store(supported, Ordering::SeqCst);
+    }
+
     // === Error injection methods ===
 
     /// Make `find_existing_pr` return an error
@@ -106,6 +156,14 @@ impl MockPlatformService {
             .insert(branch.to_string(), pr);
     }
 
+    /// Set the response for `find_closed_pr` for a specific branch
+    pub fn set_closed_pr_response(&self, branch: &str, pr: ClosedPullRequest) {
+        self.closed_pr_responses
+            .lock()
+            .unwrap()
+            .insert(branch.to_string(), pr);
+    }
+
     /// Set the response for `list_pr_comments` for a specific PR
     pub fn set_list_comments_response(&self, pr_number: u64, comments: Vec<PrComment>) {
         self.list_comments_responses
@@ -114,6 +172,46 @@ impl MockPlatformService {
             .insert(pr_number, comments);
     }
 
+    /// Set the reviewers, assignees and labels a PR already has
+    pub fn set_triage_response(&self, pr_number: u64, triage: PrTriage) {
+        self.triage_responses
+            .lock()
+            .unwrap()
+            .insert(pr_number, triage);
+    }
+
+    /// Set the response for `get_mergeability` for a specific PR
+    pub fn set_mergeability_response(&self, pr_number: u64, mergeability: Mergeability) {
+        self.mergeability_responses
+            .lock()
+            .unwrap()
+            .insert(pr_number, mergeability);
+    }
+
+    /// Mark auto-merge as already enabled on a PR
+    pub fn set_auto_merge_response(&self, pr_number: u64, method: MergeMethod) {
+        self.auto_merge_responses
+            .lock()
+            .unwrap()
+            .insert(pr_number, method);
+    }
+
+    /// Set a PR's merge queue entry (PRs without one aren't queued)
+    pub fn set_merge_queue_response(&self, pr_number: u64, entry: MergeQueueEntry) {
+        self.merge_queue_responses
+            .lock()
+            .unwrap()
+            .insert(pr_number, entry);
+    }
+
+    /// Set the response for `get_pr_status` for a specific PR
+    pub fn set_pr_status_response(&self, status: PrStatus) {
+        self.pr_status_responses
+            .lock()
+            .unwrap()
+            .insert(status.pr.number, status);
+    }
+
     // === Call verification methods ===
 
     /// Get all branches that `find_existing_pr` was called with
@@ -131,11 +229,46 @@ impl MockPlatformService {
         self.update_base_calls.lock().unwrap().clone()
     }
 
+    /// Get all `update_pr_metadata` calls
+    pub fn get_update_metadata_calls(&self) -> Vec<UpdateMetadataCall> {
+        self.update_metadata_calls.lock().unwrap().clone()
+    }
+
     /// Get all `create_pr_comment` calls
     pub fn get_create_comment_calls(&self) -> Vec<CreateCommentCall> {
         self.create_comment_calls.lock().unwrap().clone()
     }
 
+    /// Reviewers, assignees and labels added to a PR, in call order
+    pub fn get_added_triage(&self, pr_number: u64) -> PrTriage {
+        self.added_triage
+            .lock()
+            .unwrap()
+            .get(&pr_number)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get all `merge_pr` calls
+    pub fn get_merge_calls(&self) -> Vec<(u64, MergeMethod)> {
+        self.merge_calls.lock().unwrap().clone()
+    }
+
+    /// Get all PR numbers `enqueue_pr` was called with
+    pub fn get_enqueue_calls(&self) -> Vec<u64> {
+        self.enqueue_calls.lock().unwrap().clone()
+    }
+
+    /// Get all PR numbers `close_pr` was called with
+    pub fn get_close_calls(&self) -> Vec<u64> {
+        self.close_calls.lock().unwrap().clone()
+    }
+
+    /// Get all (PR number, method) pairs `enable_auto_merge` was called with
+    pub fn get_auto_merge_calls(&self) -> Vec<(u64, MergeMethod)> {
+        self.auto_merge_calls.lock().unwrap().clone()
+    }
+
     /// Get all `list_pr_comments` calls
     pub fn get_list_comments_calls(&self) -> Vec<u64> {
         self.list_comments_calls.lock().unwrap().clone()
@@ -190,17 +323,24 @@ impl PlatformService for MockPlatformService {
         Ok(responses.get(head_branch).cloned().flatten())
     }
 
+    async fn find_closed_pr(&self, head_branch: &str) -> Result<Option<ClosedPullRequest>> {
+        let responses = self.closed_pr_responses.lock().unwrap();
+        Ok(responses.get(head_branch).cloned())
+    }
+
     async fn create_pr_with_options(
         &self,
         head: &str,
         base: &str,
         title: &str,
+        body: &str,
         draft: bool,
     ) -> Result<PullRequest> {
         self.create_pr_calls.lock().unwrap().push(CreatePrCall {
             head: head.to_string(),
             base: base.to_string(),
             title: title.to_string(),
+            body: body.to_string(),
         });
 
         // Check for injected error
@@ -215,6 +355,7 @@ impl PlatformService for MockPlatformService {
             base_ref: base.to_string(),
             head_ref: head.to_string(),
             title: title.to_string(),
+            body: body.to_string(),
             node_id: Some(format!("PR_node_{number}")),
             is_draft: draft,
         };
@@ -238,6 +379,34 @@ impl PlatformService for MockPlatformService {
             base_ref: new_base.to_string(),
             head_ref: "updated".to_string(),
             title: "Updated PR".to_string(),
+            body: String::new(),
+            node_id: Some(format!("PR_node_{pr_number}")),
+            is_draft: false,
+        })
+    }
+
+    async fn update_pr_metadata(
+        &self,
+        pr_number: u64,
+        title: &str,
+        body: &str,
+    ) -> Result<PullRequest> {
+        self.update_metadata_calls
+            .lock()
+            .unwrap()
+            .push(UpdateMetadataCall {
+                pr_number,
+                title: title.to_string(),
+                body: body.to_string(),
+            });
+
+        Ok(PullRequest {
+            number: pr_number,
+            html_url: format!("https://github.com/test/repo/pull/{pr_number}"),
+            base_ref: "main".to_string(),
+            head_ref: "updated".to_string(),
+            title: title.to_string(),
+            body: body.to_string(),
             node_id: Some(format!("PR_node_{pr_number}")),
             is_draft: false,
         })
@@ -269,6 +438,11 @@ impl PlatformService for MockPlatformService {
         Ok(())
     }
 
+    async fn close_pr(&self, pr_number: u64) -> Result<()> {
+        self.close_calls.lock().unwrap().push(pr_number);
+        Ok(())
+    }
+
     async fn publish_pr(&self, pr_number: u64) -> Result<PullRequest> {
         Ok(PullRequest {
             number: pr_number,
@@ -276,12 +450,138 @@ impl PlatformService for MockPlatformService {
             base_ref: "main".to_string(),
             head_ref: "published".to_string(),
             title: "Published PR".to_string(),
+            body: String::new(),
             node_id: Some(format!("PR_node_{pr_number}")),
             is_draft: false, // After publishing, is_draft is false
         })
     }
 
+    async fn get_pr_status(&self, pr_number: u64) -> Result<PrStatus> {
+        self.pr_status_responses
+            .lock()
+            .unwrap()
+            .get(&pr_number)
+            .cloned()
+            .ok_or_else(|| Error::Platform(format!("no status for PR #{pr_number}")))
+    }
+
+    async fn get_pr_triage(&self, pr_number: u64) -> Result<PrTriage> {
+        Ok(self
+            .triage_responses
+            .lock()
+            .unwrap()
+            .get(&pr_number)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn request_reviewers(&self, pr_number: u64, reviewers: &[String]) -> Result<()> {
+        self.added_triage
+            .lock()
+            .unwrap()
+            .entry(pr_number)
+            .or_default()
+            .reviewers
+            .extend_from_slice(reviewers);
+        Ok(())
+    }
+
+    async fn add_assignees(&self, pr_number: u64, assignees: &[String]) -> Result<()> {
+        self.added_triage
+            .lock()
+            .unwrap()
+            .entry(pr_number)
+            .or_default()
+            .assignees
+            .extend_from_slice(assignees);
+        Ok(())
+    }
+
+    async fn add_labels(&self, pr_number: u64, labels: &[String]) -> Result<()> {
+        self.added_triage
+            .lock()
+            .unwrap()
+            .entry(pr_number)
+            .or_default()
+            .labels
+            .extend_from_slice(labels);
+        Ok(())
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        Ok("mock-user".to_string())
+    }
+
+    async fn get_mergeability(&self, pr_number: u64) -> Result<Mergeability> {
+        Ok(self
+            .mergeability_responses
+            .lock()
+            .unwrap()
+            .get(&pr_number)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn merge_pr(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        self.merge_calls.lock().unwrap().push((pr_number, method));
+        Ok(())
+    }
+
+    async fn get_auto_merge(&self, pr_number: u64) -> Result<Option<MergeMethod>> {
+        Ok(self
+            .auto_merge_responses
+            .lock()
+            .unwrap()
+            .get(&pr_number)
+            .copied())
+    }
+
+    async fn enable_auto_merge(&self, pr_number: u64, method: MergeMethod) -> Result<()> {
+        self.auto_merge_calls
+            .lock()
+            .unwrap()
+            .push((pr_number, method));
+        Ok(())
+    }
+
+    async fn get_merge_queue_entry(&self, pr_number: u64) -> Result<Option<MergeQueueEntry>> {
+        Ok(self
+            .merge_queue_responses
+            .lock()
+            .unwrap()
+            .get(&pr_number)
+            .copied())
+    }
+
+    async fn enqueue_pr(&self, pr_number: u64) -> Result<()> {
+        self.enqueue_calls.lock().unwrap().push(pr_number);
+        self.merge_queue_responses.lock().unwrap().insert(
+            pr_number,
+            MergeQueueEntry {
+                position: None,
+                state: MergeQueueState::Queued,
+            },
+        );
+        Ok(())
+    }
+
+    async fn dequeue_pr(&self, pr_number: u64) -> Result<()> {
+        self.merge_queue_responses
+            .lock()
+            .unwrap()
+            .remove(&pr_number);
+        Ok(())
+    }
+
     fn config(&self) -> &PlatformConfig {
         &self.config
     }
+
+    fn supports_pr_status(&self) -> bool {
+        self.pr_status.load(Ordering::SeqCst)
+    }
+
+    fn supports_stacked_bases(&self) -> bool {
+        self.stacked_bases.load(Ordering::SeqCst)
+    }
 }
@@ -82,6 +82,15 @@ impl TempJjRepo {
         );
     }
 
+    /// Write a file (relative to the repo root) into the working copy
+    #[allow(dead_code)]
+    pub fn write_file(&self, path: &str, content: &str) {
+        let path = self.dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content)
+            .unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+    }
+
     /// Create a bookmark at the current working copy commit
     pub fn create_bookmark(&self, name: &str) {
         let output = Command::new("jj")
@@ -161,6 +170,38 @@ impl TempJjRepo {
         self.run_jj(&["edit", rev]);
     }
 
+    /// Start a new change on top of a revision, e.g. to begin another stack
+    #[allow(dead_code)]
+    pub fn new_on(&self, rev: &str) {
+        self.run_jj(&["new", rev]);
+    }
+
+    /// Start a new change merging several revisions
+    #[allow(dead_code)]
+    pub fn new_merge(&self, revs: &[&str]) {
+        let mut args = vec!["new"];
+        args.extend(revs);
+        self.run_jj(&args);
+    }
+
+    /// Point `trunk()` at a new `main` bookmark on a merge of `revs`, as a
+    /// PR landed with a merge commit leaves it
+    #[allow(dead_code)]
+    pub fn merge_into_trunk(&self, revs: &[&str]) {
+        let mut args = vec!["new"];
+        args.extend(revs);
+        args.extend(["-m", "Merge"]);
+        self.run_jj(&args);
+        self.run_jj(&["bookmark", "create", "main", "-r", "@"]);
+        self.run_jj(&[
+            "config",
+            "set",
+            "--repo",
+            "revset-aliases.\"trunk()\"",
+            "main",
+        ]);
+    }
+
     /// Get the change ID for a bookmark
     #[allow(dead_code)]
     pub fn change_id(&self, bookmark: &str) -> String {
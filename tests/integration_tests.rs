@@ -6,8 +6,11 @@ mod common;
 
 use assert_cmd::Command;
 use common::{MockPlatformService, TempJjRepo, github_config, make_pr};
-use jj_ryu::graph::build_change_graph;
+use jj_ryu::error::Error;
+use jj_ryu::graph::{build_change_graph, build_change_graph_with};
+use jj_ryu::repo::{find_change_id_trailer, gerrit_change_id};
 use jj_ryu::submit::{ExecutionStep, analyze_submission, create_submission_plan};
+use jj_ryu::types::Bookmark;
 use predicates::prelude::*;
 
 // =============================================================================
@@ -243,6 +246,36 @@ fn test_single_bookmark_stack() {
     assert_eq!(analysis.segments[0].bookmark.name, "feat-a");
 }
 
+#[test]
+fn test_changed_files_per_segment() {
+    let repo = TempJjRepo::new();
+    repo.write_file("src/auth.rs", "auth");
+    repo.commit("Add auth");
+    repo.create_bookmark("feat-a");
+    repo.write_file("docs/auth.md", "docs");
+    repo.write_file("src/auth.rs", "auth v2");
+    repo.commit("Document auth");
+    repo.create_bookmark("feat-b");
+
+    let workspace = repo.workspace();
+    let graph = build_change_graph(&workspace).expect("build graph");
+    let analysis = analyze_submission(&graph, Some("feat-b")).expect("analyze");
+
+    let files: Vec<Vec<String>> = analysis
+        .segments
+        .iter()
+        .map(|s| {
+            let first = s.changes.last().unwrap();
+            let last = s.changes.first().unwrap();
+            workspace
+                .changed_files(&first.commit_id, &last.commit_id)
+                .expect("diff")
+        })
+        .collect();
+    assert_eq!(files[0], ["src/auth.rs"]);
+    assert_eq!(files[1], ["docs/auth.md", "src/auth.rs"]);
+}
+
 // =============================================================================
 // Edge Case Tests
 // =============================================================================
@@ -260,6 +293,181 @@ fn test_empty_repo_no_bookmarks() {
     assert!(graph.bookmarks.is_empty());
 }
 
+#[test]
+fn test_graph_finds_other_stacks() {
+    let repo = TempJjRepo::new();
+    repo.build_stack(&[("feat-a", "Add A"), ("feat-b", "Add B")]);
+    // A second stack next to the first, with the working copy on it
+    repo.new_on("feat-a-");
+    repo.build_stack(&[("fix-x", "Fix X")]);
+
+    let workspace = repo.workspace();
+    let mut graph = build_change_graph(&workspace).expect("build graph");
+
+    let stack = graph.stack.as_ref().expect("test expects stack");
+    assert_eq!(stack.leaf_bookmark(), Some("fix-x"));
+    assert_eq!(graph.stacks.len(), 2);
+    assert_eq!(graph.stacks[0].leaf_bookmark(), Some("fix-x"));
+    assert_eq!(graph.stacks[1].leaf_bookmark(), Some("feat-b"));
+
+    assert!(graph.focus("feat-a"));
+    let stack = graph.stack.as_ref().expect("test expects stack");
+    assert_eq!(stack.segments.len(), 2);
+    assert_eq!(stack.segments[0].bookmarks[0].name, "feat-a");
+}
+
+#[test]
+fn test_graph_merges_branching_stack() {
+    let repo = TempJjRepo::new();
+    repo.build_stack(&[("feat-a", "Add A"), ("feat-b", "Add B")]);
+    // A second branch on feat-a, with the working copy on it
+    repo.new_on("feat-a");
+    repo.build_stack(&[("feat-c", "Add C")]);
+
+    let workspace = repo.workspace();
+    let graph = build_change_graph(&workspace).expect("build graph");
+
+    assert_eq!(graph.stacks.len(), 1);
+    let stack = graph.stack.as_ref().expect("test expects stack");
+    let names: Vec<&str> = stack
+        .segments
+        .iter()
+        .map(|s| s.bookmarks[0].name.as_str())
+        .collect();
+    assert_eq!(names, ["feat-a", "feat-c", "feat-b"]);
+    assert_eq!(
+        stack.parents.get("feat-b").map(String::as_str),
+        Some("feat-a")
+    );
+    assert_eq!(
+        stack.parents.get("feat-c").map(String::as_str),
+        Some("feat-a")
+    );
+    assert!(!stack.is_linear());
+}
+
+#[test]
+fn test_graph_reports_merge_of_two_stacks() {
+    let repo = TempJjRepo::new();
+    repo.build_stack(&[("feat-a", "Add A")]);
+    repo.new_on("feat-a-");
+    repo.build_stack(&[("feat-x", "Add X")]);
+    // Join both lines of work, then stack another bookmark on the merge
+    repo.new_merge(&["feat-a", "feat-x"]);
+    repo.build_stack(&[("feat-c", "Add C")]);
+
+    let workspace = repo.workspace();
+    match build_change_graph(&workspace) {
+        Err(Error::MergeCommitDetected(bookmark, _commit)) => assert_eq!(bookmark, "feat-c"),
+        other => panic!("Expected MergeCommitDetected, got: {other:?}"),
+    }
+}
+
+#[test]
+fn test_drop_landed_commits_leaves_trunk_history_alone() {
+    // `ryu land --method merge` put feat-a's commit in trunk's history
+    let repo = TempJjRepo::new();
+    repo.build_stack(&[("feat-a", "Add A"), ("feat-b", "Add B")]);
+    repo.new_on("feat-a-");
+    repo.commit("Other work");
+    repo.merge_into_trunk(&["@-", "feat-a"]);
+
+    let workspace = repo.workspace();
+    let trunk = workspace.resolve_revset("trunk()").expect("resolve trunk")[0].clone();
+    let feat_a = workspace
+        .get_local_bookmark("feat-a")
+        .expect("get bookmark")
+        .expect("feat-a exists");
+
+    let mut workspace = repo.workspace();
+    let rebased = workspace
+        .drop_landed_commits(&[feat_a.commit_id], &["feat-a".to_string()])
+        .expect("drop landed commits");
+    assert_eq!(rebased, 0);
+
+    let workspace = repo.workspace();
+    let main = workspace
+        .get_local_bookmark("main")
+        .expect("get bookmark")
+        .expect("main exists");
+    assert_eq!(main.commit_id, trunk.commit_id);
+    assert!(workspace.get_local_bookmark("feat-a").unwrap().is_none());
+    assert!(workspace.get_local_bookmark("feat-b").unwrap().is_some());
+}
+
+#[test]
+fn test_drop_landed_commits_keeps_working_copy_edits() {
+    let repo = TempJjRepo::new();
+    repo.build_stack(&[("feat-a", "Add A"), ("feat-b", "Add B")]);
+    // Not yet snapshotted by jj
+    repo.write_file("notes.txt", "draft");
+
+    let mut workspace = repo.workspace();
+    let feat_a = workspace
+        .get_local_bookmark("feat-a")
+        .expect("get bookmark")
+        .expect("feat-a exists");
+    workspace
+        .drop_landed_commits(&[feat_a.commit_id], &["feat-a".to_string()])
+        .expect("drop landed commits");
+
+    let workspace = repo.workspace();
+    let wc = workspace.resolve_revset("@").expect("resolve @")[0].clone();
+    let changed = workspace
+        .changed_files(&wc.commit_id, &wc.commit_id)
+        .expect("changed files");
+    assert_eq!(changed, vec!["notes.txt".to_string()]);
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("notes.txt")).unwrap(),
+        "draft"
+    );
+}
+
+#[test]
+fn test_change_id_trailers_keep_existing_ids() {
+    // A commit-msg hook already gave the first commit a Change-Id, which
+    // Gerrit may have a review under
+    let hook_id = "I0123456789abcdef0123456789abcdef01234567";
+    let repo = TempJjRepo::new();
+    repo.commit(&format!("Add A\n\nChange-Id: {hook_id}"));
+    repo.commit("Add B");
+
+    let mut workspace = repo.workspace();
+    let rewritten = workspace
+        .add_change_id_trailers("trunk()..@-")
+        .expect("add trailers");
+    assert_eq!(rewritten, 1);
+
+    let workspace = repo.workspace();
+    let add_a = &workspace.resolve_revset("@--").expect("resolve @--")[0];
+    assert_eq!(find_change_id_trailer(&add_a.description), Some(hook_id));
+    let add_b = &workspace.resolve_revset("@-").expect("resolve @-")[0];
+    assert_eq!(
+        find_change_id_trailer(&add_b.description),
+        Some(gerrit_change_id(&add_b.change_id).as_str())
+    );
+    assert_eq!(
+        workspace
+            .change_id_trailers("trunk()..@")
+            .expect("read trailers")
+            .get(&add_a.change_id)
+            .map(String::as_str),
+        Some(hook_id)
+    );
+}
+
+#[test]
+fn test_change_id_trailers_refuse_undescribed_commits() {
+    let repo = TempJjRepo::new();
+    repo.build_stack(&[("feat-a", "")]);
+
+    let mut workspace = repo.workspace();
+    assert!(matches!(
+        workspace.add_change_id_trailers("trunk()..@"),
+        Err(Error::Workspace(_))
+    ));
+}
+
 #[test]
 fn test_three_level_deep_stack() {
     let repo = TempJjRepo::new();
@@ -281,6 +489,31 @@ fn test_three_level_deep_stack() {
     assert_eq!(stack.segments[2].bookmarks[0].name, "feat-c");
 }
 
+#[test]
+fn test_graph_with_planned_bookmarks() {
+    let repo = TempJjRepo::new();
+    repo.commit("Add A");
+    repo.build_stack(&[("feat-b", "Add B")]);
+
+    let workspace = repo.workspace();
+    let add_a = workspace.resolve_revset("@--").expect("resolve @--")[0].clone();
+    let planned = Bookmark {
+        name: "auto-a".to_string(),
+        commit_id: add_a.commit_id,
+        change_id: add_a.change_id,
+        has_remote: false,
+        is_synced: false,
+    };
+    let graph = build_change_graph_with(&workspace, &[planned]).expect("build graph");
+
+    let stack = graph.stack.as_ref().expect("test expects stack");
+    assert_eq!(stack.segments.len(), 2);
+    assert_eq!(stack.segments[0].bookmarks[0].name, "auto-a");
+    assert_eq!(stack.segments[1].bookmarks[0].name, "feat-b");
+    // Nothing was written to the repo
+    assert!(workspace.get_local_bookmark("auto-a").unwrap().is_none());
+}
+
 #[tokio::test]
 async fn test_plan_verifies_pr_queries_for_stack() {
     let repo = TempJjRepo::new();
@@ -46,15 +46,15 @@ mod analysis_test {
         let analysis = analyze_submission(&graph, Some("feat-c")).unwrap();
 
         assert_eq!(
-            get_base_branch("feat-a", &analysis.segments, "main").unwrap(),
+            get_base_branch("feat-a", &analysis.segments, &analysis.parents, "main").unwrap(),
             "main"
         );
         assert_eq!(
-            get_base_branch("feat-b", &analysis.segments, "main").unwrap(),
+            get_base_branch("feat-b", &analysis.segments, &analysis.parents, "main").unwrap(),
             "feat-a"
         );
         assert_eq!(
-            get_base_branch("feat-c", &analysis.segments, "main").unwrap(),
+            get_base_branch("feat-c", &analysis.segments, &analysis.parents, "main").unwrap(),
             "feat-b"
         );
     }
@@ -164,6 +164,57 @@ mod analysis_test {
     }
 }
 
+mod stacks_test {
+    use crate::common::make_linear_stack;
+    use jj_ryu::types::ChangeGraph;
+
+    /// The working copy's stack of `current`, next to a stack of `other`
+    fn two_stacks(current: &[&str], other: &[&str]) -> ChangeGraph {
+        let mut graph = make_linear_stack(current);
+        let other = make_linear_stack(other);
+        graph.bookmarks.extend(other.bookmarks);
+        graph.stacks.extend(other.stacks);
+        graph
+    }
+
+    #[test]
+    fn test_focus_moves_to_other_stack() {
+        let mut graph = two_stacks(&["feat-a", "feat-b"], &["fix-x", "fix-y"]);
+
+        assert!(graph.focus("fix-x"));
+        let stack = graph.stack.as_ref().unwrap();
+        assert_eq!(stack.leaf_bookmark(), Some("fix-y"));
+        assert_eq!(stack.segments.len(), 2);
+    }
+
+    #[test]
+    fn test_focus_keeps_working_copy_stack() {
+        let mut graph = two_stacks(&["feat-a", "feat-b"], &["feat-a", "feat-c"]);
+
+        // feat-a is in both; the working copy's stack wins
+        assert!(graph.focus("feat-a"));
+        assert_eq!(
+            graph.stack.as_ref().unwrap().leaf_bookmark(),
+            Some("feat-b")
+        );
+
+        // feat-c tops the other stack
+        assert!(graph.focus("feat-c"));
+        assert_eq!(
+            graph.stack.as_ref().unwrap().leaf_bookmark(),
+            Some("feat-c")
+        );
+    }
+
+    #[test]
+    fn test_focus_unknown_bookmark_leaves_no_stack() {
+        let mut graph = two_stacks(&["feat-a"], &["fix-x"]);
+
+        assert!(!graph.focus("nope"));
+        assert!(graph.stack.is_none());
+    }
+}
+
 mod detection_test {
     use jj_ryu::error::Error;
     use jj_ryu::platform::{detect_platform, parse_repo_info};
@@ -262,7 +313,12 @@ mod detection_test {
 
 mod plan_test {
     use crate::common::{MockPlatformService, github_config, make_linear_stack, make_pr};
-    use jj_ryu::submit::{ExecutionStep, analyze_submission, create_submission_plan};
+    use jj_ryu::submit::{
+        ExecutionStep, analyze_submission, create_submission_plan, plan_auto_merge, plan_triage,
+        wrap_generated_body,
+    };
+    use jj_ryu::types::{MergeMethod, PrTriage};
+    use std::collections::HashMap;
 
     #[tokio::test]
     async fn test_plan_new_stack_no_existing_prs() {
@@ -298,6 +354,410 @@ mod plan_test {
         assert_eq!(creates[1].base_branch, "feat-a");
     }
 
+    #[tokio::test]
+    async fn test_plan_serializes_tagged_steps_and_constraints() {
+        let graph = make_linear_stack(&["feat-a", "feat-b"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+        let mock = MockPlatformService::with_config(github_config());
+
+        let plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+        let value = serde_json::to_value(&plan).unwrap();
+
+        let steps = value["execution_steps"].as_array().unwrap();
+        assert_eq!(steps.len(), plan.execution_steps.len());
+        assert_eq!(steps[0]["type"], "push");
+        assert_eq!(steps[0]["name"], "feat-a");
+        assert!(steps.iter().any(|s| s["type"] == "create_pr"));
+
+        let constraints = value["constraints"].as_array().unwrap();
+        assert!(
+            constraints
+                .iter()
+                .any(|c| c["type"] == "push_order" && c["parent"] == "feat-a")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_pr_body_from_descriptions() {
+        let mut graph = make_linear_stack(&["feat-a"]);
+        let segment = &mut graph.stack.as_mut().unwrap().segments[0];
+        segment.changes[0].description =
+            "Commit for feat-a\n\nWhy this change is needed.\n".to_string();
+        let analysis = analyze_submission(&graph, None).unwrap();
+        let mock = MockPlatformService::with_config(github_config());
+
+        let plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+
+        match &plan.execution_steps[..] {
+            [ExecutionStep::Push(_), ExecutionStep::CreatePr(create)] => {
+                assert_eq!(create.title, "Commit for feat-a");
+                assert_eq!(
+                    create.body,
+                    wrap_generated_body("Why this change is needed.")
+                );
+            }
+            steps => panic!("unexpected steps: {steps:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_updates_stale_pr_metadata() {
+        let mut graph = make_linear_stack(&["feat-a", "feat-b"]);
+        let segment = &mut graph.stack.as_mut().unwrap().segments[1];
+        segment.changes[0].description = "Commit for feat-b\n\nNew details.\n".to_string();
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        let mock = MockPlatformService::with_config(github_config());
+        let mut pr_a = make_pr(1, "feat-a", "main");
+        pr_a.title = "Commit for feat-a".to_string();
+        pr_a.body = wrap_generated_body("");
+        let mut pr_b = make_pr(2, "feat-b", "feat-a");
+        pr_b.title = "Commit for feat-b".to_string();
+        pr_b.body = format!(
+            "Hand-written intro.\n\n{}",
+            wrap_generated_body("Old details.")
+        );
+        mock.set_find_pr_response("feat-a", Some(pr_a));
+        mock.set_find_pr_response("feat-b", Some(pr_b));
+
+        let plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+
+        // feat-a is up to date; feat-b's generated section is stale
+        assert_eq!(plan.count_metadata_updates(), 1);
+        let update = plan
+            .execution_steps
+            .iter()
+            .find_map(|s| match s {
+                ExecutionStep::UpdatePrMetadata(u) => Some(u),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(update.bookmark.name, "feat-b");
+        assert!(!update.title_changed());
+        assert_eq!(
+            update.body,
+            format!(
+                "Hand-written intro.\n\n{}",
+                wrap_generated_body("New details.")
+            )
+        );
+
+        // The new commits go up before the description that mentions them
+        let push_idx = plan
+            .execution_steps
+            .iter()
+            .position(|s| matches!(s, ExecutionStep::Push(b) if b.name == "feat-b"));
+        let update_idx = plan
+            .execution_steps
+            .iter()
+            .position(|s| matches!(s, ExecutionStep::UpdatePrMetadata(_)));
+        assert!(push_idx.is_none() || push_idx < update_idx);
+    }
+
+    #[tokio::test]
+    async fn test_plan_leaves_hand_written_body_alone() {
+        let graph = make_linear_stack(&["feat-a"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        // No generated section: the body belongs to a human
+        let mock = MockPlatformService::with_config(github_config());
+        let mut pr = make_pr(1, "feat-a", "main");
+        pr.title = "Commit for feat-a".to_string();
+        pr.body = "Written by hand.".to_string();
+        mock.set_find_pr_response("feat-a", Some(pr));
+
+        let plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+
+        assert_eq!(plan.count_metadata_updates(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_plan_updates_stale_title_without_markers() {
+        let graph = make_linear_stack(&["feat-a"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        // Opened before ryu generated bodies; the commit was reworded since
+        let mock = MockPlatformService::with_config(github_config());
+        let mut pr = make_pr(1, "feat-a", "main");
+        pr.title = "Old title".to_string();
+        pr.body = "Written by hand.".to_string();
+        mock.set_find_pr_response("feat-a", Some(pr));
+
+        let plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+
+        assert_eq!(plan.count_metadata_updates(), 1);
+        let update = plan
+            .execution_steps
+            .iter()
+            .find_map(|s| match s {
+                ExecutionStep::UpdatePrMetadata(u) => Some(u),
+                _ => None,
+            })
+            .unwrap();
+        assert!(update.title_changed());
+        assert_eq!(update.title, "Commit for feat-a");
+        assert_eq!(update.body, "Written by hand.");
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[tokio::test]
+    async fn test_plan_triage_adds_only_missing() {
+        let graph = make_linear_stack(&["feat-a", "feat-b"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        let mock = MockPlatformService::with_config(github_config());
+        mock.set_find_pr_response("feat-a", Some(make_pr(1, "feat-a", "main")));
+        mock.set_triage_response(
+            1,
+            PrTriage {
+                reviewers: names(&["Alice"]),
+                assignees: Vec::new(),
+                labels: names(&["stacked"]),
+            },
+        );
+
+        let mut plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+        let wanted = PrTriage {
+            reviewers: names(&["alice", "acme/backend"]),
+            assignees: names(&["@me"]),
+            labels: names(&["stacked"]),
+        };
+        let wanted: HashMap<String, PrTriage> = [
+            ("feat-a".to_string(), wanted.clone()),
+            ("feat-b".to_string(), wanted),
+        ]
+        .into();
+        plan_triage(&mut plan, &mock, &wanted).await.unwrap();
+
+        let updates: Vec<_> = plan
+            .execution_steps
+            .iter()
+            .filter_map(|s| match s {
+                ExecutionStep::AddTriage(u) => Some(u),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(updates.len(), 2);
+
+        // The existing PR only gets what it doesn't have yet
+        assert_eq!(updates[0].pr_number, Some(1));
+        assert_eq!(
+            updates[0].add,
+            PrTriage {
+                reviewers: names(&["acme/backend"]),
+                assignees: names(&["mock-user"]),
+                labels: Vec::new(),
+            }
+        );
+
+        // The new PR gets everything, after it's created
+        assert_eq!(updates[1].bookmark.name, "feat-b");
+        assert_eq!(updates[1].pr_number, None);
+        assert_eq!(updates[1].add.reviewers, names(&["alice", "acme/backend"]));
+        let create_idx = plan
+            .execution_steps
+            .iter()
+            .position(|s| matches!(s, ExecutionStep::CreatePr(c) if c.bookmark.name == "feat-b"));
+        let triage_idx = plan
+            .execution_steps
+            .iter()
+            .position(|s| matches!(s, ExecutionStep::AddTriage(u) if u.bookmark.name == "feat-b"));
+        assert!(create_idx < triage_idx);
+    }
+
+    #[tokio::test]
+    async fn test_plan_triage_skips_complete_prs() {
+        let graph = make_linear_stack(&["feat-a"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        let mock = MockPlatformService::with_config(github_config());
+        mock.set_find_pr_response("feat-a", Some(make_pr(1, "feat-a", "main")));
+        let triage = PrTriage {
+            reviewers: names(&["alice"]),
+            assignees: Vec::new(),
+            labels: names(&["stacked"]),
+        };
+        mock.set_triage_response(1, triage.clone());
+
+        let mut plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+        let steps_before = plan.execution_steps.len();
+        plan_triage(&mut plan, &mock, &[("feat-a".to_string(), triage)].into())
+            .await
+            .unwrap();
+
+        assert_eq!(plan.count_triage_updates(), 0);
+        assert_eq!(plan.execution_steps.len(), steps_before);
+    }
+
+    #[tokio::test]
+    async fn test_plan_triage_drops_author_from_reviewers() {
+        let graph = make_linear_stack(&["feat-a"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+        let mock = MockPlatformService::with_config(github_config());
+
+        let mut plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+        let wanted = PrTriage {
+            reviewers: names(&["@me", "Mock-User", "alice"]),
+            assignees: names(&["@me"]),
+            labels: Vec::new(),
+        };
+        plan_triage(&mut plan, &mock, &[("feat-a".to_string(), wanted)].into())
+            .await
+            .unwrap();
+
+        let update = plan
+            .execution_steps
+            .iter()
+            .find_map(|s| match s {
+                ExecutionStep::AddTriage(u) => Some(u),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(update.add.reviewers, names(&["alice"]));
+        assert_eq!(update.add.assignees, names(&["mock-user"]));
+    }
+
+    #[tokio::test]
+    async fn test_plan_auto_merge_arms_bottom_pr_only() {
+        let graph = make_linear_stack(&["feat-a", "feat-b"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        let mock = MockPlatformService::with_config(github_config());
+        mock.set_find_pr_response("feat-a", Some(make_pr(1, "feat-a", "main")));
+        mock.set_find_pr_response("feat-b", Some(make_pr(2, "feat-b", "feat-a")));
+
+        let mut plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+        let wanted: HashMap<String, MergeMethod> = [
+            ("feat-a".to_string(), MergeMethod::Squash),
+            ("feat-b".to_string(), MergeMethod::Squash),
+        ]
+        .into();
+        plan_auto_merge(&mut plan, &mock, &wanted).await.unwrap();
+
+        let updates: Vec<_> = plan
+            .execution_steps
+            .iter()
+            .filter_map(|s| match s {
+                ExecutionStep::EnableAutoMerge(u) => Some(u),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].bookmark.name, "feat-a");
+        assert_eq!(updates[0].pr_number, Some(1));
+        assert_eq!(updates[0].method, MergeMethod::Squash);
+    }
+
+    #[tokio::test]
+    async fn test_plan_auto_merge_on_new_bottom_pr() {
+        let graph = make_linear_stack(&["feat-a"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        let mock = MockPlatformService::with_config(github_config());
+        let mut plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+        plan_auto_merge(
+            &mut plan,
+            &mock,
+            &[("feat-a".to_string(), MergeMethod::Rebase)].into(),
+        )
+        .await
+        .unwrap();
+
+        // Armed once the PR exists
+        let create_idx = plan
+            .execution_steps
+            .iter()
+            .position(|s| matches!(s, ExecutionStep::CreatePr(_)));
+        let arm_idx = plan
+            .execution_steps
+            .iter()
+            .position(|s| matches!(s, ExecutionStep::EnableAutoMerge(u) if u.pr_number.is_none()));
+        assert!(arm_idx.is_some());
+        assert!(create_idx < arm_idx);
+    }
+
+    #[tokio::test]
+    async fn test_plan_auto_merge_skips_armed_pr() {
+        let graph = make_linear_stack(&["feat-a"]);
+        let analysis = analyze_submission(&graph, None).unwrap();
+
+        let mock = MockPlatformService::with_config(github_config());
+        mock.set_find_pr_response("feat-a", Some(make_pr(1, "feat-a", "main")));
+        mock.set_auto_merge_response(1, MergeMethod::Merge);
+
+        let mut plan = create_submission_plan(&analysis, &mock, "origin", "main")
+            .await
+            .unwrap();
+        plan_auto_merge(
+            &mut plan,
+            &mock,
+            &[("feat-a".to_string(), MergeMethod::Squash)].into(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.count_auto_merges(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_plan_without_stacked_bases_targets_trunk() {
+        let graph = make_linear_stack(&["feat-a", "feat-b"]);
+        let analysis = analyze_submission(&graph, Some("feat-b")).unwrap();
+
+        // PRs from a fork can't target branches that only exist in the fork
+        let mock = MockPlatformService::with_config(github_config());
+        mock.set_stacked_bases(false);
+        mock.set_find_pr_response("feat-b", Some(make_pr(2, "feat-b", "feat-a")));
+
+        let plan = create_submission_plan(&analysis, &mock, "fork", "main")
+            .await
+            .unwrap();
+
+        let create = plan
+            .execution_steps
+            .iter()
+            .find_map(|s| match s {
+                ExecutionStep::CreatePr(c) => Some(c),
+                _ => None,
+            })
+            .expect("should create feat-a");
+        assert_eq!(create.base_branch, "main");
+
+        let update = plan
+            .execution_steps
+            .iter()
+            .find_map(|s| match s {
+                ExecutionStep::UpdateBase(u) => Some(u),
+                _ => None,
+            })
+            .expect("should retarget feat-b");
+        assert_eq!(update.expected_base, "main");
+        assert_eq!(plan.remote, "fork");
+    }
+
     #[tokio::test]
     async fn test_plan_update_existing_pr_base() {
         let graph = make_linear_stack(&["feat-a", "feat-b"]);
@@ -541,6 +1001,256 @@ mod plan_test {
     }
 }
 
+mod land_test {
+    use crate::common::{MockPlatformService, github_config, make_linear_stack, make_pr};
+    use jj_ryu::error::Error;
+    use jj_ryu::platform::PlatformService;
+    use jj_ryu::submit::{check_merge_queue, ensure_ready, plan_landing};
+    use jj_ryu::types::{CheckStatus, MergeQueueEntry, MergeQueueState, Mergeability};
+
+    fn mock_with_prs(prs: &[(u64, &str, &str)]) -> MockPlatformService {
+        let mock = MockPlatformService::with_config(github_config());
+        for (number, head, base) in prs {
+            mock.set_find_pr_response(head, Some(make_pr(*number, head, base)));
+        }
+        mock
+    }
+
+    #[tokio::test]
+    async fn test_plan_landing_lands_bottom_only_by_default() {
+        let graph = make_linear_stack(&["feat-a", "feat-b", "feat-c"]);
+        let mock = mock_with_prs(&[(1, "feat-a", "main"), (2, "feat-b", "feat-a")]);
+
+        let plan = plan_landing(&graph, &mock, "main", None, false)
+            .await
+            .unwrap();
+
+        assert_eq!(plan.targets.len(), 1);
+        assert_eq!(plan.targets[0].bookmark, "feat-a");
+        assert_eq!(plan.targets[0].commit_ids, vec!["feat-a_commit"]);
+        assert_eq!(plan.targets[0].bookmarks, vec!["feat-a"]);
+        // feat-c has no PR, so it isn't restacked
+        assert_eq!(plan.rest.len(), 1);
+        assert_eq!(plan.next().unwrap().pr.number, 2);
+    }
+
+    #[tokio::test]
+    async fn test_plan_landing_all_up_to_bookmark() {
+        let graph = make_linear_stack(&["feat-a", "feat-b", "feat-c"]);
+        let mock = mock_with_prs(&[
+            (1, "feat-a", "main"),
+            (2, "feat-b", "feat-a"),
+            (3, "feat-c", "feat-b"),
+        ]);
+
+        let plan = plan_landing(&graph, &mock, "main", Some("feat-b"), true)
+            .await
+            .unwrap();
+
+        let targets: Vec<_> = plan.targets.iter().map(|t| t.bookmark.as_str()).collect();
+        assert_eq!(targets, vec!["feat-a", "feat-b"]);
+        assert_eq!(plan.rest[0].bookmark, "feat-c");
+        assert_eq!(plan.next().unwrap().bookmark, "feat-b");
+    }
+
+    #[tokio::test]
+    async fn test_plan_landing_rejects_bookmark_above_bottom_without_all() {
+        let graph = make_linear_stack(&["feat-a", "feat-b"]);
+        let mock = mock_with_prs(&[(1, "feat-a", "main"), (2, "feat-b", "feat-a")]);
+
+        let result = plan_landing(&graph, &mock, "main", Some("feat-b"), false).await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+
+        let result = plan_landing(&graph, &mock, "main", Some("feat-x"), true).await;
+        assert!(matches!(result, Err(Error::BookmarkNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_plan_landing_requires_open_prs() {
+        let graph = make_linear_stack(&["feat-a", "feat-b"]);
+        let mock = mock_with_prs(&[(1, "feat-a", "main")]);
+
+        let result = plan_landing(&graph, &mock, "main", None, true).await;
+        assert!(matches!(result, Err(Error::InvalidArgument(msg)) if msg.contains("feat-b")));
+    }
+
+    #[tokio::test]
+    async fn test_plan_landing_bottom_must_target_trunk() {
+        let graph = make_linear_stack(&["feat-a"]);
+        let mock = mock_with_prs(&[(1, "feat-a", "develop")]);
+
+        let result = plan_landing(&graph, &mock, "main", None, false).await;
+        assert!(matches!(result, Err(Error::NotReadyToLand(1, _))));
+    }
+
+    #[test]
+    fn test_ensure_ready_lists_blockers() {
+        let pr = make_pr(1, "feat-a", "main");
+        let ready = Mergeability {
+            approved: true,
+            checks: CheckStatus::Success,
+            mergeable: Some(true),
+        };
+        assert!(ensure_ready(&pr, &ready).is_ok());
+
+        let blocked = Mergeability {
+            approved: false,
+            checks: CheckStatus::Failure,
+            mergeable: Some(false),
+        };
+        let err = ensure_ready(&pr, &blocked).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "PR #1 is not ready to land: not approved, checks failed, has conflicts"
+        );
+
+        let mut draft = pr;
+        draft.is_draft = true;
+        let err = ensure_ready(&draft, &ready).unwrap_err();
+        assert!(err.to_string().ends_with("is a draft"));
+    }
+
+    #[tokio::test]
+    async fn test_check_merge_queue_reports_removed_and_failing() {
+        let mock = MockPlatformService::with_config(github_config());
+        mock.enqueue_pr(1).await.unwrap();
+        mock.enqueue_pr(2).await.unwrap();
+        mock.set_merge_queue_response(
+            2,
+            MergeQueueEntry {
+                position: Some(1),
+                state: MergeQueueState::Unmergeable,
+            },
+        );
+        mock.enqueue_pr(3).await.unwrap();
+        mock.dequeue_pr(3).await.unwrap();
+        assert_eq!(mock.get_enqueue_calls(), vec![1, 2, 3]);
+
+        let queued = vec![
+            ("feat-a".to_string(), 1),
+            ("feat-b".to_string(), 2),
+            ("feat-c".to_string(), 3),
+        ];
+        let problems = check_merge_queue(&mock, &queued).await.unwrap();
+
+        // feat-a is still queued and healthy
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].bookmark, "feat-b");
+        assert!(!problems[0].was_removed());
+        assert_eq!(problems[1].bookmark, "feat-c");
+        assert_eq!(problems[1].pr_number, 3);
+        assert!(problems[1].was_removed());
+    }
+}
+
+mod status_test {
+    use crate::common::{
+        MockPlatformService, github_config, make_closed_pr, make_linear_stack, make_pr,
+    };
+    use jj_ryu::submit::collect_stack_status;
+    use jj_ryu::types::{ChangeGraph, CheckStatus, PrState, PrStatus, PullRequest, ReviewDecision};
+    use std::collections::HashMap;
+
+    const fn status(pr: PullRequest, state: PrState) -> PrStatus {
+        PrStatus {
+            pr,
+            state,
+            review_decision: ReviewDecision::ReviewRequired,
+            approvals: 0,
+            unresolved_threads: 0,
+            checks: CheckStatus::None,
+        }
+    }
+
+    /// Stack whose bookmarks are pushed, except `unpushed`
+    fn stack(names: &[&str], unpushed: &[&str]) -> ChangeGraph {
+        let mut graph = make_linear_stack(names);
+        for bookmark in graph.bookmarks.values_mut() {
+            bookmark.has_remote = !unpushed.contains(&bookmark.name.as_str());
+        }
+        graph
+    }
+
+    #[tokio::test]
+    async fn test_collect_stack_status_finds_each_pr() {
+        let graph = stack(&["feat-a", "feat-b", "feat-c", "feat-d"], &["feat-d"]);
+        let mock = MockPlatformService::with_config(github_config());
+
+        // feat-a: merged, known from the cache; feat-b: open; feat-c: closed
+        mock.set_pr_status_response(status(make_pr(1, "feat-a", "main"), PrState::Merged));
+        mock.set_find_pr_response("feat-b", Some(make_pr(2, "feat-b", "main")));
+        let mut open = status(make_pr(2, "feat-b", "main"), PrState::Open);
+        open.approvals = 1;
+        open.checks = CheckStatus::Pending;
+        mock.set_pr_status_response(open);
+        mock.set_closed_pr_response("feat-c", make_closed_pr(3, "feat-c", "feat-b", false));
+        mock.set_pr_status_response(status(make_pr(3, "feat-c", "feat-b"), PrState::Closed));
+
+        let known: HashMap<String, u64> = [("feat-a".to_string(), 1)].into();
+        let statuses = collect_stack_status(&graph, &mock, &known).await.unwrap();
+
+        let summary: Vec<_> = statuses
+            .iter()
+            .map(|s| {
+                (
+                    s.bookmark.as_str(),
+                    s.pr.as_ref().map(|p| (p.pr.number, p.state)),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("feat-a", Some((1, PrState::Merged))),
+                ("feat-b", Some((2, PrState::Open))),
+                ("feat-c", Some((3, PrState::Closed))),
+                ("feat-d", None),
+            ]
+        );
+        let open = statuses[1].pr.as_ref().unwrap();
+        assert_eq!(open.approvals, 1);
+        assert_eq!(open.checks, CheckStatus::Pending);
+
+        // Unpushed bookmarks aren't looked up on the platform
+        assert!(!mock.get_find_pr_calls().contains(&"feat-d".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_open_pr_wins_over_cached_number() {
+        let graph = stack(&["feat-a"], &[]);
+        let mock = MockPlatformService::with_config(github_config());
+        mock.set_find_pr_response("feat-a", Some(make_pr(5, "feat-a", "main")));
+        mock.set_pr_status_response(status(make_pr(5, "feat-a", "main"), PrState::Open));
+
+        // The cache still points at an older PR for the same bookmark
+        let known: HashMap<String, u64> = [("feat-a".to_string(), 1)].into();
+        let statuses = collect_stack_status(&graph, &mock, &known).await.unwrap();
+
+        assert_eq!(statuses[0].pr.as_ref().unwrap().pr.number, 5);
+    }
+
+    #[tokio::test]
+    async fn test_state_only_without_pr_status() {
+        let graph = stack(&["feat-a", "feat-b", "feat-c"], &["feat-c"]);
+        let mock = MockPlatformService::with_config(github_config());
+        mock.set_pr_status_supported(false);
+        mock.set_closed_pr_response("feat-a", make_closed_pr(1, "feat-a", "main", true));
+        mock.set_find_pr_response("feat-b", Some(make_pr(2, "feat-b", "main")));
+
+        let statuses = collect_stack_status(&graph, &mock, &HashMap::new())
+            .await
+            .unwrap();
+
+        let merged = statuses[0].pr.as_ref().unwrap();
+        assert_eq!((merged.pr.number, merged.state), (1, PrState::Merged));
+        let open = statuses[1].pr.as_ref().unwrap();
+        assert_eq!((open.pr.number, open.state), (2, PrState::Open));
+        assert_eq!(open.review_decision, ReviewDecision::Unknown);
+        assert_eq!(open.checks, CheckStatus::Unknown);
+        assert!(statuses[2].pr.is_none());
+    }
+}
+
 mod stack_comment_test {
     use jj_ryu::submit::{
         COMMENT_DATA_PREFIX, STACK_COMMENT_THIS_PR, StackCommentData, StackItem, SubmissionPlan,
@@ -566,6 +1276,7 @@ mod stack_comment_test {
             base_ref: "main".to_string(),
             head_ref: bookmark.to_string(),
             title: format!("PR for {bookmark}"),
+            body: String::new(),
             node_id: Some(format!("PR_node_{number}")),
             is_draft: false,
         }
@@ -577,6 +1288,7 @@ mod stack_comment_test {
             pr_url: format!("https://github.com/test/test/pull/{number}"),
             pr_number: number,
             pr_title: format!("feat: {name}"),
+            parent: None,
         }
     }
 
@@ -587,10 +1299,12 @@ mod stack_comment_test {
                 bookmark: make_bookmark("feat-a"),
                 changes: vec![],
             }],
+            parents: HashMap::new(),
             constraints: vec![],
             execution_steps: vec![],
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
@@ -623,10 +1337,12 @@ mod stack_comment_test {
                     changes: vec![],
                 },
             ],
+            parents: HashMap::new(),
             constraints: vec![],
             execution_steps: vec![],
             existing_prs: HashMap::new(),
             remote: "origin".to_string(),
+            pr_remote: None,
             default_branch: "main".to_string(),
         };
 
@@ -803,4 +1519,265 @@ mod sync_test {
             other => panic!("Expected NoSupportedRemotes error, got: {other:?}"),
         }
     }
+
+    mod finished_prs {
+        use crate::common::{
+            MockPlatformService, github_config, make_closed_pr, make_linear_stack, make_pr,
+        };
+        use chrono::{Duration, Utc};
+        use jj_ryu::submit::find_finished_prs;
+        use jj_ryu::tracking::TrackedBookmark;
+        use jj_ryu::types::ChangeGraph;
+
+        /// Linear stack whose bookmarks have all been pushed
+        fn pushed_stack(names: &[&str]) -> ChangeGraph {
+            let mut graph = make_linear_stack(names);
+            for bookmark in graph.bookmarks.values_mut() {
+                bookmark.has_remote = true;
+            }
+            graph
+        }
+
+        fn tracked(names: &[&str]) -> Vec<TrackedBookmark> {
+            names
+                .iter()
+                .map(|name| TrackedBookmark::new((*name).to_string(), format!("{name}_change")))
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_squash_merged_bottom_lands() {
+            let graph = pushed_stack(&["feat-a", "feat-b"]);
+            let tracked = tracked(&["feat-a", "feat-b"]);
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_closed_pr_response("feat-a", make_closed_pr(1, "feat-a", "main", true));
+            mock.set_find_pr_response("feat-b", Some(make_pr(2, "feat-b", "feat-a")));
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert_eq!(finished.landed.len(), 1);
+            assert_eq!(finished.landed[0].bookmark, "feat-a");
+            assert_eq!(finished.landed_commits, vec!["feat-a_commit"]);
+            assert_eq!(finished.deleted_bookmarks, vec!["feat-a"]);
+            assert_eq!(finished.untrack_names(), vec!["feat-a"]);
+        }
+
+        #[tokio::test]
+        async fn test_bookmark_deleted_by_fetch_found_by_change() {
+            // The remote branch was deleted on merge, and the fetch took the
+            // local bookmark with it; its commit is still in the stack
+            let mut graph = pushed_stack(&["feat-a", "feat-b"]);
+            graph.bookmarks.remove("feat-a");
+            let tracked = tracked(&["feat-a", "feat-b"]);
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_closed_pr_response("feat-a", make_closed_pr(1, "feat-a", "main", true));
+            mock.set_find_pr_response("feat-b", Some(make_pr(2, "feat-b", "feat-a")));
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert_eq!(finished.landed.len(), 1);
+            assert_eq!(finished.landed_commits, vec!["feat-a_commit"]);
+            assert!(finished.deleted_bookmarks.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_merge_above_open_pr_is_skipped() {
+            let graph = pushed_stack(&["feat-a", "feat-b"]);
+            let tracked = tracked(&["feat-a", "feat-b"]);
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_find_pr_response("feat-a", Some(make_pr(1, "feat-a", "main")));
+            mock.set_closed_pr_response("feat-b", make_closed_pr(2, "feat-b", "main", true));
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert!(finished.landed.is_empty());
+            assert_eq!(finished.skipped.len(), 1);
+            assert!(finished.landed_commits.is_empty());
+            assert!(finished.untrack_names().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_closed_pr_is_untracked_and_blocks_landing() {
+            let graph = pushed_stack(&["feat-a", "feat-b"]);
+            let tracked = tracked(&["feat-a", "feat-b"]);
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_closed_pr_response("feat-a", make_closed_pr(1, "feat-a", "main", false));
+            mock.set_closed_pr_response("feat-b", make_closed_pr(2, "feat-b", "main", true));
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert!(finished.landed.is_empty());
+            assert_eq!(finished.untracked.len(), 1);
+            assert_eq!(finished.untracked[0].bookmark, "feat-a");
+            assert_eq!(finished.skipped.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_merge_into_other_branch_does_not_land() {
+            let graph = pushed_stack(&["feat-a"]);
+            let tracked = tracked(&["feat-a"]);
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_closed_pr_response("feat-a", make_closed_pr(1, "feat-a", "release", true));
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert!(finished.landed.is_empty());
+            assert_eq!(finished.skipped.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_pr_closed_before_tracking_is_ignored() {
+            // An old PR from an earlier branch with the same name
+            let graph = pushed_stack(&["feat-a"]);
+            let tracked = tracked(&["feat-a"]);
+            let mock = MockPlatformService::with_config(github_config());
+            let mut old = make_closed_pr(1, "feat-a", "main", true);
+            old.closed_at = Some(Utc::now() - Duration::days(30));
+            mock.set_closed_pr_response("feat-a", old);
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert!(finished.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_sibling_branch_is_not_dropped() {
+            // feat-d and feat-c both build on feat-a; feat-c was retargeted
+            // to trunk and merged after feat-a, feat-d was never submitted
+            let mut graph = pushed_stack(&["feat-a", "feat-d", "feat-c"]);
+            for stack in graph.stack.iter_mut().chain(&mut graph.stacks) {
+                stack
+                    .parents
+                    .insert("feat-c".to_string(), "feat-a".to_string());
+            }
+            let tracked = tracked(&["feat-a", "feat-c"]);
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_closed_pr_response("feat-a", make_closed_pr(1, "feat-a", "main", true));
+            mock.set_closed_pr_response("feat-c", make_closed_pr(3, "feat-c", "main", true));
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert_eq!(finished.landed.len(), 2);
+            assert_eq!(
+                finished.landed_commits,
+                vec!["feat-a_commit", "feat-c_commit"]
+            );
+            assert_eq!(finished.deleted_bookmarks, vec!["feat-a", "feat-c"]);
+        }
+
+        #[tokio::test]
+        async fn test_untracked_bookmark_blocks_landing_above_it() {
+            let graph = pushed_stack(&["feat-a", "feat-u", "feat-b"]);
+            let tracked = tracked(&["feat-a", "feat-b"]);
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_closed_pr_response("feat-a", make_closed_pr(1, "feat-a", "main", true));
+            mock.set_closed_pr_response("feat-b", make_closed_pr(2, "feat-b", "main", true));
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert_eq!(finished.landed.len(), 1);
+            assert_eq!(finished.landed_commits, vec!["feat-a_commit"]);
+            assert_eq!(finished.skipped.len(), 1);
+
+            // Once its own PR has merged, it no longer does
+            mock.set_closed_pr_response("feat-u", make_closed_pr(3, "feat-u", "feat-a", true));
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+            assert_eq!(finished.landed.len(), 1);
+
+            mock.set_closed_pr_response("feat-u", make_closed_pr(3, "feat-u", "main", true));
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+            assert_eq!(finished.landed.len(), 2);
+        }
+
+        #[tokio::test]
+        async fn test_unpushed_bookmarks_are_not_looked_up() {
+            let graph = make_linear_stack(&["feat-a"]);
+            let tracked = tracked(&["feat-a"]);
+            let mock = MockPlatformService::with_config(github_config());
+
+            let finished = find_finished_prs(&graph, &tracked, &mock, "main")
+                .await
+                .unwrap();
+
+            assert!(finished.is_empty());
+            assert!(mock.get_find_pr_calls().is_empty());
+        }
+    }
+
+    mod orphaned_prs {
+        use crate::common::{MockPlatformService, github_config, make_pr};
+        use jj_ryu::submit::close_orphaned_prs;
+        use jj_ryu::tracking::TrackingState;
+        use std::collections::HashSet;
+
+        fn tracking() -> TrackingState {
+            let mut tracking = TrackingState::new();
+            tracking.track_change("kept", "alice/kept");
+            tracking.track_change("squashed", "alice/squashed");
+            tracking.track_change("landed", "alice/landed");
+            tracking
+        }
+
+        #[tokio::test]
+        async fn test_gone_changes_prs_are_closed() {
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_find_pr_response("alice/kept", Some(make_pr(1, "alice/kept", "main")));
+            mock.set_find_pr_response(
+                "alice/squashed",
+                Some(make_pr(2, "alice/squashed", "alice/kept")),
+            );
+            // The landed change's PR merged, so it has no open PR
+            let live = HashSet::from(["kept".to_string()]);
+
+            let orphaned = close_orphaned_prs(&mock, &tracking().changes, &live, false)
+                .await
+                .unwrap();
+
+            let gone: Vec<_> = orphaned
+                .iter()
+                .map(|o| o.change.change_id.as_str())
+                .collect();
+            assert_eq!(gone, ["squashed", "landed"]);
+            assert_eq!(orphaned[0].pr.as_ref().map(|pr| pr.number), Some(2));
+            assert!(orphaned[1].pr.is_none());
+            assert_eq!(mock.get_close_calls(), vec![2]);
+        }
+
+        #[tokio::test]
+        async fn test_dry_run_closes_nothing() {
+            let mock = MockPlatformService::with_config(github_config());
+            mock.set_find_pr_response(
+                "alice/squashed",
+                Some(make_pr(2, "alice/squashed", "alice/kept")),
+            );
+            let live = HashSet::from(["kept".to_string(), "landed".to_string()]);
+
+            let orphaned = close_orphaned_prs(&mock, &tracking().changes, &live, true)
+                .await
+                .unwrap();
+
+            assert_eq!(orphaned.len(), 1);
+            assert!(mock.get_close_calls().is_empty());
+        }
+    }
 }